@@ -0,0 +1,639 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Extracts the ABI of generated bindings and reports how it changes between JDK releases.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write as _};
+
+use anyhow::Context;
+use quote::ToTokens;
+use serde::Serialize;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ForeignItem, Item, Lit, Stmt, Token};
+
+/// Constants, structs and functions of the bindings generated from one release.
+#[derive(Debug, Default)]
+pub struct AbiSnapshot {
+    constants: BTreeMap<String, i64>,
+    structs: BTreeMap<String, StructLayout>,
+    functions: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StructLayout {
+    pub size: Option<u64>,
+    pub align: Option<u64>,
+    pub fields: Vec<FieldLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldLayout {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub offset: Option<u64>,
+    /// Whether the field is an entry of a function table such as `JAWT` or `JAWT_DrawingSurface`.
+    pub function: bool,
+}
+
+impl AbiSnapshot {
+    /// Parses bindings produced by the generator, including the layout tests bindgen emits.
+    pub fn parse(bindings: &str) -> anyhow::Result<Self> {
+        let file = syn::parse_file(bindings).with_context(|| "failed to parse the bindings")?;
+        let mut snapshot = Self::default();
+        let mut layout_asserts = Vec::new();
+
+        for item in file.items {
+            match item {
+                Item::Const(item) => {
+                    if let Expr::Lit(expr) = &*item.expr {
+                        if let Lit::Int(value) = &expr.lit {
+                            snapshot
+                                .constants
+                                .insert(item.ident.to_string(), value.base10_parse()?);
+                        }
+                    } else if let Expr::Unary(expr) = &*item.expr {
+                        // Negative constants such as `-2147483648`
+                        if let Expr::Lit(inner) = &*expr.expr {
+                            if let Lit::Int(value) = &inner.lit {
+                                snapshot
+                                    .constants
+                                    .insert(item.ident.to_string(), -value.base10_parse::<i64>()?);
+                            }
+                        }
+                    }
+                }
+                Item::Struct(item) => {
+                    let fields = item
+                        .fields
+                        .iter()
+                        .filter_map(|field| {
+                            let ty = type_to_string(&field.ty);
+                            Some(FieldLayout {
+                                name: field.ident.as_ref()?.to_string(),
+                                function: ty.contains("fn("),
+                                ty,
+                                offset: None,
+                            })
+                        })
+                        .collect();
+                    snapshot.structs.insert(
+                        item.ident.to_string(),
+                        StructLayout {
+                            size: None,
+                            align: None,
+                            fields,
+                        },
+                    );
+                }
+                Item::ForeignMod(item) => {
+                    for item in item.items {
+                        if let ForeignItem::Fn(item) = item {
+                            snapshot.functions.insert(item.sig.ident.to_string());
+                        }
+                    }
+                }
+                // Only the 64-bit layout is reported when the bindings carry both.
+                Item::Fn(item) if !is_32_bit_only(&item.attrs) => {
+                    for stmt in item.block.stmts {
+                        if let Stmt::Macro(stmt) = stmt {
+                            if stmt.mac.path.is_ident("assert_eq") {
+                                layout_asserts.push(stmt.mac.parse_body_with(
+                                    Punctuated::<Expr, Token![,]>::parse_terminated,
+                                )?);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for args in layout_asserts {
+            let (Some(Expr::Lit(value)), Some(Expr::Lit(message))) = (args.get(1), args.get(2))
+            else {
+                continue;
+            };
+            let (Lit::Int(value), Lit::Str(message)) = (&value.lit, &message.lit) else {
+                continue;
+            };
+            let value = value.base10_parse::<u64>()?;
+            let message = message.value();
+            if let Some(name) = message.strip_prefix("Size of ") {
+                if let Some(layout) = snapshot.structs.get_mut(name) {
+                    layout.size = Some(value);
+                }
+            } else if let Some(name) = message.strip_prefix("Alignment of ") {
+                if let Some(layout) = snapshot.structs.get_mut(name) {
+                    layout.align = Some(value);
+                }
+            } else if let Some(path) = message.strip_prefix("Offset of field: ") {
+                let Some((name, field)) = path.split_once("::") else {
+                    continue;
+                };
+                if let Some(field) = snapshot
+                    .structs
+                    .get_mut(name)
+                    .and_then(|layout| layout.fields.iter_mut().find(|f| f.name == field))
+                {
+                    field.offset = Some(value);
+                }
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+fn type_to_string(ty: &syn::Type) -> String {
+    let mut ty = ty.to_token_stream().to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        ("( ", "("),
+        (" (", "("),
+        (" )", ")"),
+        (" ,", ","),
+        ("* mut", "*mut"),
+        ("* const", "*const"),
+        (" : ", ": "),
+        ("*mut::", "*mut ::"),
+        ("*const::", "*const ::"),
+        (",>", ">"),
+        (",)", ")"),
+    ] {
+        ty = ty.replace(from, to);
+    }
+    ty
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Constant {
+    pub name: String,
+    pub value: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstantChange {
+    pub name: String,
+    pub from: i64,
+    pub to: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub from: FieldLayout,
+    pub to: FieldLayout,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StructChange {
+    pub name: String,
+    pub size: (Option<u64>, Option<u64>),
+    pub align: (Option<u64>, Option<u64>),
+    pub added_fields: Vec<FieldLayout>,
+    pub removed_fields: Vec<FieldLayout>,
+    pub changed_fields: Vec<FieldChange>,
+}
+
+/// Differences between the bindings of two consecutive releases.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseChanges {
+    pub from: String,
+    pub to: String,
+    pub added_constants: Vec<Constant>,
+    pub removed_constants: Vec<Constant>,
+    pub changed_constants: Vec<ConstantChange>,
+    pub added_structs: Vec<String>,
+    pub removed_structs: Vec<String>,
+    pub changed_structs: Vec<StructChange>,
+    pub added_functions: Vec<String>,
+    pub removed_functions: Vec<String>,
+}
+
+impl ReleaseChanges {
+    fn between(from: (&str, &AbiSnapshot), to: (&str, &AbiSnapshot)) -> Self {
+        let (from_name, from) = from;
+        let (to_name, to) = to;
+
+        let constant = |(name, value): (&String, &i64)| Constant {
+            name: name.clone(),
+            value: *value,
+        };
+        let added_constants = to
+            .constants
+            .iter()
+            .filter(|(name, _)| !from.constants.contains_key(*name))
+            .map(constant)
+            .collect();
+        let removed_constants = from
+            .constants
+            .iter()
+            .filter(|(name, _)| !to.constants.contains_key(*name))
+            .map(constant)
+            .collect();
+        let changed_constants = from
+            .constants
+            .iter()
+            .filter_map(|(name, value)| {
+                let new_value = to.constants.get(name)?;
+                (new_value != value).then(|| ConstantChange {
+                    name: name.clone(),
+                    from: *value,
+                    to: *new_value,
+                })
+            })
+            .collect();
+
+        let added_structs = to
+            .structs
+            .keys()
+            .filter(|name| !from.structs.contains_key(*name))
+            .cloned()
+            .collect();
+        let removed_structs = from
+            .structs
+            .keys()
+            .filter(|name| !to.structs.contains_key(*name))
+            .cloned()
+            .collect();
+        let changed_structs = from
+            .structs
+            .iter()
+            .filter_map(|(name, layout)| {
+                let new_layout = to.structs.get(name)?;
+                (new_layout != layout).then(|| StructChange::between(name, layout, new_layout))
+            })
+            .collect();
+
+        Self {
+            from: from_name.to_string(),
+            to: to_name.to_string(),
+            added_constants,
+            removed_constants,
+            changed_constants,
+            added_structs,
+            removed_structs,
+            changed_structs,
+            added_functions: to.functions.difference(&from.functions).cloned().collect(),
+            removed_functions: from.functions.difference(&to.functions).cloned().collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added_constants.is_empty()
+            && self.removed_constants.is_empty()
+            && self.changed_constants.is_empty()
+            && self.added_structs.is_empty()
+            && self.removed_structs.is_empty()
+            && self.changed_structs.is_empty()
+            && self.added_functions.is_empty()
+            && self.removed_functions.is_empty()
+    }
+}
+
+impl StructChange {
+    fn between(name: &str, from: &StructLayout, to: &StructLayout) -> Self {
+        let field = |fields: &[FieldLayout], name: &str| {
+            fields.iter().find(|field| field.name == name).cloned()
+        };
+        Self {
+            name: name.to_string(),
+            size: (from.size, to.size),
+            align: (from.align, to.align),
+            added_fields: to
+                .fields
+                .iter()
+                .filter(|f| field(&from.fields, &f.name).is_none())
+                .cloned()
+                .collect(),
+            removed_fields: from
+                .fields
+                .iter()
+                .filter(|f| field(&to.fields, &f.name).is_none())
+                .cloned()
+                .collect(),
+            changed_fields: from
+                .fields
+                .iter()
+                .filter_map(|f| {
+                    let new_field = field(&to.fields, &f.name)?;
+                    (&new_field != f).then(|| FieldChange {
+                        from: f.clone(),
+                        to: new_field,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// ABI changes of one platform across the requested releases, in order.
+#[derive(Debug, Clone, Serialize)]
+pub struct AbiReport {
+    pub platform: String,
+    pub releases: Vec<String>,
+    pub changes: Vec<ReleaseChanges>,
+}
+
+impl AbiReport {
+    pub fn new(platform: &str, snapshots: &[(String, AbiSnapshot)]) -> Self {
+        Self {
+            platform: platform.to_string(),
+            releases: snapshots.iter().map(|(name, _)| name.clone()).collect(),
+            changes: snapshots
+                .windows(2)
+                .map(|pair| {
+                    ReleaseChanges::between((&pair[0].0, &pair[0].1), (&pair[1].0, &pair[1].1))
+                })
+                .collect(),
+        }
+    }
+}
+
+fn write_optional(f: &mut fmt::Formatter<'_>, value: Option<u64>) -> fmt::Result {
+    match value {
+        Some(value) => write!(f, "{value}"),
+        None => write!(f, "?"),
+    }
+}
+
+impl fmt::Display for FieldLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} @ ", self.name, self.ty)?;
+        write_optional(f, self.offset)
+    }
+}
+
+impl fmt::Display for AbiReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# {} ({})", self.platform, self.releases.join(" -> "))?;
+        for changes in &self.changes {
+            writeln!(f)?;
+            writeln!(f, "## {} -> {}", changes.from, changes.to)?;
+            if changes.is_empty() {
+                writeln!(f, "No changes.")?;
+                continue;
+            }
+            for constant in &changes.added_constants {
+                writeln!(f, "+ const {} = {}", constant.name, constant.value)?;
+            }
+            for constant in &changes.removed_constants {
+                writeln!(f, "- const {} = {}", constant.name, constant.value)?;
+            }
+            for constant in &changes.changed_constants {
+                writeln!(
+                    f,
+                    "~ const {}: {} -> {}",
+                    constant.name, constant.from, constant.to
+                )?;
+            }
+            for name in &changes.added_structs {
+                writeln!(f, "+ struct {name}")?;
+            }
+            for name in &changes.removed_structs {
+                writeln!(f, "- struct {name}")?;
+            }
+            for change in &changes.changed_structs {
+                let mut line = String::new();
+                for (label, (from, to)) in [("size", change.size), ("align", change.align)] {
+                    if from != to {
+                        let from = from.map_or("?".to_string(), |value| value.to_string());
+                        let to = to.map_or("?".to_string(), |value| value.to_string());
+                        write!(line, " {label} {from} -> {to}")?;
+                    }
+                }
+                writeln!(f, "~ struct {}{line}", change.name)?;
+                for field in &change.added_fields {
+                    let kind = if field.function { "fn " } else { "" };
+                    writeln!(f, "  + {kind}{field}")?;
+                }
+                for field in &change.removed_fields {
+                    let kind = if field.function { "fn " } else { "" };
+                    writeln!(f, "  - {kind}{field}")?;
+                }
+                for field in &change.changed_fields {
+                    writeln!(f, "  ~ {} -> {}", field.from, field.to)?;
+                }
+            }
+            for name in &changes.added_functions {
+                writeln!(f, "+ fn {name}")?;
+            }
+            for name in &changes.removed_functions {
+                writeln!(f, "- fn {name}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `attrs` contain `#[cfg(target_pointer_width = "32")]`.
+fn is_32_bit_only(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .meta
+                .to_token_stream()
+                .to_string()
+                .contains("target_pointer_width = \"32\"")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JDK_8: &str = r#"
+pub const JAWT_VERSION_1_4: u32 = 65540;
+pub const JAWT_LOCK_ERROR: i32 = 1;
+pub const JAWT_MACOSX_USE_CALAYER: i32 = -2147483648;
+pub type jint = i32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct JAWT {
+    pub version: jint,
+    pub GetDrawingSurface: ::std::option::Option<unsafe extern "system" fn(env: *mut JNIEnv) -> *mut JAWT_DrawingSurface>,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of JAWT"][::std::mem::size_of::<JAWT>() - 16usize];
+};
+#[test]
+fn bindgen_test_layout_JAWT() {
+    assert_eq!(::std::mem::size_of::<JAWT>(), 16usize, "Size of JAWT");
+    assert_eq!(::std::mem::align_of::<JAWT>(), 8usize, "Alignment of JAWT");
+    assert_eq!(unsafe { 0 }, 0usize, "Offset of field: JAWT::version");
+    assert_eq!(unsafe { 8 }, 8usize, "Offset of field: JAWT::GetDrawingSurface");
+}
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn bindgen_test_layout_JAWT_32() {
+    assert_eq!(::std::mem::size_of::<JAWT>(), 8usize, "Size of JAWT");
+}
+extern "system" {
+    pub fn JAWT_GetAWT(env: *mut JNIEnv, awt: *mut JAWT) -> jboolean;
+}
+"#;
+
+    const JDK_9: &str = r#"
+pub const JAWT_VERSION_1_4: u32 = 65540;
+pub const JAWT_VERSION_9: u32 = 589824;
+pub const JAWT_LOCK_ERROR: i32 = 2;
+pub type jint = i32;
+#[repr(C)]
+pub struct JAWT {
+    pub version: jint,
+    pub GetDrawingSurface: ::std::option::Option<unsafe extern "system" fn(env: *mut JNIEnv) -> *mut JAWT_DrawingSurface>,
+    pub Lock: ::std::option::Option<unsafe extern "system" fn(env: *mut JNIEnv)>,
+}
+#[repr(C)]
+pub struct JAWT_Rectangle {
+    pub x: jint,
+}
+#[test]
+fn bindgen_test_layout_JAWT() {
+    assert_eq!(::std::mem::size_of::<JAWT>(), 24usize, "Size of JAWT");
+    assert_eq!(::std::mem::align_of::<JAWT>(), 8usize, "Alignment of JAWT");
+    assert_eq!(unsafe { 0 }, 0usize, "Offset of field: JAWT::version");
+    assert_eq!(unsafe { 8 }, 8usize, "Offset of field: JAWT::GetDrawingSurface");
+    assert_eq!(unsafe { 16 }, 16usize, "Offset of field: JAWT::Lock");
+}
+extern "system" {
+    pub fn JAWT_GetAWT(env: *mut JNIEnv, awt: *mut JAWT) -> jboolean;
+    pub fn JAWT_Other(env: *mut JNIEnv);
+}
+"#;
+
+    fn names(constants: &[Constant]) -> Vec<(&str, i64)> {
+        constants
+            .iter()
+            .map(|constant| (constant.name.as_str(), constant.value))
+            .collect()
+    }
+
+    fn snapshots() -> Vec<(String, AbiSnapshot)> {
+        vec![
+            ("jdk8".to_string(), AbiSnapshot::parse(JDK_8).unwrap()),
+            ("jdk9".to_string(), AbiSnapshot::parse(JDK_9).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn parse_collects_constants_structs_and_functions() {
+        let snapshot = AbiSnapshot::parse(JDK_8).unwrap();
+        assert_eq!(
+            snapshot.constants,
+            BTreeMap::from([
+                ("JAWT_LOCK_ERROR".to_string(), 1),
+                ("JAWT_MACOSX_USE_CALAYER".to_string(), -2147483648),
+                ("JAWT_VERSION_1_4".to_string(), 65540),
+            ])
+        );
+        assert_eq!(
+            snapshot.functions,
+            BTreeSet::from(["JAWT_GetAWT".to_string()])
+        );
+
+        let layout = &snapshot.structs["JAWT"];
+        assert_eq!(layout.size, Some(16));
+        assert_eq!(layout.align, Some(8));
+        assert_eq!(
+            layout.fields,
+            [
+                FieldLayout {
+                    name: "version".to_string(),
+                    ty: "jint".to_string(),
+                    offset: Some(0),
+                    function: false,
+                },
+                FieldLayout {
+                    name: "GetDrawingSurface".to_string(),
+                    ty: "::std::option::Option<unsafe extern \"system\" fn(env: *mut JNIEnv) -> *mut JAWT_DrawingSurface>".to_string(),
+                    offset: Some(8),
+                    function: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_bindings() {
+        assert!(AbiSnapshot::parse("pub struct JAWT {").is_err());
+    }
+
+    #[test]
+    fn report_lists_changes_between_consecutive_releases() {
+        let report = AbiReport::new("linux", &snapshots());
+        assert_eq!(report.releases, ["jdk8", "jdk9"]);
+        let [changes] = &report.changes[..] else {
+            panic!("expected one pair of releases");
+        };
+
+        assert_eq!(
+            names(&changes.added_constants),
+            [("JAWT_VERSION_9", 589824)]
+        );
+        assert_eq!(
+            names(&changes.removed_constants),
+            [("JAWT_MACOSX_USE_CALAYER", -2147483648)]
+        );
+        let [changed] = &changes.changed_constants[..] else {
+            panic!("expected one changed constant");
+        };
+        assert_eq!(
+            (changed.name.as_str(), changed.from, changed.to),
+            ("JAWT_LOCK_ERROR", 1, 2)
+        );
+
+        assert_eq!(changes.added_structs, ["JAWT_Rectangle"]);
+        assert!(changes.removed_structs.is_empty());
+        let [change] = &changes.changed_structs[..] else {
+            panic!("expected one changed struct");
+        };
+        assert_eq!(change.name, "JAWT");
+        assert_eq!(change.size, (Some(16), Some(24)));
+        assert_eq!(change.align, (Some(8), Some(8)));
+        assert_eq!(change.added_fields.len(), 1);
+        assert_eq!(change.added_fields[0].name, "Lock");
+        assert!(change.removed_fields.is_empty());
+        assert!(change.changed_fields.is_empty());
+
+        assert_eq!(changes.added_functions, ["JAWT_Other"]);
+        assert!(changes.removed_functions.is_empty());
+    }
+
+    #[test]
+    fn report_is_rendered_as_text() {
+        let report = AbiReport::new("linux", &snapshots()).to_string();
+        assert_eq!(
+            report,
+            "# linux (jdk8 -> jdk9)\n\
+             \n\
+             ## jdk8 -> jdk9\n\
+             + const JAWT_VERSION_9 = 589824\n\
+             - const JAWT_MACOSX_USE_CALAYER = -2147483648\n\
+             ~ const JAWT_LOCK_ERROR: 1 -> 2\n\
+             + struct JAWT_Rectangle\n\
+             ~ struct JAWT size 16 -> 24\n  \
+             + fn Lock: ::std::option::Option<unsafe extern \"system\" fn(env: *mut JNIEnv)> @ 16\n\
+             + fn JAWT_Other\n"
+        );
+    }
+
+    #[test]
+    fn identical_releases_have_no_changes() {
+        let snapshots = [
+            ("jdk8".to_string(), AbiSnapshot::parse(JDK_8).unwrap()),
+            ("jdk8u".to_string(), AbiSnapshot::parse(JDK_8).unwrap()),
+        ];
+        let report = AbiReport::new("linux", &snapshots);
+        assert!(report.changes[0].is_empty());
+        assert_eq!(
+            report.to_string(),
+            "# linux (jdk8 -> jdk8u)\n\n## jdk8 -> jdk8u\nNo changes.\n"
+        );
+    }
+}
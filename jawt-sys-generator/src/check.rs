@@ -0,0 +1,105 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Compares generated bindings against the committed ones.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use similar::TextDiff;
+
+/// Normalizes line endings and trailing whitespace, which differ between checkouts and rustfmt
+/// versions without changing the bindings.
+fn normalize(bindings: &str) -> String {
+    let mut normalized = bindings
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    normalized.truncate(normalized.trim_end().len());
+    normalized.push('\n');
+    normalized
+}
+
+/// Returns a unified diff from the bindings committed at `path` to `generated`, or [None] if they
+/// match.
+pub fn diff_bindings(path: &Path, generated: &str) -> anyhow::Result<Option<String>> {
+    let committed = match fs::read_to_string(path) {
+        Ok(committed) => committed,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to read {}", path.display()))
+        }
+    };
+    let committed = normalize(&committed);
+    let generated = normalize(generated);
+    if committed == generated {
+        return Ok(None);
+    }
+
+    let name = path.display().to_string();
+    let diff = TextDiff::from_lines(&committed, &generated)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{name}"), &format!("b/{name}"))
+        .to_string();
+    Ok(Some(diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINDINGS: &str = "pub type jint = i32;\npub type jlong = i64;\n";
+
+    #[test]
+    fn normalize_ignores_line_endings_and_trailing_whitespace() {
+        assert_eq!(normalize(BINDINGS), BINDINGS);
+        assert_eq!(
+            normalize("pub type jint = i32;  \r\npub type jlong = i64;\t\r\n"),
+            BINDINGS
+        );
+        assert_eq!(
+            normalize("pub type jint = i32;\npub type jlong = i64;\n\n\n"),
+            BINDINGS
+        );
+        assert_eq!(
+            normalize("pub type jint = i32;\npub type jlong = i64;"),
+            BINDINGS
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_leading_whitespace_and_inner_blank_lines() {
+        let bindings = "pub struct JAWT {\n    pub version: jint,\n}\n\npub type jint = i32;\n";
+        assert_eq!(normalize(bindings), bindings);
+        assert_ne!(
+            normalize("pub struct JAWT {\n  pub version: jint,\n}\n"),
+            normalize("pub struct JAWT {\n    pub version: jint,\n}\n")
+        );
+    }
+
+    #[test]
+    fn diff_bindings_reports_only_real_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bindings.rs");
+        fs::write(&path, "pub type jint = i32;\r\npub type jlong = i64;  \r\n").unwrap();
+        assert_eq!(diff_bindings(&path, BINDINGS).unwrap(), None);
+
+        let diff = diff_bindings(&path, "pub type jint = i32;\npub type jlong = i128;\n")
+            .unwrap()
+            .expect("the change was not reported");
+        assert!(diff.contains("-pub type jlong = i64;"));
+        assert!(diff.contains("+pub type jlong = i128;"));
+        assert!(diff.contains(&format!("a/{}", path.display())));
+    }
+
+    #[test]
+    fn missing_bindings_are_reported_as_added() {
+        let dir = tempfile::tempdir().unwrap();
+        let diff = diff_bindings(&dir.path().join("bindings.rs"), BINDINGS)
+            .unwrap()
+            .expect("the missing file was not reported");
+        assert!(diff.contains("+pub type jint = i32;"));
+    }
+}
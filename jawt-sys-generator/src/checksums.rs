@@ -0,0 +1,205 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Pins SHA-256 hashes of the fetched headers.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+
+use crate::{Header, Platform};
+
+/// SHA-256 hashes of headers, keyed by platform name and then by header file name. Stored as TOML:
+///
+/// ```toml
+/// [unix]
+/// "jawt.h" = "0123abcd..."
+/// ```
+#[derive(Debug, Default)]
+pub struct Checksums(BTreeMap<String, BTreeMap<String, String>>);
+
+impl Checksums {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let checksums = toml::from_str(&content)
+            .with_context(|| format!("{} contains an invalid checksum file", path.display()))?;
+        Ok(Self(checksums))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = toml::to_string(&self.0).with_context(|| "failed to serialize checksums")?;
+        fs::write(path, content).with_context(|| format!("failed to write to {}", path.display()))
+    }
+
+    /// Hashes the headers of `platform` in `header_dir`.
+    pub fn compute(
+        platform: Platform,
+        header_dir: &Path,
+    ) -> anyhow::Result<BTreeMap<String, String>> {
+        let mut checksums = BTreeMap::new();
+        for header in Header::all() {
+            let path = header.destination(header_dir);
+            let content =
+                fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+            let mut checksum = String::new();
+            for byte in Sha256::digest(&content) {
+                write!(checksum, "{byte:02x}").unwrap();
+            }
+            log::debug!("{} {checksum} ({platform:?})", path.display());
+            checksums.insert(header.filename().to_string(), checksum);
+        }
+        Ok(checksums)
+    }
+
+    pub fn insert(&mut self, platform: Platform, checksums: BTreeMap<String, String>) {
+        self.0.insert(platform.name().to_string(), checksums);
+    }
+
+    /// Records the checksums of `platform` for one of several releases, under `<release>/<platform>`.
+    pub fn insert_release(
+        &mut self,
+        release: &str,
+        platform: Platform,
+        checksums: BTreeMap<String, String>,
+    ) {
+        self.0
+            .insert(format!("{release}/{}", platform.name()), checksums);
+    }
+
+    /// Fails if a header of `platform` in `header_dir` is not pinned or has a different hash.
+    pub fn verify(&self, platform: Platform, header_dir: &Path) -> anyhow::Result<()> {
+        let pinned = self
+            .0
+            .get(platform.name())
+            .with_context(|| format!("no checksums are pinned for {}", platform.name()))?;
+        let actual = Self::compute(platform, header_dir)?;
+
+        let mut mismatches = Vec::new();
+        for (filename, actual) in &actual {
+            match pinned.get(filename) {
+                Some(expected) if expected.eq_ignore_ascii_case(actual) => {}
+                Some(expected) => mismatches.push(format!(
+                    "{}/{filename}: expected {expected}, got {actual}",
+                    platform.name()
+                )),
+                None => mismatches.push(format!(
+                    "{}/{filename}: no checksum is pinned, got {actual}",
+                    platform.name()
+                )),
+            }
+        }
+        if !mismatches.is_empty() {
+            anyhow::bail!(
+                "headers do not match the pinned checksums:\n{}",
+                mismatches.join("\n")
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_headers(header_dir: &Path) {
+        for header in Header::all() {
+            fs::write(
+                header.destination(header_dir),
+                format!("/* {} */\n", header.filename()),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn recorded_checksums_verify_until_a_header_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        write_headers(dir.path());
+
+        let computed = Checksums::compute(Platform::Unix, dir.path()).unwrap();
+        assert_eq!(computed.len(), Header::all().len());
+        assert_eq!(
+            computed["jni.h"],
+            // SHA-256 of "/* jni.h */\n".
+            "e0d2e893fdd13dd10a5060a5fcb70012b4629680cf726cefb30303c935847e45"
+        );
+
+        let mut checksums = Checksums::default();
+        checksums.insert(Platform::Unix, computed);
+        let path = dir.path().join("checksums.toml");
+        checksums.save(&path).unwrap();
+        let checksums = Checksums::load(&path).unwrap();
+        checksums.verify(Platform::Unix, dir.path()).unwrap();
+
+        fs::write(Header::Jawt.destination(dir.path()), "/* changed */\n").unwrap();
+        let error = checksums
+            .verify(Platform::Unix, dir.path())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("unix/jawt.h: expected"), "{error}");
+        assert!(!error.contains("jni.h"), "{error}");
+    }
+
+    #[test]
+    fn headers_without_pinned_checksums_fail_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        write_headers(dir.path());
+
+        let error = Checksums::default()
+            .verify(Platform::Windows, dir.path())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("no checksums are pinned for windows"),
+            "{error}"
+        );
+
+        let mut computed = Checksums::compute(Platform::Unix, dir.path()).unwrap();
+        computed.remove("jawt_md.h");
+        let mut checksums = Checksums::default();
+        checksums.insert(Platform::Unix, computed);
+        let error = checksums
+            .verify(Platform::Unix, dir.path())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("unix/jawt_md.h: no checksum is pinned"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn checksums_are_compared_case_insensitively() {
+        let dir = tempfile::tempdir().unwrap();
+        write_headers(dir.path());
+
+        let computed = Checksums::compute(Platform::MacOS, dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|(filename, checksum)| (filename, checksum.to_uppercase()))
+            .collect();
+        let mut checksums = Checksums::default();
+        checksums.insert(Platform::MacOS, computed);
+        checksums.verify(Platform::MacOS, dir.path()).unwrap();
+    }
+
+    #[test]
+    fn releases_are_recorded_under_their_own_key() {
+        let dir = tempfile::tempdir().unwrap();
+        write_headers(dir.path());
+        let computed = Checksums::compute(Platform::Unix, dir.path()).unwrap();
+
+        let mut checksums = Checksums::default();
+        checksums.insert_release("jdk-21+35", Platform::Unix, computed);
+        let path = dir.path().join("checksums.toml");
+        checksums.save(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[\"jdk-21+35/unix\"]"), "{content}");
+        assert!(checksums.verify(Platform::Unix, dir.path()).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Locates the JNI and JAWT headers bindings are generated from.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+#[cfg(feature = "network")]
+use url::Url;
+
+use crate::Platform;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Header {
+    Jni,
+    JniPlatform,
+    Jawt,
+    JawtPlatform,
+}
+
+impl Header {
+    pub fn all() -> &'static [Self] {
+        &[
+            Header::Jni,
+            Header::JniPlatform,
+            Header::Jawt,
+            Header::JawtPlatform,
+        ]
+    }
+
+    pub fn destination(self, destination_dir: &Path) -> PathBuf {
+        destination_dir.join(self.filename())
+    }
+
+    pub fn filename(self) -> &'static str {
+        match self {
+            Self::Jni => "jni.h",
+            Self::JniPlatform => "jni_md.h",
+            Self::Jawt => "jawt.h",
+            Self::JawtPlatform => "jawt_md.h",
+        }
+    }
+
+    /// Path of the header relative to the `include` directory of an installed JDK.
+    fn local_location(self, include_dir: &Path, platform: Platform) -> PathBuf {
+        match self {
+            Self::Jni | Self::Jawt => include_dir.join(self.filename()),
+            Self::JniPlatform | Self::JawtPlatform => include_dir
+                .join(platform.jdk_include_dir())
+                .join(self.filename()),
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn location(self, repo: &Url, tag: &str, platform: Platform) -> anyhow::Result<Url> {
+        Self::construct_location(
+            repo,
+            tag,
+            self.module(),
+            self.platform(platform),
+            self.filename(),
+        )
+    }
+
+    #[cfg(feature = "network")]
+    fn module(self) -> &'static str {
+        match self {
+            Self::Jni | Self::JniPlatform => "java.base",
+            Self::Jawt | Self::JawtPlatform => "java.desktop",
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn platform(self, platform: Platform) -> &'static str {
+        match (self, platform) {
+            (Self::Jni | Self::Jawt, _) => "share",
+            (Self::JniPlatform, Platform::Windows) => "windows",
+            (Self::JniPlatform, _) => "unix",
+            (Self::JawtPlatform, Platform::Windows) => "windows",
+            (Self::JawtPlatform, Platform::MacOS) => "macosx",
+            (Self::JawtPlatform, Platform::Unix) => "unix",
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn construct_location(
+        repo: &Url,
+        tag: &str,
+        module: &str,
+        platform: &str,
+        filename: &str,
+    ) -> anyhow::Result<Url> {
+        let mut new_url = repo.clone();
+        new_url
+            .path_segments_mut()
+            .ok()
+            .with_context(|| "given url cannot be a base URL")?
+            .extend([
+                "raw", "refs", "tags", tag, "src", module, platform, "native", "include", filename,
+            ]);
+        Ok(new_url)
+    }
+}
+
+/// Where the headers are taken from.
+#[derive(Debug, Clone)]
+pub enum HeaderSource {
+    /// A tag of the OpenJDK GitHub repository.
+    #[cfg(feature = "network")]
+    Remote { repo: Url, tag: String },
+    /// A directory laid out like the `include` directory of an installed JDK, with the
+    /// platform-specific headers in a subdirectory such as `linux` or `win32`.
+    Local { include_dir: PathBuf },
+}
+
+impl HeaderSource {
+    /// Uses the `include` directory of the JDK installed at `java_home`.
+    pub fn java_home(java_home: &Path) -> anyhow::Result<Self> {
+        let include_dir = java_home.join("include");
+        if !include_dir.is_dir() {
+            anyhow::bail!(
+                "{} does not contain an include directory; is it a JRE instead of a JDK?",
+                java_home.display()
+            );
+        }
+        Ok(Self::Local { include_dir })
+    }
+
+    /// Copies every [Header] for `platform` into `destination_dir`.
+    pub fn fetch_all(&self, platform: Platform, destination_dir: &Path) -> anyhow::Result<()> {
+        match self {
+            #[cfg(feature = "network")]
+            Self::Remote { repo, tag } => {
+                let runtime = tokio::runtime::Runtime::new()
+                    .with_context(|| "failed to start the async runtime")?;
+                runtime.block_on(async {
+                    for header in Header::all() {
+                        let location = header.location(repo, tag, platform)?;
+
+                        log::info!("Downloading {location}...");
+                        let response = reqwest::get(location.clone())
+                            .await
+                            .with_context(|| format!("failed to send a request to {location}"))?;
+
+                        response.error_for_status_ref().with_context(|| {
+                            format!("{location} responded with {}", response.status())
+                        })?;
+
+                        let body = response
+                            .bytes()
+                            .await
+                            .with_context(|| format!("failed to download from {location}"))?;
+                        let header_destination = header.destination(destination_dir);
+                        fs::write(&header_destination, body).with_context(|| {
+                            format!("failed to write to {}", header_destination.display())
+                        })?;
+                    }
+                    Ok(())
+                })
+            }
+            Self::Local { include_dir } => {
+                for header in Header::all() {
+                    let location = header.local_location(include_dir, platform);
+
+                    log::info!("Copying {}...", location.display());
+                    let header_destination = header.destination(destination_dir);
+                    fs::copy(&location, &header_destination).with_context(|| {
+                        format!(
+                            "failed to copy {} to {}",
+                            location.display(),
+                            header_destination.display()
+                        )
+                    })?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
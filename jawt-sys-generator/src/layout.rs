@@ -0,0 +1,129 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Combines the layout tests of bindings generated for targets of different pointer widths.
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+static LAYOUT_TEST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?ms)^#\[test\]\r?\nfn (bindgen_test_layout_\w+)\(\) \{\r?\n.*?^\}\r?\n").unwrap()
+});
+
+/// Gates the layout tests of `bindings_64` behind `target_pointer_width = "64"` and adds the
+/// layout tests of `bindings_32` behind `target_pointer_width = "32"` next to them. Fails if the
+/// bindings differ in anything but their layout tests.
+pub fn merge_layout_tests(bindings_64: &str, bindings_32: &str) -> anyhow::Result<String> {
+    let without_tests = |bindings: &str| LAYOUT_TEST.replace_all(bindings, "").into_owned();
+    if without_tests(bindings_64) != without_tests(bindings_32) {
+        anyhow::bail!("the 32-bit bindings differ from the 64-bit bindings beyond layout tests");
+    }
+
+    let tests_32 = LAYOUT_TEST
+        .captures_iter(bindings_32)
+        .map(|caps| (caps[1].to_string(), caps[0].to_string()))
+        .collect::<HashMap<_, _>>();
+
+    let merged = LAYOUT_TEST.replace_all(bindings_64, |caps: &Captures<'_>| {
+        // The attributes follow the line endings of the bindings.
+        let newline = if caps[0].ends_with("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut tests = format!("#[cfg(target_pointer_width = \"64\")]{newline}{}", &caps[0]);
+        if let Some(test_32) = tests_32.get(&caps[1]) {
+            tests.push_str(&format!("#[cfg(target_pointer_width = \"32\")]{newline}"));
+            tests.push_str(test_32);
+        }
+        tests
+    });
+    Ok(merged.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINDINGS_64: &str = "\
+pub struct A {
+    pub a: *mut u8,
+}
+#[test]
+fn bindgen_test_layout_A() {
+    assert_eq!(size_of::<A>(), 8usize);
+}
+pub struct B {
+    pub b: u8,
+}
+#[test]
+fn bindgen_test_layout_B() {
+    assert_eq!(size_of::<B>(), 1usize);
+}
+";
+
+    const BINDINGS_32: &str = "\
+pub struct A {
+    pub a: *mut u8,
+}
+#[test]
+fn bindgen_test_layout_A() {
+    assert_eq!(size_of::<A>(), 4usize);
+}
+pub struct B {
+    pub b: u8,
+}
+";
+
+    #[test]
+    fn tests_without_a_32_bit_counterpart_are_only_gated() {
+        let merged = merge_layout_tests(BINDINGS_64, BINDINGS_32).unwrap();
+        assert_eq!(
+            merged,
+            "\
+pub struct A {
+    pub a: *mut u8,
+}
+#[cfg(target_pointer_width = \"64\")]
+#[test]
+fn bindgen_test_layout_A() {
+    assert_eq!(size_of::<A>(), 8usize);
+}
+#[cfg(target_pointer_width = \"32\")]
+#[test]
+fn bindgen_test_layout_A() {
+    assert_eq!(size_of::<A>(), 4usize);
+}
+pub struct B {
+    pub b: u8,
+}
+#[cfg(target_pointer_width = \"64\")]
+#[test]
+fn bindgen_test_layout_B() {
+    assert_eq!(size_of::<B>(), 1usize);
+}
+"
+        );
+    }
+
+    #[test]
+    fn crlf_bindings_are_merged() {
+        let crlf = |bindings: &str| bindings.replace('\n', "\r\n");
+        let merged = merge_layout_tests(&crlf(BINDINGS_64), &crlf(BINDINGS_32)).unwrap();
+        assert_eq!(
+            merged,
+            crlf(&merge_layout_tests(BINDINGS_64, BINDINGS_32).unwrap())
+        );
+    }
+
+    #[test]
+    fn bindings_differing_beyond_layout_tests_are_rejected() {
+        let bindings_32 = BINDINGS_32.replace("pub b: u8", "pub b: u16");
+        let error = merge_layout_tests(BINDINGS_64, &bindings_32).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the 32-bit bindings differ from the 64-bit bindings beyond layout tests"
+        );
+    }
+}
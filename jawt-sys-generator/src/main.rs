@@ -8,20 +8,71 @@ use bindgen::RustTarget;
 use clap::Parser;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex, Replacer};
+#[cfg(feature = "network")]
 use url::Url;
 
+mod abi;
+use abi::*;
+
+mod check;
+use check::*;
+
+mod checksums;
+use checksums::*;
+
+mod headers;
+use headers::*;
+
+mod layout;
+use layout::*;
+
 #[derive(Parser)]
 #[clap(name = clap::crate_name!())]
 #[clap(version = clap::crate_version!())]
 #[clap(propagate_version = true)]
 /// Generates raw bindings for jawt-sys.
 struct Cli {
+    #[cfg(feature = "network")]
     #[clap(long, default_value("https://github.com/openjdk/jdk"))]
     /// The URL of the OpenJDK GitHub repository containing the required headers.
     repo: Url,
+    #[cfg(feature = "network")]
     #[clap(long, default_value("jdk-17+35"))]
     /// The name of the tag to extract the required headers from.
     tag: String,
+    #[clap(long, conflicts_with("java_home"))]
+    /// A local directory laid out like the include directory of a JDK to take the headers from
+    /// instead of downloading them.
+    headers_dir: Option<PathBuf>,
+    #[clap(long)]
+    /// The JDK whose include directory to take the headers from instead of downloading them.
+    java_home: Option<PathBuf>,
+    #[clap(long, value_enum, conflicts_with("all"))]
+    /// The platform to generate bindings for. Can be repeated. Defaults to the current platform.
+    platform: Vec<Platform>,
+    #[clap(long)]
+    /// Generates bindings for every platform.
+    all: bool,
+    #[clap(long)]
+    /// Compares the generated bindings against the committed ones instead of writing them, and
+    /// fails with a diff if they differ.
+    check: bool,
+    #[clap(long)]
+    /// A TOML file with the SHA-256 hashes of the headers. Fails if a fetched header is not pinned
+    /// or has a different hash.
+    verify_checksums: Option<PathBuf>,
+    #[clap(long)]
+    /// Writes the SHA-256 hashes of the fetched headers to a TOML file usable with
+    /// --verify-checksums.
+    record_checksums: Option<PathBuf>,
+    #[clap(long, conflicts_with_all(["check", "verify_checksums", "headers_dir", "java_home"]))]
+    /// A release to include in an ABI report instead of writing bindings: a tag of the OpenJDK
+    /// repository, or LABEL=DIR for a directory laid out like the include directory of a JDK.
+    /// Repeat in release order.
+    report: Vec<String>,
+    #[clap(long, requires("report"))]
+    /// Writes the ABI report as JSON to this file in addition to printing it.
+    report_json: Option<PathBuf>,
     #[clap(long, default_value(sys_rust_target()))]
     /// The target Rust version.
     rust_target: RustTarget,
@@ -56,58 +107,245 @@ fn sys_rust_target() -> &'static str {
     &SYS_RUST_TARGET
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
     let Cli {
+        #[cfg(feature = "network")]
         repo,
+        #[cfg(feature = "network")]
         tag,
+        headers_dir,
+        java_home,
+        platform: platforms,
+        all,
+        check,
+        verify_checksums,
+        record_checksums,
+        report,
+        report_json,
         rust_target,
     } = Cli::parse();
 
     env_logger::init();
 
-    let current_platform = Platform::current();
-    log::info!("Downloading headers for {current_platform:?}...");
+    let header_source = match (headers_dir, java_home) {
+        (Some(include_dir), _) => HeaderSource::Local { include_dir },
+        (None, Some(java_home)) => HeaderSource::java_home(&java_home)?,
+        #[cfg(feature = "network")]
+        (None, None) => HeaderSource::Remote {
+            repo: repo.clone(),
+            tag,
+        },
+        #[cfg(not(feature = "network"))]
+        (None, None) => anyhow::bail!(
+            "--headers-dir or --java-home is required when the network feature is disabled"
+        ),
+    };
+
+    let platforms = if all {
+        Platform::all().to_vec()
+    } else if platforms.is_empty() {
+        vec![Platform::current()]
+    } else {
+        platforms
+    };
 
-    let header_destination_dir = sys_manifest_dir().join(".bindgen");
-    log::info!("Header destination: {}", header_destination_dir.display());
+    let header_root_dir = sys_manifest_dir().join(".bindgen");
+    log::info!("Header destination: {}", header_root_dir.display());
 
-    if matches!(header_destination_dir.try_exists(), Ok(true) | Err(_)) {
-        fs::remove_dir_all(&header_destination_dir)
-            .with_context(|| format!("failed to clean {}", header_destination_dir.display()))?;
+    if matches!(header_root_dir.try_exists(), Ok(true) | Err(_)) {
+        fs::remove_dir_all(&header_root_dir)
+            .with_context(|| format!("failed to clean {}", header_root_dir.display()))?;
+    }
+
+    if !report.is_empty() {
+        let releases = report
+            .iter()
+            .map(|release| match release.split_once('=') {
+                Some((label, include_dir)) => Ok((
+                    label.to_string(),
+                    HeaderSource::Local {
+                        include_dir: PathBuf::from(include_dir),
+                    },
+                )),
+                #[cfg(feature = "network")]
+                None => Ok((
+                    release.clone(),
+                    HeaderSource::Remote {
+                        repo: repo.clone(),
+                        tag: release.clone(),
+                    },
+                )),
+                #[cfg(not(feature = "network"))]
+                None => Err(anyhow::anyhow!(
+                    "{release} must be LABEL=DIR when the network feature is disabled"
+                )),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        return write_abi_report(
+            &releases,
+            &platforms,
+            &header_root_dir,
+            rust_target,
+            report_json.as_deref(),
+            record_checksums.as_deref(),
+        );
+    }
+
+    let bindings_destination_dir = sys_manifest_dir().join("src");
+    let pinned_checksums = verify_checksums
+        .as_deref()
+        .map(Checksums::load)
+        .transpose()?;
+    let mut recorded_checksums = Checksums::default();
+    let mut outdated = Vec::new();
+
+    for platform in platforms {
+        log::info!("Fetching headers for {platform:?} from {header_source:?}...");
+
+        let header_destination_dir = header_root_dir.join(platform.name());
+        fs::create_dir_all(&header_destination_dir).with_context(|| {
+            format!(
+                "failed to create a new directory at {}",
+                header_destination_dir.display()
+            )
+        })?;
+        header_source.fetch_all(platform, &header_destination_dir)?;
+
+        if let Some(pinned_checksums) = &pinned_checksums {
+            pinned_checksums.verify(platform, &header_destination_dir)?;
+        }
+        if let Some(record_checksums) = &record_checksums {
+            recorded_checksums.insert(
+                platform,
+                Checksums::compute(platform, &header_destination_dir)?,
+            );
+            recorded_checksums.save(record_checksums)?;
+        }
+
+        log::info!("Generating bindings for {platform:?}...");
+        let bindings = generate(platform, &header_destination_dir, rust_target)?;
+        let bindings_destination = bindings_destination_dir.join(platform.bindings_filename());
+
+        if check {
+            if let Some(diff) = diff_bindings(&bindings_destination, &bindings)? {
+                print!("{diff}");
+                outdated.push(platform.bindings_filename());
+            }
+        } else {
+            fs::write(&bindings_destination, bindings)
+                .with_context(|| "failed to write bindings to a file")?;
+        }
     }
-    fs::create_dir_all(&header_destination_dir).with_context(|| {
-        format!(
-            "failed to create a new directory at {}",
-            header_destination_dir.display()
-        )
-    })?;
-    let main_header_destination = header_destination_dir.join("bindings.h");
-    fs::write(&main_header_destination, include_str!("bindings.h"))
-        .with_context(|| "failed to generate bindings.h")?;
 
-    for header in Header::all() {
-        let location = header.location(&repo, &tag, current_platform)?;
-
-        log::info!("Downloading {location}...");
-        let response = reqwest::get(location.clone())
-            .await
-            .with_context(|| format!("failed to send a request to {location}"))?;
-
-        response
-            .error_for_status_ref()
-            .with_context(|| format!("{location} responded with {}", response.status()))?;
-
-        let body = response
-            .bytes()
-            .await
-            .with_context(|| format!("failed to download from {location}"))?;
-        let header_destination = header.destination(&header_destination_dir);
-        fs::write(&header_destination, body)
-            .with_context(|| format!("failed to write to {}", header_destination.display()))?;
+    if !outdated.is_empty() {
+        anyhow::bail!("{} differ from the generated bindings", outdated.join(", "));
     }
 
-    log::info!("Generating bindings...");
+    log::info!("Done.");
+
+    Ok(())
+}
+
+/// Generates the bindings of every release for every platform, then prints how the ABI changes
+/// between consecutive releases.
+fn write_abi_report(
+    releases: &[(String, HeaderSource)],
+    platforms: &[Platform],
+    header_root_dir: &Path,
+    rust_target: RustTarget,
+    report_json: Option<&Path>,
+    record_checksums: Option<&Path>,
+) -> anyhow::Result<()> {
+    let mut reports = Vec::new();
+    let mut recorded_checksums = Checksums::default();
+    for &platform in platforms {
+        let mut snapshots = Vec::new();
+        for (index, (label, header_source)) in releases.iter().enumerate() {
+            log::info!("Fetching headers of {label} for {platform:?} from {header_source:?}...");
+
+            let header_destination_dir = header_root_dir
+                .join("report")
+                .join(platform.name())
+                .join(index.to_string());
+            fs::create_dir_all(&header_destination_dir).with_context(|| {
+                format!(
+                    "failed to create a new directory at {}",
+                    header_destination_dir.display()
+                )
+            })?;
+            header_source.fetch_all(platform, &header_destination_dir)?;
+            if let Some(record_checksums) = record_checksums {
+                recorded_checksums.insert_release(
+                    label,
+                    platform,
+                    Checksums::compute(platform, &header_destination_dir)?,
+                );
+                recorded_checksums.save(record_checksums)?;
+            }
+
+            log::info!("Generating bindings of {label} for {platform:?}...");
+            let bindings = generate(platform, &header_destination_dir, rust_target)?;
+            let snapshot = AbiSnapshot::parse(&bindings)
+                .with_context(|| format!("failed to extract the ABI of {label}"))?;
+            snapshots.push((label.clone(), snapshot));
+        }
+
+        let report = AbiReport::new(platform.name(), &snapshots);
+        println!("{report}");
+        reports.push(report);
+    }
+
+    if let Some(report_json) = report_json {
+        let content = serde_json::to_string_pretty(&reports)
+            .with_context(|| "failed to serialize the ABI report")?;
+        fs::write(report_json, content)
+            .with_context(|| format!("failed to write to {}", report_json.display()))?;
+    }
+
+    log::info!("Done.");
+
+    Ok(())
+}
+
+/// Generates the bindings of `platform` from the headers in `header_destination_dir`. For platforms
+/// with 32-bit targets, the layout tests of the 32-bit bindings are added next to the 64-bit ones.
+fn generate(
+    platform: Platform,
+    header_destination_dir: &Path,
+    rust_target: RustTarget,
+) -> anyhow::Result<String> {
+    let target = (platform != Platform::current()).then(|| platform.clang_target());
+    let bindings = generate_for_target(
+        platform,
+        header_destination_dir,
+        rust_target,
+        target.as_deref(),
+    )?;
+
+    let Some(target_32) = platform.clang_target_32() else {
+        return Ok(bindings);
+    };
+    log::info!("Generating 32-bit layout tests for {platform:?}...");
+    let bindings_32 = generate_for_target(
+        platform,
+        header_destination_dir,
+        rust_target,
+        Some(target_32),
+    )?;
+    merge_layout_tests(&bindings, &bindings_32)
+}
+
+/// Generates and postprocesses the bindings of `platform` from the headers in
+/// `header_destination_dir`. `target` is the clang target triple, or [None] for the host.
+fn generate_for_target(
+    platform: Platform,
+    header_destination_dir: &Path,
+    rust_target: RustTarget,
+    target: Option<&str>,
+) -> anyhow::Result<String> {
+    let main_header_destination = header_destination_dir.join("bindings.h");
+    fs::write(&main_header_destination, include_str!("bindings.h"))
+        .with_context(|| "failed to generate bindings.h")?;
 
     let builder = bindgen::builder()
         .header(main_header_destination.display().to_string())
@@ -118,7 +356,7 @@ async fn main() -> anyhow::Result<()> {
         .raw_line("")
         .raw_line("use jni_sys::*;");
 
-    let builder = match current_platform {
+    let builder = match platform {
         Platform::Windows => {
             builder
                 .raw_line("use windows_sys::Win32::Foundation::HWND;")
@@ -139,31 +377,43 @@ async fn main() -> anyhow::Result<()> {
     let builder = builder
         .blocklist_file(
             Header::Jni
-                .destination(&header_destination_dir)
+                .destination(header_destination_dir)
                 .to_string_lossy()
                 .replace("\\", "\\\\"),
         )
         .blocklist_file(
             Header::JniPlatform
-                .destination(&header_destination_dir)
+                .destination(header_destination_dir)
                 .to_string_lossy()
                 .replace("\\", "\\\\"),
         )
         .allowlist_file(
             Header::Jawt
-                .destination(&header_destination_dir)
+                .destination(header_destination_dir)
                 .to_string_lossy()
                 .replace("\\", "\\\\"),
         )
         .allowlist_file(
             Header::JawtPlatform
-                .destination(&header_destination_dir)
+                .destination(header_destination_dir)
                 .to_string_lossy()
                 .replace("\\", "\\\\"),
         )
         .rust_target(rust_target)
         .clang_arg(format!("-I{}", header_destination_dir.to_string_lossy()));
 
+    // System headers of other targets are usually not installed, so stand-ins are used instead.
+    let builder = match target {
+        None => builder,
+        Some(target) => {
+            let stub_dir = header_destination_dir.join("stubs");
+            write_stub_headers(&stub_dir)?;
+            builder
+                .clang_arg(format!("--target={target}"))
+                .clang_arg(format!("-I{}", stub_dir.to_string_lossy()))
+        }
+    };
+
     let mut bindings = builder
         .generate()
         .with_context(|| "binding generation failed")?
@@ -172,7 +422,7 @@ async fn main() -> anyhow::Result<()> {
     // Postprocessing
     {
         // Remove definitions of Windows-API types, as these are provided by the windows-sys crate.
-        if matches!(current_platform, Platform::Windows) {
+        if matches!(platform, Platform::Windows) {
             let regex = Regex::new("(?m)pub type (HWND|HBITMAP|HDC|HPALETTE) = .*;\r?\n?").unwrap();
             bindings = regex.replace_all(&bindings, "").into_owned();
         }
@@ -203,31 +453,42 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let bindings_destination_dir = sys_manifest_dir().join("src");
-
-    fs::write(
-        bindings_destination_dir.join(match current_platform {
-            Platform::Windows => "bindings_windows.rs",
-            Platform::MacOS => "bindings_macos.rs",
-            Platform::Unix => "bindings_unix.rs",
-        }),
-        bindings,
-    )
-    .with_context(|| "failed to write bindings to a file")?;
-
-    log::info!("Done.");
+    Ok(bindings)
+}
 
+fn write_stub_headers(stub_dir: &Path) -> anyhow::Result<()> {
+    const STUB_HEADERS: [(&str, &str); 4] = [
+        ("stdio.h", include_str!("stubs/stdio.h")),
+        ("windows.h", include_str!("stubs/windows.h")),
+        ("X11/Xlib.h", include_str!("stubs/X11/Xlib.h")),
+        ("X11/Xutil.h", include_str!("stubs/X11/Xutil.h")),
+    ];
+
+    for (path, content) in STUB_HEADERS {
+        let destination = stub_dir.join(path);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&destination, content)
+            .with_context(|| format!("failed to write to {}", destination.display()))?;
+    }
     Ok(())
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 enum Platform {
     Windows,
+    #[clap(name = "macos")]
     MacOS,
     Unix,
 }
 
 impl Platform {
+    fn all() -> &'static [Self] {
+        &[Platform::Windows, Platform::MacOS, Platform::Unix]
+    }
+
     fn current() -> Self {
         if cfg!(target_os = "windows") {
             Self::Windows
@@ -237,82 +498,54 @@ impl Platform {
             Self::Unix
         }
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Header {
-    Jni,
-    JniPlatform,
-    Jawt,
-    JawtPlatform,
-}
 
-impl Header {
-    fn all() -> &'static [Self] {
-        &[
-            Header::Jni,
-            Header::JniPlatform,
-            Header::Jawt,
-            Header::JawtPlatform,
-        ]
-    }
-
-    fn location(self, repo: &Url, tag: &str, platform: Platform) -> anyhow::Result<Url> {
-        Self::construct_location(
-            repo,
-            tag,
-            self.module(),
-            self.platform(platform),
-            self.filename(),
-        )
-    }
-
-    fn destination(self, destination_dir: &Path) -> PathBuf {
-        destination_dir.join(self.filename())
+    fn name(self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::MacOS => "macos",
+            Self::Unix => "unix",
+        }
     }
 
-    fn module(self) -> &'static str {
+    fn bindings_filename(self) -> &'static str {
         match self {
-            Self::Jni | Self::JniPlatform => "java.base",
-            Self::Jawt | Self::JawtPlatform => "java.desktop",
+            Self::Windows => "bindings_windows.rs",
+            Self::MacOS => "bindings_macos.rs",
+            Self::Unix => "bindings_unix.rs",
         }
     }
 
-    fn platform(self, platform: Platform) -> &'static str {
-        match (self, platform) {
-            (Self::Jni | Self::Jawt, _) => "share",
-            (Self::JniPlatform, Platform::Windows) => "windows",
-            (Self::JniPlatform, _) => "unix",
-            (Self::JawtPlatform, Platform::Windows) => "windows",
-            (Self::JawtPlatform, Platform::MacOS) => "macosx",
-            (Self::JawtPlatform, Platform::Unix) => "unix",
+    /// The target triple clang parses the headers for. The architecture of the host is kept, as
+    /// the bindings do not depend on it beyond the pointer width.
+    fn clang_target(self) -> String {
+        let arch = match std::env::consts::ARCH {
+            "x86_64" | "aarch64" => std::env::consts::ARCH,
+            _ => "x86_64",
+        };
+        match self {
+            Self::Windows => format!("{arch}-pc-windows-msvc"),
+            Self::MacOS => format!("{arch}-apple-darwin"),
+            Self::Unix => format!("{arch}-unknown-linux-gnu"),
         }
     }
 
-    fn filename(self) -> &'static str {
+    /// The 32-bit target triple whose layout tests are added to the bindings, if the platform has
+    /// 32-bit targets AWT runs on.
+    fn clang_target_32(self) -> Option<&'static str> {
         match self {
-            Self::Jni => "jni.h",
-            Self::JniPlatform => "jni_md.h",
-            Self::Jawt => "jawt.h",
-            Self::JawtPlatform => "jawt_md.h",
+            // Layouts of the bindings are identical on i686 and armv7.
+            Self::Unix => Some("i686-unknown-linux-gnu"),
+            Self::Windows | Self::MacOS => None,
         }
     }
 
-    fn construct_location(
-        repo: &Url,
-        tag: &str,
-        module: &str,
-        platform: &str,
-        filename: &str,
-    ) -> anyhow::Result<Url> {
-        let mut new_url = repo.clone();
-        new_url
-            .path_segments_mut()
-            .ok()
-            .with_context(|| "given url cannot be a base URL")?
-            .extend([
-                "raw", "refs", "tags", tag, "src", module, platform, "native", "include", filename,
-            ]);
-        Ok(new_url)
+    /// Name of the subdirectory of a JDK's `include` directory containing `jni_md.h` and
+    /// `jawt_md.h`.
+    fn jdk_include_dir(self) -> &'static str {
+        match self {
+            Self::Windows => "win32",
+            Self::MacOS => "darwin",
+            Self::Unix => "linux",
+        }
     }
 }
@@ -0,0 +1,12 @@
+/* Copyright (c) 2025 Gobley Contributors. */
+
+/* Stand-in for the Xlib header included by jawt_md.h when generating bindings for Unix on another
+ * platform. Only the types referenced by jawt_md.h are declared; the generated bindings take
+ * them from x11-dl. */
+#pragma once
+
+typedef unsigned long XID;
+typedef unsigned long VisualID;
+typedef XID Drawable;
+typedef XID Colormap;
+typedef struct _XDisplay Display;
@@ -0,0 +1,7 @@
+/* Copyright (c) 2025 Gobley Contributors. */
+
+/* Stand-in for the Xlib utility header included by jawt_md.h. Nothing in it is used by the
+ * generated bindings. */
+#pragma once
+
+#include <X11/Xlib.h>
@@ -0,0 +1,5 @@
+/* Copyright (c) 2025 Gobley Contributors. */
+
+/* Stand-in for the C library header included by jni.h when generating bindings for another
+ * platform. Nothing in it is used by the generated bindings. */
+#pragma once
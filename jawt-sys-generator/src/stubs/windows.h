@@ -0,0 +1,17 @@
+/* Copyright (c) 2025 Gobley Contributors. */
+
+/* Stand-in for the Windows SDK header included by jawt_md.h when generating bindings for Windows
+ * on another platform. The handles are declared the way DECLARE_HANDLE does, and are replaced
+ * with the windows-sys types after generation. */
+#pragma once
+
+#define JAWT_SYS_DECLARE_HANDLE(name) \
+    struct name##__ {                 \
+        int unused;                   \
+    };                                \
+    typedef struct name##__ *name
+
+JAWT_SYS_DECLARE_HANDLE(HWND);
+JAWT_SYS_DECLARE_HANDLE(HDC);
+JAWT_SYS_DECLARE_HANDLE(HBITMAP);
+JAWT_SYS_DECLARE_HANDLE(HPALETTE);
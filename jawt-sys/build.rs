@@ -0,0 +1,211 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Links `jawt.dll` or `libjawt.{dylib, so}` of a JDK when the `link-jawt` feature is enabled.
+//!
+//! The JDK is searched for in this order:
+//!
+//! 1. `JAWT_SYS_JAVA_HOME`
+//! 2. `JAVA_HOME`
+//! 3. The JDK `javac` on `PATH` belongs to
+//!
+//! `JAWT_SYS_RPATH` holds directories, separated like `PATH`, to embed as the `rpath` of the tests
+//! of this package. Dependents read the same variable from their own build scripts.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+const JAVA_HOME_VARS: &[&str] = &["JAWT_SYS_JAVA_HOME", "JAVA_HOME"];
+const RPATH_VAR: &str = "JAWT_SYS_RPATH";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_LINK_JAWT").is_none() {
+        return;
+    }
+    for var in JAVA_HOME_VARS.iter().chain([&RPATH_VAR, &"PATH"]) {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+
+    let target = Target::from_env();
+    let (java_home, origin) = match find_java_home(&target) {
+        Some(found) => found,
+        None => fail(&format!(
+            "could not find a JDK to link {} against. Set JAWT_SYS_JAVA_HOME or JAVA_HOME to the \
+             home directory of a JDK, or put its `javac` on PATH.",
+            target.link_filename()
+        )),
+    };
+
+    let Some(lib_dir) = target.find_lib_dir(&java_home) else {
+        fail(&missing_library_message(&target, &java_home, origin))
+    };
+
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    println!("cargo:rustc-link-lib=dylib=jawt");
+
+    let Some(rpath) = env::var_os(RPATH_VAR).filter(|value| !value.is_empty()) else {
+        return;
+    };
+    if target.is_windows() {
+        println!("cargo:warning={RPATH_VAR} is ignored as Windows targets have no rpath");
+        return;
+    }
+    // Only applies to the targets of this package, as Cargo does not forward linker arguments.
+    for dir in env::split_paths(&rpath) {
+        println!("cargo:rustc-link-arg=-Wl,-rpath,{}", dir.display());
+    }
+    if target.os != "macos" {
+        // Unlike `RUNPATH`, `RPATH` is also searched for the dependencies of `libjawt.so`, such
+        // as `libjvm.so` in `lib/server`.
+        println!("cargo:rustc-link-arg=-Wl,--disable-new-dtags");
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {message}");
+    std::process::exit(1);
+}
+
+/// The target platform, which may differ from the host when cross-compiling.
+struct Target {
+    os: String,
+    arch: String,
+}
+
+impl Target {
+    fn from_env() -> Self {
+        Self {
+            os: env::var("CARGO_CFG_TARGET_OS").unwrap_or_default(),
+            arch: env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default(),
+        }
+    }
+
+    fn is_windows(&self) -> bool {
+        self.os == "windows"
+    }
+
+    /// The file the linker needs, which is an import library on Windows.
+    fn link_filename(&self) -> &'static str {
+        match self.os.as_str() {
+            "windows" => "jawt.lib",
+            "macos" => "libjawt.dylib",
+            _ => "libjawt.so",
+        }
+    }
+
+    /// The name of the architecture directory of JDK 8 and earlier on Linux.
+    fn jdk8_arch_dir(&self) -> &str {
+        match self.arch.as_str() {
+            "x86_64" => "amd64",
+            "x86" => "i386",
+            arch => arch,
+        }
+    }
+
+    /// Returns the directory of `java_home` containing [Self::link_filename()]. JDK 9 and later
+    /// keep it in `lib`; JDK 8 keeps it in `jre/lib` or `jre/lib/<arch>`.
+    fn find_lib_dir(&self, java_home: &Path) -> Option<PathBuf> {
+        self.lib_dirs(java_home)
+            .into_iter()
+            .find(|dir| dir.join(self.link_filename()).is_file())
+    }
+
+    fn lib_dirs(&self, java_home: &Path) -> [PathBuf; 4] {
+        let arch = self.jdk8_arch_dir();
+        [
+            java_home.join("lib"),
+            java_home.join("lib").join(arch),
+            java_home.join("jre").join("lib"),
+            java_home.join("jre").join("lib").join(arch),
+        ]
+    }
+}
+
+/// Where the JDK was found, for diagnostics.
+#[derive(Clone, Copy)]
+enum Origin {
+    Var(&'static str),
+    Javac,
+}
+
+fn find_java_home(target: &Target) -> Option<(PathBuf, Origin)> {
+    for var in JAVA_HOME_VARS {
+        if let Some(java_home) = env::var_os(var).filter(|value| !value.is_empty()) {
+            return Some((PathBuf::from(java_home), Origin::Var(var)));
+        }
+    }
+    find_javac(target).map(|java_home| (java_home, Origin::Javac))
+}
+
+/// Returns the home directory of the JDK `javac` on `PATH` belongs to, resolving the symbolic
+/// links package managers install it behind.
+fn find_javac(target: &Target) -> Option<PathBuf> {
+    let filename = if cfg!(windows) { "javac.exe" } else { "javac" };
+    let path = env::var_os("PATH")?;
+    let javac = env::split_paths(&path)
+        .map(|dir| dir.join(filename))
+        .find(|javac| javac.is_file())?;
+    let javac = javac.canonicalize().unwrap_or(javac);
+    let java_home = javac.parent()?.parent()?.to_path_buf();
+    // JDK 8 on macOS nests the home directory in a bundle.
+    if target.os == "macos" && !java_home.join("lib").is_dir() {
+        let bundled = java_home.join("Contents").join("Home");
+        if bundled.is_dir() {
+            return Some(bundled);
+        }
+    }
+    Some(java_home)
+}
+
+fn missing_library_message(target: &Target, java_home: &Path, origin: Origin) -> String {
+    let origin = match origin {
+        Origin::Var(var) => format!("{var} points to"),
+        Origin::Javac => "`javac` on PATH belongs to".to_string(),
+    };
+    if !java_home.is_dir() {
+        return format!("{origin} {}, which does not exist.", java_home.display());
+    }
+    let mut message = format!(
+        "{origin} {}, which does not contain {}.",
+        java_home.display(),
+        target.link_filename()
+    );
+    if is_headless(target, java_home) {
+        message.push_str(
+            " It is a headless runtime without AWT, such as the `-headless` JRE packages of Linux \
+             distributions or a jlink image without the `java.desktop` module. Install a full JDK \
+             and point JAWT_SYS_JAVA_HOME to it.",
+        );
+    } else if !java_home.join("include").is_dir() {
+        message.push_str(
+            " It looks like a JRE; point JAWT_SYS_JAVA_HOME to the home directory of a JDK.",
+        );
+    }
+    message
+}
+
+/// Whether the Java runtime at `java_home` was built or packaged without AWT.
+fn is_headless(target: &Target, java_home: &Path) -> bool {
+    // Headless packages of Linux distributions ship the AWT implementation for headless
+    // environments only.
+    let headless_libraries = target.lib_dirs(java_home).iter().any(|dir| {
+        let has = |name: &str| dir.join(name).is_file();
+        has("libawt_headless.so") && !has("libawt_xawt.so")
+    });
+    if headless_libraries {
+        return true;
+    }
+
+    // The `release` file of JDK 9 and later lists the modules of the runtime image.
+    let Ok(release) = std::fs::read_to_string(java_home.join("release")) else {
+        return false;
+    };
+    release
+        .lines()
+        .find_map(|line| line.strip_prefix("MODULES="))
+        .is_some_and(|modules| {
+            !modules
+                .split_whitespace()
+                .any(|module| module.trim_matches('"') == "java.desktop")
+        })
+}
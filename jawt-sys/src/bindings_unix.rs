@@ -21,6 +21,43 @@ pub struct jawt_Rectangle {
     pub width: jint,
     pub height: jint,
 }
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn bindgen_test_layout_jawt_Rectangle() {
+    const UNINIT: ::core::mem::MaybeUninit<jawt_Rectangle> = ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::core::mem::size_of::<jawt_Rectangle>(),
+        16usize,
+        "Size of jawt_Rectangle"
+    );
+    assert_eq!(
+        ::core::mem::align_of::<jawt_Rectangle>(),
+        4usize,
+        "Alignment of jawt_Rectangle"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).x) as usize - ptr as usize },
+        0usize,
+        "Offset of field: jawt_Rectangle::x"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).y) as usize - ptr as usize },
+        4usize,
+        "Offset of field: jawt_Rectangle::y"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).width) as usize - ptr as usize },
+        8usize,
+        "Offset of field: jawt_Rectangle::width"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).height) as usize - ptr as usize },
+        12usize,
+        "Offset of field: jawt_Rectangle::height"
+    );
+}
+#[cfg(target_pointer_width = "32")]
 #[test]
 fn bindgen_test_layout_jawt_Rectangle() {
     const UNINIT: ::core::mem::MaybeUninit<jawt_Rectangle> = ::core::mem::MaybeUninit::uninit();
@@ -65,6 +102,7 @@ pub struct jawt_DrawingSurfaceInfo {
     pub clipSize: jint,
     pub clip: *mut JAWT_Rectangle,
 }
+#[cfg(target_pointer_width = "64")]
 #[test]
 fn bindgen_test_layout_jawt_DrawingSurfaceInfo() {
     const UNINIT: ::core::mem::MaybeUninit<jawt_DrawingSurfaceInfo> =
@@ -106,6 +144,48 @@ fn bindgen_test_layout_jawt_DrawingSurfaceInfo() {
         "Offset of field: jawt_DrawingSurfaceInfo::clip"
     );
 }
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn bindgen_test_layout_jawt_DrawingSurfaceInfo() {
+    const UNINIT: ::core::mem::MaybeUninit<jawt_DrawingSurfaceInfo> =
+        ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::core::mem::size_of::<jawt_DrawingSurfaceInfo>(),
+        32usize,
+        "Size of jawt_DrawingSurfaceInfo"
+    );
+    assert_eq!(
+        ::core::mem::align_of::<jawt_DrawingSurfaceInfo>(),
+        4usize,
+        "Alignment of jawt_DrawingSurfaceInfo"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).platformInfo) as usize - ptr as usize },
+        0usize,
+        "Offset of field: jawt_DrawingSurfaceInfo::platformInfo"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).ds) as usize - ptr as usize },
+        4usize,
+        "Offset of field: jawt_DrawingSurfaceInfo::ds"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).bounds) as usize - ptr as usize },
+        8usize,
+        "Offset of field: jawt_DrawingSurfaceInfo::bounds"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).clipSize) as usize - ptr as usize },
+        24usize,
+        "Offset of field: jawt_DrawingSurfaceInfo::clipSize"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).clip) as usize - ptr as usize },
+        28usize,
+        "Offset of field: jawt_DrawingSurfaceInfo::clip"
+    );
+}
 pub type JAWT_DrawingSurfaceInfo = jawt_DrawingSurfaceInfo;
 #[repr(C)]
 pub struct jawt_DrawingSurface {
@@ -119,6 +199,7 @@ pub struct jawt_DrawingSurface {
         ::core::option::Option<unsafe extern "C" fn(dsi: *mut JAWT_DrawingSurfaceInfo)>,
     pub Unlock: ::core::option::Option<unsafe extern "C" fn(ds: *mut jawt_DrawingSurface)>,
 }
+#[cfg(target_pointer_width = "64")]
 #[test]
 fn bindgen_test_layout_jawt_DrawingSurface() {
     const UNINIT: ::core::mem::MaybeUninit<jawt_DrawingSurface> =
@@ -165,6 +246,53 @@ fn bindgen_test_layout_jawt_DrawingSurface() {
         "Offset of field: jawt_DrawingSurface::Unlock"
     );
 }
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn bindgen_test_layout_jawt_DrawingSurface() {
+    const UNINIT: ::core::mem::MaybeUninit<jawt_DrawingSurface> =
+        ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::core::mem::size_of::<jawt_DrawingSurface>(),
+        24usize,
+        "Size of jawt_DrawingSurface"
+    );
+    assert_eq!(
+        ::core::mem::align_of::<jawt_DrawingSurface>(),
+        4usize,
+        "Alignment of jawt_DrawingSurface"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).env) as usize - ptr as usize },
+        0usize,
+        "Offset of field: jawt_DrawingSurface::env"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).target) as usize - ptr as usize },
+        4usize,
+        "Offset of field: jawt_DrawingSurface::target"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).Lock) as usize - ptr as usize },
+        8usize,
+        "Offset of field: jawt_DrawingSurface::Lock"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).GetDrawingSurfaceInfo) as usize - ptr as usize },
+        12usize,
+        "Offset of field: jawt_DrawingSurface::GetDrawingSurfaceInfo"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).FreeDrawingSurfaceInfo) as usize - ptr as usize },
+        16usize,
+        "Offset of field: jawt_DrawingSurface::FreeDrawingSurfaceInfo"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).Unlock) as usize - ptr as usize },
+        20usize,
+        "Offset of field: jawt_DrawingSurface::Unlock"
+    );
+}
 pub type JAWT_DrawingSurface = jawt_DrawingSurface;
 #[repr(C)]
 pub struct jawt {
@@ -199,6 +327,7 @@ pub struct jawt {
         unsafe extern "C" fn(env: *mut JNIEnv, embeddedFrame: jobject, doActivate: jboolean),
     >,
 }
+#[cfg(target_pointer_width = "64")]
 #[test]
 fn bindgen_test_layout_jawt() {
     const UNINIT: ::core::mem::MaybeUninit<jawt> = ::core::mem::MaybeUninit::uninit();
@@ -251,6 +380,59 @@ fn bindgen_test_layout_jawt() {
         "Offset of field: jawt::SynthesizeWindowActivation"
     );
 }
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn bindgen_test_layout_jawt() {
+    const UNINIT: ::core::mem::MaybeUninit<jawt> = ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(::core::mem::size_of::<jawt>(), 36usize, "Size of jawt");
+    assert_eq!(::core::mem::align_of::<jawt>(), 4usize, "Alignment of jawt");
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).version) as usize - ptr as usize },
+        0usize,
+        "Offset of field: jawt::version"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).GetDrawingSurface) as usize - ptr as usize },
+        4usize,
+        "Offset of field: jawt::GetDrawingSurface"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).FreeDrawingSurface) as usize - ptr as usize },
+        8usize,
+        "Offset of field: jawt::FreeDrawingSurface"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).Lock) as usize - ptr as usize },
+        12usize,
+        "Offset of field: jawt::Lock"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).Unlock) as usize - ptr as usize },
+        16usize,
+        "Offset of field: jawt::Unlock"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).GetComponent) as usize - ptr as usize },
+        20usize,
+        "Offset of field: jawt::GetComponent"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).CreateEmbeddedFrame) as usize - ptr as usize },
+        24usize,
+        "Offset of field: jawt::CreateEmbeddedFrame"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).SetBounds) as usize - ptr as usize },
+        28usize,
+        "Offset of field: jawt::SetBounds"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).SynthesizeWindowActivation) as usize - ptr as usize },
+        32usize,
+        "Offset of field: jawt::SynthesizeWindowActivation"
+    );
+}
 pub type JAWT = jawt;
 extern "C" {
     #[cfg(feature = "static-get-awt")]
@@ -272,6 +454,7 @@ pub struct jawt_X11DrawingSurfaceInfo {
         ) -> ::core::ffi::c_int,
     >,
 }
+#[cfg(target_pointer_width = "64")]
 #[test]
 fn bindgen_test_layout_jawt_X11DrawingSurfaceInfo() {
     const UNINIT: ::core::mem::MaybeUninit<jawt_X11DrawingSurfaceInfo> =
@@ -318,4 +501,51 @@ fn bindgen_test_layout_jawt_X11DrawingSurfaceInfo() {
         "Offset of field: jawt_X11DrawingSurfaceInfo::GetAWTColor"
     );
 }
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn bindgen_test_layout_jawt_X11DrawingSurfaceInfo() {
+    const UNINIT: ::core::mem::MaybeUninit<jawt_X11DrawingSurfaceInfo> =
+        ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::core::mem::size_of::<jawt_X11DrawingSurfaceInfo>(),
+        24usize,
+        "Size of jawt_X11DrawingSurfaceInfo"
+    );
+    assert_eq!(
+        ::core::mem::align_of::<jawt_X11DrawingSurfaceInfo>(),
+        4usize,
+        "Alignment of jawt_X11DrawingSurfaceInfo"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).drawable) as usize - ptr as usize },
+        0usize,
+        "Offset of field: jawt_X11DrawingSurfaceInfo::drawable"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).display) as usize - ptr as usize },
+        4usize,
+        "Offset of field: jawt_X11DrawingSurfaceInfo::display"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).visualID) as usize - ptr as usize },
+        8usize,
+        "Offset of field: jawt_X11DrawingSurfaceInfo::visualID"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).colormapID) as usize - ptr as usize },
+        12usize,
+        "Offset of field: jawt_X11DrawingSurfaceInfo::colormapID"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).depth) as usize - ptr as usize },
+        16usize,
+        "Offset of field: jawt_X11DrawingSurfaceInfo::depth"
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).GetAWTColor) as usize - ptr as usize },
+        20usize,
+        "Offset of field: jawt_X11DrawingSurfaceInfo::GetAWTColor"
+    );
+}
 pub type JAWT_X11DrawingSurfaceInfo = jawt_X11DrawingSurfaceInfo;
@@ -0,0 +1,295 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [run_with_canvas()].
+
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use jawt::{Awt, AwtVersion, DrawingSurface, DrawingSurfaceInfo, DrawingSurfaceLockResult};
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use crate::error::{call, or_skip};
+use crate::jvm::check_display;
+use crate::{java_vm, HarnessError};
+
+/// Options of [run_with_canvas()].
+#[derive(Debug, Clone)]
+pub struct HarnessOptions {
+    /// Title of the Frame.
+    pub title: String,
+    /// Preferred width of the Canvas.
+    pub width: i32,
+    /// Preferred height of the Canvas.
+    pub height: i32,
+    /// How long to wait for the Canvas to become showing and lockable.
+    pub timeout: Duration,
+    /// Options passed to the JVM if it is not started yet. See [java_vm()].
+    pub jvm_options: Vec<String>,
+}
+
+impl Default for HarnessOptions {
+    fn default() -> Self {
+        Self {
+            title: "jawt-test-harness".to_string(),
+            width: 320,
+            height: 240,
+            timeout: Duration::from_secs(10),
+            jvm_options: Vec::new(),
+        }
+    }
+}
+
+/// What the closure passed to [run_with_canvas()] receives.
+pub struct CanvasContext<'a, 'local> {
+    /// The JNI environment of the current thread.
+    pub env: &'a mut JNIEnv<'local>,
+    /// The AWT native interface.
+    pub awt: &'a Awt,
+    /// The drawing surface of [CanvasContext::canvas].
+    pub drawing_surface: &'a mut DrawingSurface,
+    /// The `java.awt.Frame`.
+    pub frame: &'a JObject<'local>,
+    /// The `java.awt.Canvas` inside [CanvasContext::frame].
+    pub canvas: &'a JObject<'local>,
+    /// [HarnessOptions::timeout].
+    pub timeout: Duration,
+}
+
+impl CanvasContext<'_, '_> {
+    /// Resizes the Canvas with `Component.setSize()` and locks the drawing surface until its
+    /// bounds have the new size. Returns the union of the lock results reported meanwhile, which
+    /// renderers pass to their `update` methods as if they had seen every lock.
+    pub fn resize_canvas(
+        &mut self,
+        width: i32,
+        height: i32,
+    ) -> Result<DrawingSurfaceLockResult, HarnessError> {
+        let canvas = self.canvas;
+        call(self.env, |env| {
+            env.call_method(
+                canvas,
+                "setSize",
+                "(II)V",
+                &[JValue::Int(width), JValue::Int(height)],
+            )
+        })?;
+
+        self.lock_until(|_, info| {
+            let bounds = info.bounds();
+            bounds.width == width && bounds.height == height
+        })
+    }
+
+    /// Removes the Canvas from the Frame and adds it again, so AWT destroys its native window and
+    /// creates a new one. Locks the drawing surface until it reports
+    /// [DrawingSurfaceLockResult::SURFACE_CHANGED] and returns the union of the lock results
+    /// reported meanwhile.
+    pub fn recreate_canvas_peer(&mut self) -> Result<DrawingSurfaceLockResult, HarnessError> {
+        let (frame, canvas) = (self.frame, self.canvas);
+        call(self.env, |env| {
+            env.call_method(
+                frame,
+                "remove",
+                "(Ljava/awt/Component;)V",
+                &[JValue::Object(canvas)],
+            )
+        })?;
+        call(self.env, |env| {
+            env.call_method(
+                frame,
+                "add",
+                "(Ljava/awt/Component;)Ljava/awt/Component;",
+                &[JValue::Object(canvas)],
+            )
+        })?;
+        call(self.env, |env| {
+            env.call_method(frame, "validate", "()V", &[])
+        })?;
+        self.lock_until(|lock_result, _| {
+            lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED)
+        })
+    }
+
+    /// Locks the drawing surface until `done` returns `true` and returns the union of the lock
+    /// results.
+    fn lock_until(
+        &mut self,
+        mut done: impl FnMut(DrawingSurfaceLockResult, &DrawingSurfaceInfo) -> bool,
+    ) -> Result<DrawingSurfaceLockResult, HarnessError> {
+        let deadline = Instant::now() + self.timeout;
+        let mut changes = DrawingSurfaceLockResult::empty();
+        loop {
+            if let Some((lock_result, mut guard)) = self.drawing_surface.lock() {
+                changes |= lock_result;
+                if let Some(info) = guard.drawing_surface_info() {
+                    if done(lock_result, &info) {
+                        return Ok(changes);
+                    }
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(HarnessError::Timeout(self.timeout));
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Creates a Frame containing a Canvas, waits until the Canvas is showing and its drawing surface
+/// can be locked, and runs `f` on the current thread. The drawing surface is freed and the Frame
+/// is disposed afterwards, even if `f` panics.
+pub fn run_with_canvas<R>(
+    options: &HarnessOptions,
+    f: impl FnOnce(CanvasContext<'_, '_>) -> R,
+) -> Result<R, HarnessError> {
+    check_display()?;
+    let java_vm = java_vm(&options.jvm_options)?;
+    let mut env = java_vm.attach_current_thread()?;
+
+    let result = env.with_local_frame(16, |env| -> Result<_, HarnessError> {
+        let (frame, canvas) = create_frame(env, options)?;
+        let result = run_on_canvas(env, options, &frame, &canvas, f);
+        let disposed = call(env, |env| env.call_method(&frame, "dispose", "()V", &[]));
+        let result = result?;
+        disposed?;
+        Ok(result)
+    })?;
+    match result {
+        Ok(result) => Ok(result),
+        Err(panic) => panic::resume_unwind(panic),
+    }
+}
+
+/// Same as [run_with_canvas()], but skips the test as described in [or_skip()] if the environment
+/// is missing and panics on other harness errors.
+#[track_caller]
+pub fn run_with_canvas_or_skip<R>(
+    options: &HarnessOptions,
+    f: impl FnOnce(CanvasContext<'_, '_>) -> R,
+) -> Option<R> {
+    or_skip(run_with_canvas(options, f))
+}
+
+fn create_frame<'local>(
+    env: &mut JNIEnv<'local>,
+    options: &HarnessOptions,
+) -> Result<(JObject<'local>, JObject<'local>), HarnessError> {
+    let title = call(env, |env| env.new_string(&options.title))?;
+    let frame = call(env, |env| {
+        env.new_object(
+            "java/awt/Frame",
+            "(Ljava/lang/String;)V",
+            &[JValue::Object(&title)],
+        )
+    })?;
+    match add_canvas(env, options, &frame) {
+        Ok(canvas) => Ok((frame, canvas)),
+        Err(error) => {
+            // The Frame may already own a native peer if `pack` or `setVisible` threw.
+            let _ = call(env, |env| env.call_method(&frame, "dispose", "()V", &[]));
+            Err(error)
+        }
+    }
+}
+
+/// Adds a Canvas to `frame` and shows it.
+fn add_canvas<'local>(
+    env: &mut JNIEnv<'local>,
+    options: &HarnessOptions,
+    frame: &JObject<'local>,
+) -> Result<JObject<'local>, HarnessError> {
+    let canvas = call(env, |env| env.new_object("java/awt/Canvas", "()V", &[]))?;
+    let size = call(env, |env| {
+        env.new_object(
+            "java/awt/Dimension",
+            "(II)V",
+            &[JValue::Int(options.width), JValue::Int(options.height)],
+        )
+    })?;
+    call(env, |env| {
+        env.call_method(
+            &canvas,
+            "setPreferredSize",
+            "(Ljava/awt/Dimension;)V",
+            &[JValue::Object(&size)],
+        )
+    })?;
+    call(env, |env| {
+        env.call_method(
+            frame,
+            "add",
+            "(Ljava/awt/Component;)Ljava/awt/Component;",
+            &[JValue::Object(&canvas)],
+        )
+    })?;
+    call(env, |env| env.call_method(frame, "pack", "()V", &[]))?;
+    call(env, |env| {
+        env.call_method(frame, "setVisible", "(Z)V", &[JValue::Bool(1)])
+    })?;
+    Ok(canvas)
+}
+
+/// Runs `f` once the Canvas is showing, returning the panic of `f` as an [Err] in [Ok].
+#[allow(clippy::type_complexity)]
+fn run_on_canvas<'local, R>(
+    env: &mut JNIEnv<'local>,
+    options: &HarnessOptions,
+    frame: &JObject<'local>,
+    canvas: &JObject<'local>,
+    f: impl FnOnce(CanvasContext<'_, 'local>) -> R,
+) -> Result<thread::Result<R>, HarnessError> {
+    let deadline = Instant::now() + options.timeout;
+    let timeout = || HarnessError::Timeout(options.timeout);
+
+    while !call(env, |env| env.call_method(canvas, "isShowing", "()Z", &[]))?.z()? {
+        if Instant::now() >= deadline {
+            return Err(timeout());
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    sync_toolkit(env)?;
+
+    let awt =
+        Awt::from_version(env, AwtVersion::VERSION_1_7).ok_or(HarnessError::AwtUnavailable)?;
+    // Safety: `canvas` outlives the drawing surface, which is dropped before returning.
+    let target = unsafe { JObject::from_raw(canvas.as_raw()) };
+    let mut drawing_surface = awt
+        .drawing_surface(env, target)
+        .ok_or(HarnessError::DrawingSurfaceUnavailable)?;
+
+    // The native window may be created after the Canvas reports that it is showing.
+    while drawing_surface.lock().is_none() {
+        if Instant::now() >= deadline {
+            return Err(timeout());
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        f(CanvasContext {
+            env,
+            awt: &awt,
+            drawing_surface: &mut drawing_surface,
+            frame,
+            canvas,
+            timeout: options.timeout,
+        })
+    }));
+    Ok(result)
+}
+
+fn sync_toolkit(env: &mut JNIEnv) -> Result<(), HarnessError> {
+    let toolkit = call(env, |env| {
+        env.call_static_method(
+            "java/awt/Toolkit",
+            "getDefaultToolkit",
+            "()Ljava/awt/Toolkit;",
+            &[],
+        )
+    })?
+    .l()?;
+    call(env, |env| env.call_method(&toolkit, "sync", "()V", &[]))?;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [HarnessError] enum.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use jni::JNIEnv;
+
+/// Errors the harness can return.
+#[derive(Debug, Clone)]
+pub enum HarnessError {
+    /// No display to open a window on is configured, for example because `DISPLAY` is not set.
+    NoDisplay,
+    /// No JVM could be located or started.
+    JvmUnavailable(String),
+    /// A Java exception was thrown. Contains the result of `Throwable.toString()`.
+    Java(String),
+    /// A JNI call failed without throwing a Java exception.
+    Jni(String),
+    /// [Awt::from_version()](jawt::Awt::from_version()) failed.
+    AwtUnavailable,
+    /// [Awt::drawing_surface()](jawt::Awt::drawing_surface()) failed.
+    DrawingSurfaceUnavailable,
+    /// The Canvas did not become showing and lockable, or did not reach the size requested by
+    /// [CanvasContext::resize_canvas()](crate::CanvasContext::resize_canvas()), within the given
+    /// time.
+    Timeout(Duration),
+}
+
+impl HarnessError {
+    /// Returns `true` if the error is caused by a missing display or JDK rather than by the code
+    /// under test.
+    pub fn is_environment_missing(&self) -> bool {
+        match self {
+            Self::NoDisplay | Self::JvmUnavailable(_) => true,
+            Self::Java(message) => message.starts_with("java.awt.HeadlessException"),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoDisplay => write!(f, "no display is available; set DISPLAY"),
+            Self::JvmUnavailable(message) => write!(f, "failed to start a JVM: {message}"),
+            Self::Java(message) => write!(f, "a Java exception was thrown: {message}"),
+            Self::Jni(message) => write!(f, "a JNI call failed: {message}"),
+            Self::AwtUnavailable => write!(f, "failed to get the AWT native interface"),
+            Self::DrawingSurfaceUnavailable => {
+                write!(f, "failed to get the drawing surface of the Canvas")
+            }
+            Self::Timeout(timeout) => {
+                write!(f, "the Canvas did not become ready in {timeout:?}")
+            }
+        }
+    }
+}
+
+impl Error for HarnessError {}
+
+impl From<jni::errors::Error> for HarnessError {
+    fn from(value: jni::errors::Error) -> Self {
+        Self::Jni(value.to_string())
+    }
+}
+
+/// Returns the value of `result`, or [None] if the error is caused by a missing environment as
+/// described in [HarnessError::is_environment_missing()]. Tests return early on [None], so they
+/// are skipped rather than failed on machines without a JDK or a display. Panics on any other
+/// error, and on every error if the `CI` environment variable is set, as the CI workflows provide
+/// both.
+#[track_caller]
+pub fn or_skip<T>(result: Result<T, HarnessError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(error) if error.is_environment_missing() && env::var_os("CI").is_none() => {
+            eprintln!("skipping: {error}");
+            None
+        }
+        Err(error) => panic!("{error}"),
+    }
+}
+
+/// Skips the rest of a test because an optional dependency described by `reason`, such as a
+/// Vulkan driver, is missing. Panics instead if the `CI` environment variable is set, as the CI
+/// workflows install every dependency the tests use.
+#[track_caller]
+pub fn skip_unless_ci(reason: &str) {
+    if env::var_os("CI").is_some() {
+        panic!("{reason}");
+    }
+    eprintln!("skipping: {reason}");
+}
+
+/// Runs a JNI call, describing and clearing the pending Java exception if it throws one.
+pub(crate) fn call<'local, T>(
+    env: &mut JNIEnv<'local>,
+    f: impl FnOnce(&mut JNIEnv<'local>) -> jni::errors::Result<T>,
+) -> Result<T, HarnessError> {
+    match f(env) {
+        Ok(value) => Ok(value),
+        Err(jni::errors::Error::JavaException) => Err(HarnessError::Java(describe_exception(env))),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn describe_exception(env: &mut JNIEnv) -> String {
+    let Ok(throwable) = env.exception_occurred() else {
+        return "unknown exception".to_string();
+    };
+    let _ = env.exception_clear();
+    let description = env
+        .call_method(&throwable, "toString", "()Ljava/lang/String;", &[])
+        .and_then(|description| description.l())
+        .and_then(|description| env.get_string(&description.into()).map(String::from));
+    let _ = env.exception_clear();
+    description.unwrap_or_else(|_| "unknown exception".to_string())
+}
@@ -0,0 +1,30 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [clear_with()].
+
+use std::ffi::{c_void, CStr};
+use std::mem;
+
+const GL_COLOR_BUFFER_BIT: u32 = 0x4000;
+
+type GlClearColor = unsafe extern "C" fn(f32, f32, f32, f32);
+type GlClear = unsafe extern "C" fn(u32);
+
+/// Clears the color buffer of the OpenGL or OpenGL ES context current on the calling thread to
+/// `color`, given as RGBA. `proc_address` looks up GL functions, such as
+/// `GlxSurface::proc_address()` or `EglSurface::proc_address()`. Panics if `glClearColor` or
+/// `glClear` can't be found.
+pub fn clear_with(proc_address: impl Fn(&CStr) -> *const c_void, color: [f32; 4]) {
+    let load = |name: &[u8]| {
+        let address = proc_address(CStr::from_bytes_with_nul(name).unwrap());
+        assert!(!address.is_null(), "{name:?} is not available");
+        address
+    };
+    // Safety: the addresses are the GL functions of the same names.
+    unsafe {
+        let clear_color: GlClearColor = mem::transmute(load(b"glClearColor\0"));
+        let clear: GlClear = mem::transmute(load(b"glClear\0"));
+        clear_color(color[0], color[1], color[2], color[3]);
+        clear(GL_COLOR_BUFFER_BIT);
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [image_rgb()].
+
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use crate::error::call;
+use crate::HarnessError;
+
+/// Returns the pixel of `image`, a `java.awt.image.BufferedImage`, at (`x`, `y`) in the default
+/// ARGB color model, as returned by `getRGB`.
+pub fn image_rgb(env: &mut JNIEnv, image: &JObject, x: i32, y: i32) -> Result<u32, HarnessError> {
+    let rgb = call(env, |env| {
+        env.call_method(image, "getRGB", "(II)I", &[JValue::Int(x), JValue::Int(y)])?
+            .i()
+    })?;
+    Ok(rgb as u32)
+}
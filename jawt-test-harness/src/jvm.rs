@@ -0,0 +1,49 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Starts the JVM shared by every test in a process.
+
+use jni::{InitArgsBuilder, JNIVersion, JavaVM};
+use once_cell::sync::OnceCell;
+
+use crate::HarnessError;
+
+/// Returns the JVM of this process, starting it with `options` on the first call. A process can
+/// start only one JVM, so the JVM is never destroyed and `options` of later calls are ignored.
+///
+/// AWT is always started in non-headless mode.
+pub fn java_vm(options: &[String]) -> Result<&'static JavaVM, HarnessError> {
+    static JAVA_VM: OnceCell<JavaVM> = OnceCell::new();
+
+    JAVA_VM.get_or_try_init(|| {
+        let mut builder = InitArgsBuilder::new()
+            .version(JNIVersion::V8)
+            .option("-Djava.awt.headless=false");
+        for option in options {
+            builder = builder.option(option);
+        }
+        let args = builder
+            .build()
+            .map_err(|error| HarnessError::JvmUnavailable(error.to_string()))?;
+        JavaVM::new(args).map_err(|error| HarnessError::JvmUnavailable(error.to_string()))
+    })
+}
+
+/// Returns the JVM of this process, starting it with AWT in headless mode on the first call. Tests
+/// that open no window use it so that they run without a display. As with [java_vm()], the mode
+/// is decided by whichever call starts the JVM.
+pub fn headless_java_vm() -> Result<&'static JavaVM, HarnessError> {
+    java_vm(&["-Djava.awt.headless=true".to_string()])
+}
+
+/// Returns [HarnessError::NoDisplay] if AWT has no display to open windows on.
+pub(crate) fn check_display() -> Result<(), HarnessError> {
+    if cfg!(all(
+        target_family = "unix",
+        not(target_vendor = "apple"),
+        not(target_os = "android")
+    )) && std::env::var_os("DISPLAY").map_or(true, |display| display.is_empty())
+    {
+        return Err(HarnessError::NoDisplay);
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Integration test harness for [jawt]. It boots a JVM with the JNI invocation API, creates a
+//! [java.awt.Frame] containing a [java.awt.Canvas] through JNI, and runs a closure with the [Awt]
+//! and the [DrawingSurface] of the Canvas.
+//!
+//! The JVM is located through `JAVA_HOME` or the `java` executable on `PATH`. On Linux, an X
+//! server such as Xvfb must be reachable through `DISPLAY`. Tests should skip themselves when
+//! [HarnessError::is_environment_missing()] returns `true`, which [or_skip()] and
+//! [run_with_canvas_or_skip()] do.
+//!
+//! [mock] provides drawing surfaces implemented in Rust for tests that need neither.
+//!
+//! [java.awt.Frame]: https://docs.oracle.com/en/java/javase/17/docs/api/java.desktop/java/awt/Frame.html
+//! [java.awt.Canvas]: https://docs.oracle.com/en/java/javase/17/docs/api/java.desktop/java/awt/Canvas.html
+//! [Awt]: jawt::Awt
+//! [DrawingSurface]: jawt::DrawingSurface
+
+mod canvas;
+pub use canvas::*;
+
+mod error;
+pub use error::*;
+
+mod gl;
+pub use gl::*;
+
+mod image;
+pub use image::*;
+
+mod jvm;
+pub use jvm::*;
+
+pub mod mock;
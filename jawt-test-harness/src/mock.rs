@@ -0,0 +1,127 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Mock JAWT tables and drawing surfaces implemented in Rust, for testing code that reads drawing
+//! surface information without a JVM or a display.
+
+use std::ffi::c_void;
+use std::ptr::{self, NonNull};
+
+use jawt::sys::{JAWT_DrawingSurface, JAWT_DrawingSurfaceInfo, JAWT};
+use jawt::{Awt, AwtVersion, DrawingSurface, Rect};
+use jni::sys::{jint, jobject, JNIEnv};
+
+/// Creates an [Awt] reporting `version`, for code that checks the version of the JAWT it got a
+/// drawing surface from. The table has no functions, so calling any of them panics.
+pub fn mock_awt(version: AwtVersion) -> Awt {
+    // Safety: every function is `None`, which `Awt` reports instead of calling.
+    unsafe {
+        Awt::from_inner(JAWT {
+            version: version.inner(),
+            GetDrawingSurface: None,
+            FreeDrawingSurface: None,
+            Lock: None,
+            Unlock: None,
+            GetComponent: None,
+            CreateEmbeddedFrame: None,
+            SetBounds: None,
+            SynthesizeWindowActivation: None,
+        })
+    }
+}
+
+/// Creates a JAWT table reporting `version` whose drawing surfaces are implemented in Rust, for
+/// code that calls the JAWT functions directly, such as tests of `jawt::validation::wrap()`.
+///
+/// `GetDrawingSurface` returns drawing surfaces like [mock_drawing_surface()] with no platform
+/// information and empty bounds for any component, and `FreeDrawingSurface` frees them. `Lock`
+/// and `Unlock` do nothing, and the other functions are missing.
+pub fn mock_jawt(version: AwtVersion) -> JAWT {
+    JAWT {
+        version: version.inner(),
+        GetDrawingSurface: Some(get_drawing_surface),
+        FreeDrawingSurface: Some(free_drawing_surface),
+        Lock: Some(lock_awt),
+        Unlock: Some(unlock_awt),
+        GetComponent: None,
+        CreateEmbeddedFrame: None,
+        SetBounds: None,
+        SynthesizeWindowActivation: None,
+    }
+}
+
+#[repr(C)]
+struct MockDrawingSurface {
+    // Must be the first field, as the functions below cast between the two.
+    ds: JAWT_DrawingSurface,
+    dsi: JAWT_DrawingSurfaceInfo,
+}
+
+/// Creates a [DrawingSurface] whose JAWT functions are implemented in Rust. Locking always
+/// succeeds, and the drawing surface information reports `platform_info`, `bounds` and no clip.
+///
+/// # Safety
+///
+/// `platform_info` must be null or outlive the returned [DrawingSurface].
+pub unsafe fn mock_drawing_surface(platform_info: *mut c_void, bounds: Rect) -> DrawingSurface {
+    let mock = new_mock_drawing_surface(platform_info, bounds);
+    unsafe { DrawingSurface::from_raw_parts(NonNull::new_unchecked(mock), free_drawing_surface) }
+}
+
+fn new_mock_drawing_surface(platform_info: *mut c_void, bounds: Rect) -> *mut JAWT_DrawingSurface {
+    let mock = Box::into_raw(Box::new(MockDrawingSurface {
+        ds: JAWT_DrawingSurface {
+            env: ptr::null_mut(),
+            target: ptr::null_mut(),
+            Lock: Some(lock),
+            GetDrawingSurfaceInfo: Some(get_drawing_surface_info),
+            FreeDrawingSurfaceInfo: Some(free_drawing_surface_info),
+            Unlock: Some(unlock),
+        },
+        dsi: JAWT_DrawingSurfaceInfo {
+            platformInfo: platform_info,
+            ds: ptr::null_mut(),
+            bounds: bounds.into_sys(),
+            clipSize: 0,
+            clip: ptr::null_mut(),
+        },
+    }));
+    unsafe {
+        (*mock).dsi.ds = ptr::addr_of_mut!((*mock).ds);
+    }
+    mock.cast()
+}
+
+unsafe extern "C" fn get_drawing_surface(
+    _env: *mut JNIEnv,
+    _target: jobject,
+) -> *mut JAWT_DrawingSurface {
+    let bounds = Rect {
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+    };
+    new_mock_drawing_surface(ptr::null_mut(), bounds)
+}
+
+unsafe extern "C" fn lock_awt(_env: *mut JNIEnv) {}
+
+unsafe extern "C" fn unlock_awt(_env: *mut JNIEnv) {}
+
+unsafe extern "C" fn lock(_ds: *mut JAWT_DrawingSurface) -> jint {
+    0
+}
+
+unsafe extern "C" fn get_drawing_surface_info(
+    ds: *mut JAWT_DrawingSurface,
+) -> *mut JAWT_DrawingSurfaceInfo {
+    unsafe { ptr::addr_of_mut!((*ds.cast::<MockDrawingSurface>()).dsi) }
+}
+
+unsafe extern "C" fn free_drawing_surface_info(_dsi: *mut JAWT_DrawingSurfaceInfo) {}
+
+unsafe extern "C" fn unlock(_ds: *mut JAWT_DrawingSurface) {}
+
+unsafe extern "C" fn free_drawing_surface(ds: *mut JAWT_DrawingSurface) {
+    drop(unsafe { Box::from_raw(ds.cast::<MockDrawingSurface>()) });
+}
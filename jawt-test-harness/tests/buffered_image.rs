@@ -0,0 +1,67 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use jawt::{BufferedImage, BufferedImageType, Rect};
+use jawt_test_harness::{headless_java_vm, image_rgb, or_skip};
+
+#[test]
+fn pixels_written_from_rust_are_drawn() {
+    let Some(java_vm) = or_skip(headless_java_vm()) else {
+        return;
+    };
+    let mut env = java_vm.attach_current_thread().unwrap();
+
+    let image = BufferedImage::new(&mut env, 4, 3, BufferedImageType::IntRgb).unwrap();
+    assert_eq!((image.width(), image.height()), (4, 3));
+    let mut pixels = [0x0000ff; 12];
+    pixels[4 + 1] = 0xff0000;
+    image.write_pixels(&mut env, 0, &pixels).unwrap();
+    image.write_pixels(&mut env, 4 + 3, &[0x00ff00]).unwrap();
+    assert!(image.write_pixels(&mut env, 10, &pixels[..3]).is_err());
+    env.exception_clear().unwrap();
+    let mut row = [0; 4];
+    image.read_pixels(&mut env, 4, &mut row).unwrap();
+    assert_eq!(row, [0x0000ff, 0xff0000, 0x0000ff, 0x00ff00]);
+    assert_eq!(
+        image_rgb(&mut env, image.as_obj(), 0, 0).unwrap(),
+        0xff0000ff
+    );
+    assert_eq!(
+        image_rgb(&mut env, image.as_obj(), 1, 1).unwrap(),
+        0xffff0000
+    );
+
+    let target = BufferedImage::new(&mut env, 8, 6, BufferedImageType::IntArgb).unwrap();
+    let graphics = env
+        .call_method(
+            target.as_obj(),
+            "createGraphics",
+            "()Ljava/awt/Graphics2D;",
+            &[],
+        )
+        .unwrap()
+        .l()
+        .unwrap();
+    assert!(image.draw(&mut env, &graphics, 2, 2).unwrap());
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 3, 3).unwrap(),
+        0xffff0000
+    );
+    assert_eq!(image_rgb(&mut env, target.as_obj(), 1, 1).unwrap(), 0);
+
+    let bounds = Rect {
+        x: 0,
+        y: 0,
+        width: 8,
+        height: 6,
+    };
+    assert!(image.draw_scaled(&mut env, &graphics, bounds).unwrap());
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 0, 0).unwrap(),
+        0xff0000ff
+    );
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 2, 2).unwrap(),
+        0xffff0000
+    );
+    env.call_method(&graphics, "dispose", "()V", &[]).unwrap();
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use std::ptr;
+
+use jawt::{DamageRegion, DamageTracker, Rect};
+use jawt_test_harness::mock::mock_drawing_surface;
+use jawt_test_harness::{headless_java_vm, or_skip, run_with_canvas_or_skip, HarnessOptions};
+
+#[test]
+fn damage_is_clipped_to_the_canvas() {
+    let options = HarnessOptions::default();
+    let result = run_with_canvas_or_skip(&options, |context| {
+        let tracker = DamageTracker::new(context.env, context.canvas)
+            .expect("failed to create the damage tracker");
+        tracker.add(Rect {
+            x: 10,
+            y: 10,
+            width: 20,
+            height: 20,
+        });
+        tracker.add(Rect {
+            x: 300,
+            y: 200,
+            width: 100,
+            height: 100,
+        });
+
+        let repainted = tracker
+            .request_repaint(context.env)
+            .expect("failed to request a repaint");
+        assert_eq!(
+            repainted,
+            Some(Rect {
+                x: 10,
+                y: 10,
+                width: 390,
+                height: 290,
+            })
+        );
+
+        let (_, mut guard) = context
+            .drawing_surface
+            .lock()
+            .expect("failed to lock the drawing surface");
+        let info = guard
+            .drawing_surface_info()
+            .expect("failed to get the drawing surface info");
+        let mut damage = tracker.take(&info);
+        damage.sort();
+        assert!(tracker.damage().is_empty());
+        damage
+    });
+    let Some(damage) = result else {
+        return;
+    };
+    assert_eq!(
+        damage,
+        [
+            Rect {
+                x: 10,
+                y: 10,
+                width: 20,
+                height: 20,
+            },
+            Rect {
+                x: 300,
+                y: 200,
+                width: options.width - 300,
+                height: options.height - 200,
+            },
+        ]
+    );
+}
+
+#[test]
+fn nothing_is_taken_with_an_empty_clip() {
+    let Some(java_vm) = or_skip(headless_java_vm()) else {
+        return;
+    };
+    let mut env = java_vm.attach_current_thread().unwrap();
+    let component = env.new_object("java/awt/Canvas", "()V", &[]).unwrap();
+    let tracker = DamageTracker::new(&env, &component).unwrap();
+    tracker.add(Rect {
+        x: 10,
+        y: 10,
+        width: 20,
+        height: 20,
+    });
+
+    let bounds = Rect {
+        x: 0,
+        y: 0,
+        width: 100,
+        height: 100,
+    };
+    // Safety: the platform information is null.
+    let mut drawing_surface = unsafe { mock_drawing_surface(ptr::null_mut(), bounds) };
+    let (_, mut guard) = drawing_surface.lock().unwrap();
+    let info = guard.drawing_surface_info().unwrap();
+    assert!(info.clip().is_empty());
+    assert_eq!(tracker.take(&info), []);
+    assert!(tracker.damage().is_empty());
+}
+
+#[test]
+fn huge_rects_saturate_instead_of_overflowing() {
+    let huge = Rect {
+        x: 0,
+        y: 0,
+        width: i32::MAX,
+        height: i32::MAX,
+    };
+    let canvas = Rect {
+        x: 10,
+        y: 20,
+        width: 640,
+        height: 480,
+    };
+    assert!(huge.contains(&canvas));
+    assert!(!canvas.contains(&huge));
+    assert_eq!(huge.intersection(&canvas), Some(canvas));
+    assert_eq!(huge.union(&canvas), huge);
+    assert_eq!(
+        huge.union(&canvas.translate(-20, -40)),
+        Rect {
+            x: -10,
+            y: -20,
+            width: i32::MAX,
+            height: i32::MAX,
+        }
+    );
+    let shifted = huge.translate(canvas.x, canvas.y);
+    assert!(shifted.contains(&canvas));
+    assert_eq!(shifted.intersection(&canvas), Some(canvas));
+    assert_eq!(
+        shifted.intersection(&huge),
+        Some(Rect {
+            x: canvas.x,
+            y: canvas.y,
+            width: i32::MAX - canvas.x,
+            height: i32::MAX - canvas.y,
+        })
+    );
+    assert_eq!(
+        huge.translate(i32::MAX, i32::MIN),
+        Rect {
+            x: i32::MAX,
+            y: i32::MIN,
+            width: i32::MAX,
+            height: i32::MAX,
+        }
+    );
+
+    let mut region = DamageRegion::new();
+    region.add(canvas);
+    region.add(huge);
+    assert_eq!(region.rects(), [huge]);
+    let clip = [canvas.translate(-canvas.x, -canvas.y)];
+    assert_eq!(region.clip(&clip), clip);
+}
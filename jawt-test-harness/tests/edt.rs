@@ -0,0 +1,38 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use jawt::edt::{assert_dispatch_thread, invoke_and_wait, invoke_later, is_dispatch_thread};
+use jawt_test_harness::{run_with_canvas_or_skip, HarnessOptions};
+
+#[test]
+fn closures_run_on_the_dispatch_thread() {
+    run_with_canvas_or_skip(&HarnessOptions::default(), |context| {
+        let env = &mut *context.env;
+        assert!(!is_dispatch_thread(env).unwrap());
+
+        let on_edt = invoke_and_wait(env, |env| is_dispatch_thread(env).unwrap()).unwrap();
+        assert!(on_edt);
+
+        let nested = invoke_and_wait(env, |env| invoke_and_wait(env, |_| 42).unwrap()).unwrap();
+        assert_eq!(nested, 42);
+
+        let task = invoke_later(env, |env| {
+            assert_dispatch_thread(env);
+            "done"
+        })
+        .unwrap();
+        assert_eq!(task.join().unwrap(), "done");
+
+        let panicked = panic::catch_unwind(AssertUnwindSafe(|| {
+            invoke_and_wait(env, |_| -> i32 { panic!("panicked on the EDT") })
+        }));
+        let payload = panicked.expect_err("the panic was not propagated");
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"panicked on the EDT"));
+
+        let task = invoke_later(env, |_| -> i32 { panic!("panicked later") }).unwrap();
+        assert!(task.join().is_err());
+
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| assert_dispatch_thread(env))).is_err());
+    });
+}
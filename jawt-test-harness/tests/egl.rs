@@ -0,0 +1,60 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+#![cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+
+use jawt::unix::{EglContextAttributes, EglSurface};
+use jawt::{DrawingSurfaceInfo, DrawingSurfaceLockResult};
+use jawt_test_harness::{clear_with, run_with_canvas_or_skip, HarnessOptions};
+
+fn render(surface: &EglSurface, info: &DrawingSurfaceInfo) {
+    surface.make_current(info).expect("failed to make current");
+    clear_with(|name| surface.proc_address(name), [1.0, 0.5, 0.0, 1.0]);
+    surface.swap_buffers(info).expect("failed to swap buffers");
+    surface.release_current(info);
+}
+
+#[test]
+fn surface_is_created_and_recreated_when_the_canvas_surface_changes() {
+    let options = HarnessOptions::default();
+    run_with_canvas_or_skip(&options, |mut context| {
+        let mut surface = {
+            let (_, mut guard) = context
+                .drawing_surface
+                .lock()
+                .expect("failed to lock the drawing surface");
+            let info = guard
+                .drawing_surface_info()
+                .expect("failed to get the drawing surface info");
+            let surface = EglSurface::new(&info, &EglContextAttributes::default())
+                .expect("failed to create the EGL surface");
+            assert!(!surface.surface().is_null());
+            render(&surface, &info);
+            surface
+        };
+        let old_window = surface.target().window();
+
+        let changes = context
+            .recreate_canvas_peer()
+            .expect("failed to re-create the Canvas peer");
+        assert!(changes.contains(DrawingSurfaceLockResult::SURFACE_CHANGED));
+
+        let (_, mut guard) = context
+            .drawing_surface
+            .lock()
+            .expect("failed to lock the drawing surface");
+        let info = guard
+            .drawing_surface_info()
+            .expect("failed to get the drawing surface info");
+        surface
+            .update(&info, changes)
+            .expect("failed to update the EGL surface");
+        assert!(!surface.surface().is_null());
+        assert_ne!(surface.target().window(), old_window);
+        render(&surface, &info);
+        surface.destroy(&info);
+    });
+}
@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use jawt::environment::{probe, Environment, Toolkit, Unsupported};
+use jawt_test_harness::{headless_java_vm, or_skip};
+
+#[test]
+fn headless_jvm_is_reported() {
+    let Some(java_vm) = or_skip(headless_java_vm()) else {
+        return;
+    };
+    let mut env = java_vm.attach_current_thread().unwrap();
+
+    let environment = probe(&mut env).expect("failed to probe the environment");
+    assert!(environment.desktop_module);
+    assert!(environment.headless);
+    assert_eq!(environment.toolkit, Some(Toolkit::Headless));
+    assert!(environment.java_version.is_some());
+    assert!(environment.java_vendor.is_some());
+    assert_eq!(
+        environment.drawing_surface_support(),
+        Err(Unsupported::Headless)
+    );
+    assert!(!env.exception_check().unwrap());
+}
+
+#[test]
+fn drawing_surface_support_follows_the_toolkit() {
+    let environment = Environment {
+        headless: false,
+        desktop_module: true,
+        toolkit: Some(Toolkit::X11),
+        toolkit_error: None,
+        java_version: Some("17".to_string()),
+        java_vendor: None,
+        display: true,
+        wayland_display: false,
+    };
+    assert!(environment.drawing_surface_expected());
+
+    let no_display = Environment {
+        display: false,
+        ..environment.clone()
+    };
+    assert_eq!(
+        no_display.drawing_surface_support(),
+        Err(Unsupported::NoDisplay)
+    );
+
+    let unknown = Environment {
+        toolkit: Some(Toolkit::from_class_name("com.example.Toolkit")),
+        ..environment.clone()
+    };
+    assert_eq!(
+        unknown.drawing_surface_support(),
+        Err(Unsupported::UnsupportedToolkit(Toolkit::Other(
+            "com.example.Toolkit".to_string()
+        )))
+    );
+
+    let failed = Environment {
+        toolkit: None,
+        toolkit_error: Some("java.awt.AWTError: Can't connect to X11 window server".to_string()),
+        ..environment.clone()
+    };
+    assert!(matches!(
+        failed.drawing_surface_support(),
+        Err(Unsupported::ToolkitUnavailable(_))
+    ));
+
+    let minimal = Environment {
+        desktop_module: false,
+        toolkit: None,
+        ..environment
+    };
+    assert_eq!(
+        minimal.drawing_surface_support(),
+        Err(Unsupported::MissingDesktopModule)
+    );
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+#![cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+
+use jawt::unix::{GlxContextAttributes, GlxSurface};
+use jawt::DrawingSurfaceLockResult;
+use jawt_test_harness::{clear_with, run_with_canvas_or_skip, HarnessOptions};
+
+fn clear(surface: &GlxSurface) {
+    clear_with(|name| surface.proc_address(name), [0.0, 0.5, 1.0, 1.0]);
+}
+
+#[test]
+fn context_renders_and_follows_resizes() {
+    let options = HarnessOptions::default();
+    run_with_canvas_or_skip(&options, |mut context| {
+        let mut surface = {
+            let (_, mut guard) = context
+                .drawing_surface
+                .lock()
+                .expect("failed to lock the drawing surface");
+            let info = guard
+                .drawing_surface_info()
+                .expect("failed to get the drawing surface info");
+            let surface = GlxSurface::new(&info, &GlxContextAttributes::default())
+                .expect("failed to create the GLX surface");
+            surface.make_current(&info).expect("failed to make current");
+            clear(&surface);
+            surface.swap_buffers(&info).expect("failed to swap buffers");
+            surface.release_current(&info);
+            surface
+        };
+        assert_eq!(
+            surface.size(),
+            (options.width as u32, options.height as u32)
+        );
+
+        let (width, height) = (options.width / 2, options.height / 2);
+        let changes = context
+            .resize_canvas(width, height)
+            .expect("failed to resize the Canvas");
+        assert!(changes.contains(DrawingSurfaceLockResult::BOUNDS_CHANGED));
+
+        let (_, mut guard) = context
+            .drawing_surface
+            .lock()
+            .expect("failed to lock the drawing surface");
+        let info = guard
+            .drawing_surface_info()
+            .expect("failed to get the drawing surface info");
+        surface
+            .update(&info, changes)
+            .expect("failed to update the GLX surface");
+        assert_eq!(surface.size(), (width as u32, height as u32));
+        surface.make_current(&info).expect("failed to make current");
+        clear(&surface);
+        surface.swap_buffers(&info).expect("failed to swap buffers");
+        surface.destroy(&info);
+    });
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use jawt::{diff_images, ImageDiffError, ImageDiffOptions, ImageMask, Rect, RgbaImage};
+
+fn filled(width: u32, height: u32, pixel: [u8; 4]) -> RgbaImage {
+    let mut image = RgbaImage::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            image.set_pixel(x, y, pixel);
+        }
+    }
+    image
+}
+
+#[test]
+fn differences_within_the_tolerance_match() {
+    let expected = filled(4, 4, [100, 150, 200, 255]);
+    let mut actual = expected.clone();
+    actual.set_pixel(1, 2, [102, 149, 200, 255]);
+    actual.set_pixel(3, 3, [100, 150, 205, 255]);
+
+    let exact = diff_images(&expected, &actual, &ImageDiffOptions::default()).unwrap();
+    assert_eq!(exact.compared_pixels, 16);
+    assert_eq!(exact.mismatched_pixels, 2);
+    assert_eq!(exact.max_difference, 5);
+    assert_eq!(exact.first_mismatch, Some((1, 2)));
+    assert!(exact.mismatches.contains(1, 2));
+    assert!(exact.mismatches.contains(3, 3));
+    assert!(!exact.mismatches.contains(0, 0));
+
+    let tolerant = diff_images(
+        &expected,
+        &actual,
+        &ImageDiffOptions {
+            tolerance: 2,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(tolerant.mismatched_pixels, 1);
+    assert_eq!(tolerant.first_mismatch, Some((3, 3)));
+
+    let loose = diff_images(
+        &expected,
+        &actual,
+        &ImageDiffOptions {
+            tolerance: 5,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(loose.is_match());
+    assert_eq!(loose.max_difference, 5);
+}
+
+#[test]
+fn alpha_is_compared_only_when_requested() {
+    let expected = filled(2, 2, [10, 20, 30, 255]);
+    let actual = filled(2, 2, [10, 20, 30, 0]);
+
+    let with_alpha = diff_images(&expected, &actual, &ImageDiffOptions::default()).unwrap();
+    assert_eq!(with_alpha.mismatched_pixels, 4);
+    assert_eq!(with_alpha.max_difference, 255);
+
+    let without_alpha = diff_images(
+        &expected,
+        &actual,
+        &ImageDiffOptions {
+            compare_alpha: false,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(without_alpha.is_match());
+    assert_eq!(without_alpha.max_difference, 0);
+}
+
+#[test]
+fn only_masked_pixels_are_compared() {
+    let expected = filled(4, 4, [0, 0, 0, 255]);
+    let mut actual = expected.clone();
+    actual.set_pixel(0, 0, [255, 255, 255, 255]);
+    actual.set_pixel(3, 3, [255, 255, 255, 255]);
+
+    let mut mask = ImageMask::from_rects(
+        4,
+        4,
+        &[Rect {
+            x: 2,
+            y: 2,
+            width: 10,
+            height: 10,
+        }],
+    );
+    assert!(mask.contains(3, 3));
+    assert!(!mask.contains(1, 1));
+    assert!(!mask.contains(4, 4));
+
+    let diff = diff_images(
+        &expected,
+        &actual,
+        &ImageDiffOptions {
+            mask: Some(&mask),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(diff.compared_pixels, 4);
+    assert_eq!(diff.mismatched_pixels, 1);
+    assert_eq!(diff.first_mismatch, Some((3, 3)));
+
+    mask.set(3, 3, false);
+    let diff = diff_images(
+        &expected,
+        &actual,
+        &ImageDiffOptions {
+            mask: Some(&mask),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(diff.compared_pixels, 3);
+    assert!(diff.is_match());
+}
+
+#[test]
+fn size_mismatches_are_errors() {
+    let expected = RgbaImage::new(4, 4);
+    let actual = RgbaImage::new(4, 3);
+    assert_eq!(
+        diff_images(&expected, &actual, &ImageDiffOptions::default()),
+        Err(ImageDiffError::SizeMismatch {
+            expected: (4, 4),
+            actual: (4, 3),
+        })
+    );
+
+    let mask = ImageMask::new(2, 2, true);
+    assert_eq!(
+        diff_images(
+            &expected,
+            &expected,
+            &ImageDiffOptions {
+                mask: Some(&mask),
+                ..Default::default()
+            }
+        ),
+        Err(ImageDiffError::SizeMismatch {
+            expected: (4, 4),
+            actual: (2, 2),
+        })
+    );
+}
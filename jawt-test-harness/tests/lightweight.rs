@@ -0,0 +1,116 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use jawt::{BufferedImage, BufferedImageType, LightweightSurface, Rect};
+use jawt_test_harness::{headless_java_vm, image_rgb, or_skip};
+use jni::objects::JValue;
+
+#[test]
+fn only_damage_is_rendered_at_the_graphics_scale() {
+    let Some(java_vm) = or_skip(headless_java_vm()) else {
+        return;
+    };
+    let mut env = java_vm.attach_current_thread().unwrap();
+    let component = env.new_object("javax/swing/JPanel", "()V", &[]).unwrap();
+    env.call_method(
+        &component,
+        "setSize",
+        "(II)V",
+        &[JValue::Int(10), JValue::Int(8)],
+    )
+    .unwrap();
+    let surface = LightweightSurface::new(&env, &component).unwrap();
+
+    // A Graphics scaled by 2, as on a HiDPI display.
+    let target = BufferedImage::new(&mut env, 20, 16, BufferedImageType::IntArgb).unwrap();
+    let graphics = env
+        .call_method(
+            target.as_obj(),
+            "createGraphics",
+            "()Ljava/awt/Graphics2D;",
+            &[],
+        )
+        .unwrap()
+        .l()
+        .unwrap();
+    env.call_method(
+        &graphics,
+        "scale",
+        "(DD)V",
+        &[JValue::Double(2.0), JValue::Double(2.0)],
+    )
+    .unwrap();
+
+    let mut rendered = Vec::new();
+    surface
+        .paint(&mut env, &graphics, |frame| {
+            assert_eq!((frame.width(), frame.height()), (20, 16));
+            assert_eq!(frame.scale(), (2.0, 2.0));
+            rendered.extend_from_slice(frame.damage());
+            frame.pixels().fill(0xff0000ff);
+        })
+        .unwrap();
+    assert_eq!(
+        rendered,
+        [Rect {
+            x: 0,
+            y: 0,
+            width: 20,
+            height: 16,
+        }]
+    );
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 19, 15).unwrap(),
+        0xff0000ff
+    );
+
+    // Nothing is damaged, so the image is drawn without rendering.
+    surface
+        .paint(&mut env, &graphics, |_| panic!("rendered without damage"))
+        .unwrap();
+
+    surface.damage().add(Rect {
+        x: 1,
+        y: 1,
+        width: 2,
+        height: 2,
+    });
+    let mut rendered = Vec::new();
+    surface
+        .paint(&mut env, &graphics, |frame| {
+            let width = frame.width() as usize;
+            for rect in frame.damage() {
+                for y in rect.y..rect.y + rect.height {
+                    let start = y as usize * width + rect.x as usize;
+                    frame.pixels()[start..start + rect.width as usize].fill(0xffff0000);
+                }
+            }
+            rendered.extend_from_slice(frame.damage());
+        })
+        .unwrap();
+    assert_eq!(
+        rendered,
+        [Rect {
+            x: 2,
+            y: 2,
+            width: 4,
+            height: 4,
+        }]
+    );
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 1, 1).unwrap(),
+        0xff0000ff
+    );
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 2, 2).unwrap(),
+        0xffff0000
+    );
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 5, 5).unwrap(),
+        0xffff0000
+    );
+    assert_eq!(
+        image_rgb(&mut env, target.as_obj(), 6, 6).unwrap(),
+        0xff0000ff
+    );
+    env.call_method(&graphics, "dispose", "()V", &[]).unwrap();
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+#![cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+
+use std::ffi::c_void;
+use std::ptr::{self, NonNull};
+
+use jawt::environment::Toolkit;
+use jawt::sys::{JAWT_DrawingSurface, JAWT_X11DrawingSurfaceInfo};
+use jawt::unix::{UnixDrawingSurfaceInfo, UnixToolkit, WaylandDrawingSurfaceInfo};
+use jawt::{AwtVersion, DrawingSurface, PlatformInfoError, Rect, VersionedPlatformInfo};
+use jawt_test_harness::mock::{mock_awt, mock_drawing_surface};
+
+const BOUNDS: Rect = Rect {
+    x: 0,
+    y: 0,
+    width: 64,
+    height: 48,
+};
+
+fn with_platform_info<T>(
+    platform_info: *mut c_void,
+    toolkit: UnixToolkit,
+    f: impl FnOnce(Result<UnixDrawingSurfaceInfo<'_>, PlatformInfoError>) -> T,
+) -> T {
+    let mut drawing_surface: DrawingSurface =
+        unsafe { mock_drawing_surface(platform_info, BOUNDS) };
+    let (_, mut guard) = drawing_surface.lock().unwrap();
+    let info = guard.drawing_surface_info().unwrap();
+    assert_eq!(info.bounds(), BOUNDS);
+    // Safety: the mock hands out the layout of `toolkit`.
+    f(unsafe { info.unix_platform_info_with(toolkit) })
+}
+
+#[test]
+fn x11_layout_is_typed() {
+    let mut x11 = JAWT_X11DrawingSurfaceInfo {
+        drawable: 42,
+        display: NonNull::dangling().as_ptr(),
+        visualID: 33,
+        colormapID: 0,
+        depth: 24,
+        GetAWTColor: None,
+    };
+    with_platform_info(ptr::addr_of_mut!(x11).cast(), UnixToolkit::X11, |info| {
+        let info = info.expect("the X11 layout was refused");
+        assert_eq!(info.toolkit(), UnixToolkit::X11);
+        assert!(info.wayland().is_none());
+        let x11 = info.x11().unwrap();
+        assert_eq!(x11.drawable(), 42);
+        assert_eq!(x11.visual_id(), 33);
+        assert_eq!(x11.depth(), 24);
+    });
+
+    x11.drawable = 0;
+    with_platform_info(ptr::addr_of_mut!(x11).cast(), UnixToolkit::X11, |info| {
+        assert_eq!(info.unwrap_err(), PlatformInfoError::InvalidLayout)
+    });
+}
+
+#[test]
+fn wayland_layout_is_typed() {
+    let display = NonNull::<c_void>::dangling().as_ptr();
+    let surface = display.wrapping_add(1);
+    let mut wayland = WaylandDrawingSurfaceInfo::new(display, surface);
+    with_platform_info(
+        ptr::addr_of_mut!(wayland).cast(),
+        UnixToolkit::Wayland,
+        |info| {
+            let info = info.expect("the Wayland layout was refused");
+            assert_eq!(info.toolkit(), UnixToolkit::Wayland);
+            assert!(info.x11().is_none());
+            let wayland = info.wayland().unwrap();
+            assert_eq!(wayland.display(), display);
+            assert_eq!(wayland.surface(), surface);
+        },
+    );
+
+    let mut wayland = WaylandDrawingSurfaceInfo::new(display, ptr::null_mut());
+    with_platform_info(
+        ptr::addr_of_mut!(wayland).cast(),
+        UnixToolkit::Wayland,
+        |info| assert_eq!(info.unwrap_err(), PlatformInfoError::InvalidLayout),
+    );
+}
+
+#[test]
+fn unknown_layouts_are_refused() {
+    with_platform_info(ptr::null_mut(), UnixToolkit::X11, |info| {
+        assert_eq!(info.unwrap_err(), PlatformInfoError::Null)
+    });
+
+    // No JVM is started by this test, so no toolkit is loaded.
+    assert_eq!(UnixToolkit::detect(), None);
+    let mut drawing_surface = unsafe { mock_drawing_surface(ptr::null_mut(), BOUNDS) };
+    let (_, mut guard) = drawing_surface.lock().unwrap();
+    let info = guard.drawing_surface_info().unwrap();
+    assert_eq!(
+        info.unix_platform_info().unwrap_err(),
+        PlatformInfoError::UnknownToolkit
+    );
+
+    assert_eq!(
+        UnixToolkit::from_toolkit(&Toolkit::Wayland),
+        Some(UnixToolkit::Wayland)
+    );
+    assert_eq!(UnixToolkit::from_toolkit(&Toolkit::Headless), None);
+}
+
+unsafe extern "C" fn get_awt_color(_ds: *mut JAWT_DrawingSurface, r: i32, g: i32, b: i32) -> i32 {
+    (r << 16) | (g << 8) | b
+}
+
+#[test]
+fn version_gated_fields_follow_the_awt_version() {
+    let mut x11 = JAWT_X11DrawingSurfaceInfo {
+        drawable: 42,
+        display: NonNull::dangling().as_ptr(),
+        visualID: 0,
+        colormapID: 0,
+        depth: 24,
+        GetAWTColor: Some(get_awt_color),
+    };
+    let mut drawing_surface =
+        unsafe { mock_drawing_surface(ptr::addr_of_mut!(x11).cast(), BOUNDS) };
+    let (_, mut guard) = drawing_surface.lock().unwrap();
+    let info = guard.drawing_surface_info().unwrap();
+
+    // No JVM is started by this test, so the toolkit is unknown and the checked accessors refuse
+    // the information.
+    assert_eq!(
+        info.try_platform_info().unwrap_err(),
+        PlatformInfoError::UnknownToolkit
+    );
+    assert_eq!(
+        info.try_platform_info_for(&mock_awt(AwtVersion::VERSION_9))
+            .unwrap_err(),
+        PlatformInfoError::UnknownToolkit
+    );
+
+    // Safety: the mock hands out the X11 layout.
+    let platform_info = unsafe { info.unix_platform_info_with(UnixToolkit::X11) }
+        .unwrap()
+        .x11()
+        .unwrap();
+    assert_eq!(
+        platform_info.try_get_awt_color(&info, 0x10, 0x20, 0x30),
+        Some(0x102030)
+    );
+    #[allow(deprecated)]
+    let pixel = platform_info.get_awt_color(&info, 0x10, 0x20, 0x30);
+    assert_eq!(pixel, 0x102030);
+    let versioned = VersionedPlatformInfo::new(platform_info, AwtVersion::VERSION_1_3);
+    assert_eq!(versioned.get_awt_color(&info, 0x10, 0x20, 0x30), None);
+    let versioned = VersionedPlatformInfo::new(platform_info, AwtVersion::VERSION_1_4);
+    assert_eq!(
+        versioned.get_awt_color(&info, 0x10, 0x20, 0x30),
+        Some(0x102030)
+    );
+    assert_eq!(versioned.drawable(), 42);
+}
+
+#[test]
+fn missing_get_awt_color_is_none() {
+    let mut x11 = JAWT_X11DrawingSurfaceInfo {
+        drawable: 42,
+        display: NonNull::dangling().as_ptr(),
+        visualID: 0,
+        colormapID: 0,
+        depth: 24,
+        GetAWTColor: None,
+    };
+    with_platform_info(ptr::addr_of_mut!(x11).cast(), UnixToolkit::X11, |info| {
+        let versioned =
+            VersionedPlatformInfo::new(info.unwrap().x11().unwrap(), AwtVersion::VERSION_9);
+        assert_eq!(versioned.version(), AwtVersion::VERSION_9);
+        assert_eq!(versioned.drawable(), 42);
+        assert_eq!(versioned.depth(), 24);
+    });
+
+    let mut drawing_surface =
+        unsafe { mock_drawing_surface(ptr::addr_of_mut!(x11).cast(), BOUNDS) };
+    let (_, mut guard) = drawing_surface.lock().unwrap();
+    let info = guard.drawing_surface_info().unwrap();
+    let platform_info = unsafe { info.unix_platform_info_with(UnixToolkit::X11) }
+        .unwrap()
+        .x11()
+        .unwrap();
+    assert_eq!(platform_info.try_get_awt_color(&info, 1, 2, 3), None);
+    assert_eq!(
+        VersionedPlatformInfo::new(platform_info, AwtVersion::VERSION_9)
+            .get_awt_color(&info, 1, 2, 3),
+        None
+    );
+}
+
+#[test]
+#[should_panic(expected = "GetAWTColor is not available")]
+fn deprecated_get_awt_color_panics_when_missing() {
+    let mut x11 = JAWT_X11DrawingSurfaceInfo {
+        drawable: 42,
+        display: NonNull::dangling().as_ptr(),
+        visualID: 0,
+        colormapID: 0,
+        depth: 24,
+        GetAWTColor: None,
+    };
+    let mut drawing_surface =
+        unsafe { mock_drawing_surface(ptr::addr_of_mut!(x11).cast(), BOUNDS) };
+    let (_, mut guard) = drawing_surface.lock().unwrap();
+    let info = guard.drawing_surface_info().unwrap();
+    let platform_info = unsafe { info.unix_platform_info_with(UnixToolkit::X11) }
+        .unwrap()
+        .x11()
+        .unwrap();
+    #[allow(deprecated)]
+    platform_info.get_awt_color(&info, 1, 2, 3);
+}
+
+#[test]
+fn version_flags_are_ignored_when_comparing() {
+    // `JAWT_MACOSX_USE_CALAYER` is stored in the sign bit.
+    let with_flag = unsafe { AwtVersion::from_raw(AwtVersion::VERSION_1_4.inner() | i32::MIN) };
+    assert!(with_flag.is_at_least(AwtVersion::VERSION_1_4));
+    assert!(!with_flag.is_at_least(AwtVersion::VERSION_1_7));
+    assert!(AwtVersion::VERSION_9.is_at_least(AwtVersion::VERSION_1_7));
+}
@@ -0,0 +1,47 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use std::mem;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use jawt::sys::{JAWT_DrawingSurface, JAWT_DrawingSurfaceInfo};
+use jawt::{DrawingSurface, DrawingSurfaceInfo};
+
+static FREED_DRAWING_SURFACES: AtomicUsize = AtomicUsize::new(0);
+static FREED_DRAWING_SURFACE_INFOS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn free_drawing_surface(_ds: *mut JAWT_DrawingSurface) {
+    FREED_DRAWING_SURFACES.fetch_add(1, Ordering::SeqCst);
+}
+
+unsafe extern "C" fn free_drawing_surface_info(_dsi: *mut JAWT_DrawingSurfaceInfo) {
+    FREED_DRAWING_SURFACE_INFOS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn drawing_surface_is_freed_on_drop_but_not_by_into_raw_parts() {
+    let mut raw: JAWT_DrawingSurface = unsafe { mem::zeroed() };
+    let inner = NonNull::from(&mut raw);
+
+    drop(unsafe { DrawingSurface::from_raw_parts(inner, free_drawing_surface) });
+    assert_eq!(FREED_DRAWING_SURFACES.load(Ordering::SeqCst), 1);
+
+    let surface = unsafe { DrawingSurface::from_raw_parts(inner, free_drawing_surface) };
+    let (parts, _) = surface.into_raw_parts();
+    assert_eq!(parts, inner);
+    assert_eq!(FREED_DRAWING_SURFACES.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn drawing_surface_info_is_freed_on_drop_but_not_by_into_raw_parts() {
+    let mut raw: JAWT_DrawingSurfaceInfo = unsafe { mem::zeroed() };
+    let inner = NonNull::from(&mut raw);
+
+    drop(unsafe { DrawingSurfaceInfo::from_raw_parts(inner, free_drawing_surface_info) });
+    assert_eq!(FREED_DRAWING_SURFACE_INFOS.load(Ordering::SeqCst), 1);
+
+    let info = unsafe { DrawingSurfaceInfo::from_raw_parts(inner, free_drawing_surface_info) };
+    let (parts, _) = info.into_raw_parts();
+    assert_eq!(parts, inner);
+    assert_eq!(FREED_DRAWING_SURFACE_INFOS.load(Ordering::SeqCst), 1);
+}
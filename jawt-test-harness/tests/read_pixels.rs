@@ -0,0 +1,83 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+#![cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+
+use std::ptr;
+
+use jawt::unix::trap_errors;
+use jawt::{diff_images, ImageDiffOptions, ImageMask, RgbaImage};
+use jawt_test_harness::{run_with_canvas_or_skip, HarnessOptions};
+
+#[test]
+fn filled_canvas_is_read_back() {
+    let options = HarnessOptions::default();
+    run_with_canvas_or_skip(&options, |context| {
+        let (_, mut guard) = context
+            .drawing_surface
+            .lock()
+            .expect("failed to lock the drawing surface");
+        let info = guard
+            .drawing_surface_info()
+            .expect("failed to get the drawing surface info");
+        let platform_info = info
+            .try_platform_info()
+            .expect("failed to get the platform info");
+        let pixel = info
+            .try_platform_info_for(context.awt)
+            .expect("failed to get the platform info")
+            .get_awt_color(&info, 255, 128, 0)
+            .expect("GetAWTColor is not available");
+
+        let display = platform_info.display();
+        let drawable = platform_info.drawable();
+        let bounds = info.bounds();
+        unsafe {
+            trap_errors(display, |xlib| {
+                let gc = (xlib.XCreateGC)(display, drawable, 0, ptr::null_mut());
+                (xlib.XSetForeground)(display, gc, pixel as u32 as _);
+                (xlib.XFillRectangle)(
+                    display,
+                    drawable,
+                    gc,
+                    0,
+                    0,
+                    bounds.width as _,
+                    bounds.height as _,
+                );
+                (xlib.XFreeGC)(display, gc);
+            })
+        }
+        .expect("failed to fill the Canvas");
+
+        let actual = info.read_pixels().expect("failed to read the pixels back");
+        let mut expected = RgbaImage::new(actual.width(), actual.height());
+        for y in 0..expected.height() {
+            for x in 0..expected.width() {
+                expected.set_pixel(x, y, [255, 128, 0, 255]);
+            }
+        }
+        let mask = ImageMask::from_clip(&info);
+        let diff = diff_images(
+            &expected,
+            &actual,
+            &ImageDiffOptions {
+                // Visuals with fewer than 8 bits per channel round the color.
+                tolerance: 8,
+                mask: Some(&mask),
+                ..Default::default()
+            },
+        )
+        .expect("the read-back image has the wrong size");
+        assert!(diff.compared_pixels > 0);
+        assert!(
+            diff.is_match(),
+            "{} pixels differ, the first at {:?}",
+            diff.mismatched_pixels,
+            diff.first_mismatch
+        );
+    });
+}
@@ -0,0 +1,76 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use jawt::render_loop::{Frame, Pacing, RenderLoop, RenderLoopOptions, Renderer};
+use jawt::{DrawingSurfaceInfo, DrawingSurfaceLockResult, Rect};
+use jawt_test_harness::{run_with_canvas_or_skip, HarnessOptions};
+
+#[derive(Default)]
+struct Counters {
+    frames: AtomicUsize,
+    dropped: AtomicBool,
+}
+
+struct CountingRenderer(Arc<Counters>);
+
+impl Renderer for CountingRenderer {
+    type Snapshot = Rect;
+
+    fn snapshot(&mut self, _: DrawingSurfaceLockResult, info: &DrawingSurfaceInfo<'_>) -> Rect {
+        info.bounds()
+    }
+
+    fn render(&mut self, bounds: Rect) -> Frame {
+        assert!(bounds.width > 0 && bounds.height > 0);
+        self.0.frames.fetch_add(1, Ordering::SeqCst);
+        Frame::Presented
+    }
+}
+
+impl Drop for CountingRenderer {
+    fn drop(&mut self) {
+        self.0.dropped.store(true, Ordering::SeqCst);
+    }
+}
+
+fn wait_for_frames(counters: &Counters, frames: usize) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while counters.frames.load(Ordering::SeqCst) < frames {
+        assert!(Instant::now() < deadline, "the render loop stalled");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn render_loop_pauses_resumes_and_stops() {
+    let counters = Arc::new(Counters::default());
+    run_with_canvas_or_skip(&HarnessOptions::default(), |context| {
+        let options = RenderLoopOptions {
+            pacing: Pacing::rate(120),
+            ..RenderLoopOptions::default()
+        };
+        let renderer = CountingRenderer(counters.clone());
+        let render_loop =
+            RenderLoop::spawn(context.env, context.awt, context.canvas, options, |_, _| {
+                Some(renderer)
+            })
+            .expect("failed to spawn the render loop");
+        wait_for_frames(&counters, 3);
+
+        render_loop.pause();
+        thread::sleep(Duration::from_millis(100));
+        let paused_frames = counters.frames.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(counters.frames.load(Ordering::SeqCst), paused_frames);
+
+        render_loop.resume();
+        wait_for_frames(&counters, paused_frames + 3);
+
+        render_loop.stop().expect("the renderer panicked");
+        assert!(counters.dropped.load(Ordering::SeqCst));
+    });
+}
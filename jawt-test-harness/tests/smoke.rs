@@ -0,0 +1,23 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use jawt_test_harness::{run_with_canvas_or_skip, HarnessOptions};
+
+#[test]
+fn canvas_can_be_locked() {
+    let options = HarnessOptions::default();
+    let result = run_with_canvas_or_skip(&options, |context| {
+        let (_, mut guard) = context
+            .drawing_surface
+            .lock()
+            .expect("failed to lock the drawing surface");
+        let info = guard
+            .drawing_surface_info()
+            .expect("failed to get the drawing surface info");
+        info.bounds()
+    });
+    let Some(bounds) = result else {
+        return;
+    };
+    assert_eq!(bounds.width, options.width);
+    assert_eq!(bounds.height, options.height);
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use std::ptr;
+use std::sync::{Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+
+use jawt::sys::{JAWT_DrawingSurface, JAWT, JAWT_LOCK_ERROR};
+use jawt::validation::{self, ViolationKind};
+use jawt::AwtVersion;
+use jawt_test_harness::mock::mock_jawt;
+
+static VIOLATIONS: Mutex<Vec<(ThreadId, &str, ViolationKind)>> = Mutex::new(Vec::new());
+
+/// Wraps a mock JAWT table, collecting violations instead of printing them. Tests run in parallel,
+/// so each one only looks at the violations reported on its own threads.
+fn validated_jawt() -> JAWT {
+    validation::set_violation_handler(|violation| {
+        VIOLATIONS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((violation.thread, violation.function, violation.kind.clone()));
+    });
+    unsafe { validation::wrap(mock_jawt(AwtVersion::VERSION_9)) }
+}
+
+/// Removes and returns the violations reported on `thread`.
+fn take_violations(thread: ThreadId) -> Vec<(&'static str, ViolationKind)> {
+    let mut violations = VIOLATIONS.lock().unwrap_or_else(PoisonError::into_inner);
+    let (taken, rest) = violations
+        .drain(..)
+        .partition::<Vec<_>, _>(|(owner, _, _)| *owner == thread);
+    *violations = rest;
+    taken
+        .into_iter()
+        .map(|(_, function, kind)| (function, kind))
+        .collect()
+}
+
+fn take_own_violations() -> Vec<(&'static str, ViolationKind)> {
+    take_violations(thread::current().id())
+}
+
+fn get_drawing_surface(jawt: &JAWT) -> *mut JAWT_DrawingSurface {
+    let ds = unsafe { jawt.GetDrawingSurface.unwrap()(ptr::null_mut(), ptr::null_mut()) };
+    assert!(!ds.is_null());
+    ds
+}
+
+unsafe fn lock(ds: *mut JAWT_DrawingSurface) -> i32 {
+    unsafe { (*ds).Lock.unwrap()(ds) }
+}
+
+unsafe fn unlock(ds: *mut JAWT_DrawingSurface) {
+    unsafe { (*ds).Unlock.unwrap()(ds) }
+}
+
+#[test]
+fn balanced_use_reports_nothing() {
+    let jawt = validated_jawt();
+    let ds = get_drawing_surface(&jawt);
+    unsafe {
+        assert_eq!(lock(ds), 0);
+        let dsi = (*ds).GetDrawingSurfaceInfo.unwrap()(ds);
+        assert!(!dsi.is_null());
+        assert_eq!((*dsi).ds, ds);
+        (*ds).FreeDrawingSurfaceInfo.unwrap()(dsi);
+        unlock(ds);
+        jawt.FreeDrawingSurface.unwrap()(ds);
+        jawt.Lock.unwrap()(ptr::null_mut());
+        jawt.Unlock.unwrap()(ptr::null_mut());
+    }
+    assert_eq!(take_own_violations(), []);
+}
+
+#[test]
+fn unbalanced_unlock_is_reported() {
+    let jawt = validated_jawt();
+    let ds = get_drawing_surface(&jawt);
+    unsafe {
+        lock(ds);
+        unlock(ds);
+        unlock(ds);
+        jawt.FreeDrawingSurface.unwrap()(ds);
+        jawt.Unlock.unwrap()(ptr::null_mut());
+    }
+    assert_eq!(
+        take_own_violations(),
+        [
+            (
+                "JAWT_DrawingSurface.Unlock",
+                ViolationKind::UnbalancedUnlock
+            ),
+            ("JAWT.Unlock", ViolationKind::UnbalancedAwtUnlock),
+        ]
+    );
+}
+
+#[test]
+fn foreign_thread_calls_are_reported() {
+    let jawt = validated_jawt();
+    let ds = get_drawing_surface(&jawt);
+    let owner = thread::current().id();
+
+    let address = ds as usize;
+    let foreign = thread::spawn(move || {
+        let ds = address as *mut JAWT_DrawingSurface;
+        unsafe {
+            lock(ds);
+            unlock(ds);
+        }
+        thread::current().id()
+    })
+    .join()
+    .unwrap();
+    unsafe { jawt.FreeDrawingSurface.unwrap()(ds) };
+
+    assert_eq!(
+        take_violations(foreign),
+        [
+            (
+                "JAWT_DrawingSurface.Lock",
+                ViolationKind::ForeignThread { owner }
+            ),
+            (
+                "JAWT_DrawingSurface.Unlock",
+                ViolationKind::ForeignThread { owner }
+            ),
+        ]
+    );
+    assert_eq!(take_own_violations(), []);
+}
+
+#[test]
+fn drawing_surface_info_without_lock_is_refused() {
+    let jawt = validated_jawt();
+    let ds = get_drawing_surface(&jawt);
+    unsafe {
+        let dsi = (*ds).GetDrawingSurfaceInfo.unwrap()(ds);
+        assert!(dsi.is_null());
+        jawt.FreeDrawingSurface.unwrap()(ds);
+    }
+    assert_eq!(
+        take_own_violations(),
+        [(
+            "JAWT_DrawingSurface.GetDrawingSurfaceInfo",
+            ViolationKind::NotLocked
+        )]
+    );
+}
+
+#[test]
+fn unlock_and_free_with_outstanding_info_are_reported() {
+    let jawt = validated_jawt();
+    let ds = get_drawing_surface(&jawt);
+    unsafe {
+        lock(ds);
+        let dsi = (*ds).GetDrawingSurfaceInfo.unwrap()(ds);
+        assert!(!dsi.is_null());
+        unlock(ds);
+        lock(ds);
+        jawt.FreeDrawingSurface.unwrap()(ds);
+        // Freeing the information of a freed drawing surface is still reported.
+        (*ds).FreeDrawingSurfaceInfo.unwrap()(dsi);
+        (*ds).FreeDrawingSurfaceInfo.unwrap()(dsi);
+    }
+    assert_eq!(
+        take_own_violations(),
+        [
+            (
+                "JAWT_DrawingSurface.Unlock",
+                ViolationKind::UnlockedWithInfo { outstanding: 1 }
+            ),
+            (
+                "JAWT.FreeDrawingSurface",
+                ViolationKind::FreedWhileLocked { lock_depth: 1 }
+            ),
+            (
+                "JAWT.FreeDrawingSurface",
+                ViolationKind::FreedWithInfo { outstanding: 1 }
+            ),
+            (
+                "JAWT_DrawingSurface.FreeDrawingSurfaceInfo",
+                ViolationKind::StaleDrawingSurface
+            ),
+            (
+                "JAWT_DrawingSurface.FreeDrawingSurfaceInfo",
+                ViolationKind::UnknownDrawingSurfaceInfo
+            ),
+        ]
+    );
+}
+
+#[test]
+fn stale_and_unknown_drawing_surfaces_are_refused() {
+    let jawt = validated_jawt();
+    let ds = get_drawing_surface(&jawt);
+    let mut unknown = unsafe { ptr::read(ds) };
+    unsafe {
+        jawt.FreeDrawingSurface.unwrap()(ds);
+        assert_eq!(lock(ds), JAWT_LOCK_ERROR);
+        assert!((*ds).GetDrawingSurfaceInfo.unwrap()(ds).is_null());
+        unlock(ds);
+        jawt.FreeDrawingSurface.unwrap()(ds);
+        assert_eq!(lock(&mut unknown), JAWT_LOCK_ERROR);
+    }
+    assert_eq!(
+        take_own_violations(),
+        [
+            (
+                "JAWT_DrawingSurface.Lock",
+                ViolationKind::StaleDrawingSurface
+            ),
+            (
+                "JAWT_DrawingSurface.GetDrawingSurfaceInfo",
+                ViolationKind::StaleDrawingSurface
+            ),
+            (
+                "JAWT_DrawingSurface.Unlock",
+                ViolationKind::StaleDrawingSurface
+            ),
+            (
+                "JAWT.FreeDrawingSurface",
+                ViolationKind::StaleDrawingSurface
+            ),
+            (
+                "JAWT_DrawingSurface.Lock",
+                ViolationKind::UnknownDrawingSurface
+            ),
+        ]
+    );
+}
@@ -0,0 +1,118 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+#![cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+
+use std::ffi::CStr;
+
+use ash::vk;
+use jawt::unix::{VulkanSurface, VulkanSurfaceExtension, VulkanSurfaceUpdate};
+use jawt::DrawingSurfaceLockResult;
+use jawt_test_harness::{run_with_canvas_or_skip, skip_unless_ci, CanvasContext, HarnessOptions};
+
+#[test]
+fn surface_presents_and_is_recreated_when_the_canvas_surface_changes() {
+    let options = HarnessOptions::default();
+    run_with_canvas_or_skip(&options, |context| {
+        let Ok(entry) = (unsafe { ash::Entry::load() }) else {
+            skip_unless_ci("no Vulkan loader is installed");
+            return;
+        };
+        let Some(extension) =
+            VulkanSurfaceExtension::choose(&entry).expect("failed to query instance extensions")
+        else {
+            skip_unless_ci("the Vulkan loader supports no X11 surface extension");
+            return;
+        };
+        let extension_names = extension.required_instance_extensions().map(CStr::as_ptr);
+        let application_info = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_0);
+        let create_info = vk::InstanceCreateInfo::default()
+            .application_info(&application_info)
+            .enabled_extension_names(&extension_names);
+        let instance = unsafe { entry.create_instance(&create_info, None) }
+            .expect("failed to create a Vulkan instance");
+        present_and_recreate(context, &options, &entry, &instance, extension);
+        unsafe { instance.destroy_instance(None) };
+    });
+}
+
+fn present_and_recreate(
+    mut context: CanvasContext,
+    options: &HarnessOptions,
+    entry: &ash::Entry,
+    instance: &ash::Instance,
+    extension: VulkanSurfaceExtension,
+) {
+    let (mut surface, physical_device, queue_family_index) = {
+        let (_, mut guard) = context
+            .drawing_surface
+            .lock()
+            .expect("failed to lock the drawing surface");
+        let info = guard
+            .drawing_surface_info()
+            .expect("failed to get the drawing surface info");
+        let surface = unsafe { VulkanSurface::new(entry, instance, &info, extension) }
+            .expect("failed to create the Vulkan surface");
+
+        let physical_devices = unsafe { instance.enumerate_physical_devices() }
+            .expect("failed to enumerate physical devices");
+        let presentable = physical_devices.iter().find_map(|&physical_device| {
+            let queue_families =
+                unsafe { instance.get_physical_device_queue_family_properties(physical_device) };
+            (0..queue_families.len() as u32)
+                .find(|&index| {
+                    surface
+                        .supports_present(physical_device, index)
+                        .expect("failed to query present support")
+                })
+                .map(|index| (physical_device, index))
+        });
+        let Some((physical_device, queue_family_index)) = presentable else {
+            drop(surface);
+            skip_unless_ci("no Vulkan device can present to the Canvas");
+            return;
+        };
+
+        let support = surface
+            .support(physical_device)
+            .expect("failed to query the surface support");
+        assert!(!support.formats.is_empty());
+        assert!(support.present_modes.contains(&vk::PresentModeKHR::FIFO));
+        assert_eq!(
+            surface.swapchain_extent(&support.capabilities),
+            vk::Extent2D {
+                width: options.width as u32,
+                height: options.height as u32,
+            }
+        );
+        (surface, physical_device, queue_family_index)
+    };
+    let old_window = surface.window();
+
+    let changes = context
+        .recreate_canvas_peer()
+        .expect("failed to re-create the Canvas peer");
+    assert!(changes.contains(DrawingSurfaceLockResult::SURFACE_CHANGED));
+
+    let (_, mut guard) = context
+        .drawing_surface
+        .lock()
+        .expect("failed to lock the drawing surface");
+    let info = guard
+        .drawing_surface_info()
+        .expect("failed to get the drawing surface info");
+    assert_eq!(
+        surface.update(&info, changes),
+        VulkanSurfaceUpdate::RecreateSurface
+    );
+    unsafe { surface.recreate(&info) }.expect("failed to re-create the Vulkan surface");
+    assert_ne!(surface.window(), old_window);
+    assert!(surface
+        .supports_present(physical_device, queue_family_index)
+        .expect("failed to query present support"));
+    // The surface is destroyed while the drawing surface is locked.
+    drop(surface);
+}
@@ -5,64 +5,48 @@ package dev.gobley.jawt.tests
 import java.awt.Canvas
 import java.awt.Component
 import java.awt.Graphics
+import java.awt.event.ComponentAdapter
 import java.awt.event.ComponentEvent
-import java.awt.event.ComponentListener
 import java.io.File
 import java.nio.file.Files
 import java.nio.file.StandardCopyOption
 import java.util.concurrent.atomic.AtomicLong
 
 class RustCanvas : Canvas(), AutoCloseable {
-    private var renderContext: AtomicLong = AtomicLong(0L)
+    private var renderLoop: AtomicLong = AtomicLong(0L)
 
     init {
-        addComponentListener(object : ComponentListener {
-            override fun componentResized(e: ComponentEvent) {
-                handleResize()
-                render()
-            }
-
-            override fun componentMoved(e: ComponentEvent) {
-                handleResize()
-                render()
-            }
-
+        addComponentListener(object : ComponentAdapter() {
             override fun componentShown(e: ComponentEvent) {
-                destroy(renderContext.getAndSet(create(this@RustCanvas)))
-                handleResize()
-                render()
+                resume(renderLoop.get())
             }
 
             override fun componentHidden(e: ComponentEvent) {
-                destroy(renderContext.getAndSet(0))
+                pause(renderLoop.get())
             }
         })
     }
 
     override fun addNotify() {
         super.addNotify()
-        destroy(renderContext.getAndSet(create(this)))
+        destroy(renderLoop.getAndSet(create(this)))
     }
 
     override fun removeNotify() {
-        destroy(renderContext.getAndSet(0))
+        destroy(renderLoop.getAndSet(0))
         super.removeNotify()
     }
 
-    private fun handleResize() {
-        resize(renderContext.get(), width, height)
-    }
-
-    private fun render() {
-        render(renderContext.get())
+    override fun update(g: Graphics) {
+        // Frames are rendered by the render loop.
     }
 
     override fun paint(g: Graphics) {
-        render()
+        // Frames are rendered by the render loop.
     }
 
     override fun close() {
-        destroy(renderContext.getAndSet(0))
+        destroy(renderLoop.getAndSet(0))
     }
 
     companion object {
@@ -75,13 +59,13 @@ class RustCanvas : Canvas(), AutoCloseable {
         private external fun create(component: Component): Long
 
         @JvmStatic
-        private external fun render(renderContext: Long)
+        private external fun pause(renderLoop: Long)
 
         @JvmStatic
-        private external fun resize(renderContext: Long, width: Int, height: Int)
+        private external fun resume(renderLoop: Long)
 
         @JvmStatic
-        private external fun destroy(renderContext: Long)
+        private external fun destroy(renderLoop: Long)
 
         private fun copyLibraryIfNeeded(): File {
             return File.createTempFile("jawt_tests", getLibrarySuffix()).apply {
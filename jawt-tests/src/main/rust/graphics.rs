@@ -1,34 +1,16 @@
 // Copyright (c) 2025 Gobley Contributors.
 
-#[cfg(target_os = "windows")]
-pub mod windows;
-
-#[cfg(target_os = "macos")]
-pub mod macos;
-
-#[cfg(all(
-    target_family = "unix",
-    not(target_vendor = "apple"),
-    not(target_os = "android")
-))]
-pub mod unix;
-
 use std::mem;
-use std::sync::Mutex;
 
 use futures::executor::block_on;
+use jawt::render_loop::{Frame, Renderer};
+use jawt::{DrawingSurfaceInfo, DrawingSurfaceLockResult};
+use jawt_wgpu::{AwtSurface, PlatformRenderTarget, RenderTarget};
 use wgpu::util::*;
 use wgpu::*;
 
-pub trait RenderTarget: Send + Sync {
-    fn size(&self) -> (u32, u32);
-    unsafe fn create_surface(&self, instance: &Instance) -> Surface<'static>;
-}
-
 pub struct RenderContext {
-    _target: Box<dyn RenderTarget>,
-    surface: Surface<'static>,
-    surface_config: Mutex<SurfaceConfiguration>,
+    surface: AwtSurface<PlatformRenderTarget>,
     device: Device,
     queue: Queue,
     vertex_buffer: Buffer,
@@ -36,30 +18,29 @@ pub struct RenderContext {
 }
 
 impl RenderContext {
-    pub fn new(target: impl RenderTarget + 'static) -> RenderContext {
+    pub fn new(target: PlatformRenderTarget) -> RenderContext {
         log::debug!("RenderContext::new()");
 
         let instance = Instance::new(&InstanceDescriptor::default());
-        // Safety: target will be retained by `RenderContext`
-        let surface = unsafe { target.create_surface(&instance) };
+        // Safety: the Canvas stops the render loop in `removeNotify()`
+        let mut surface =
+            unsafe { AwtSurface::new(&instance, target) }.expect("could not create WGPU surface");
         let adapter = block_on(instance.request_adapter(&RequestAdapterOptions {
             power_preference: PowerPreference::LowPower,
-            compatible_surface: Some(&surface),
+            compatible_surface: Some(surface.surface()),
             force_fallback_adapter: false,
         }))
         .expect("could not retrieve a adapter");
 
-        let (width, height) = target.size();
-
-        log::debug!("RenderContext::new(): width = {width}, height = {height}");
-        let surface_config = surface
-            .get_default_config(&adapter, width, height)
-            .expect("could not retrieve the surface configuration");
-
         let (device, queue) = block_on(adapter.request_device(&DeviceDescriptor::default(), None))
             .expect("could not retrieve a device");
 
-        surface.configure(&device, &surface_config);
+        surface
+            .configure(&adapter, &device)
+            .expect("could not retrieve the surface configuration");
+        let surface_format = surface.config().unwrap().format;
+        log::debug!("RenderContext::new(): config = {:?}", surface.config());
+
         let vertex_buffer = device.create_buffer_init(&BufferInitDescriptor {
             label: None,
             contents: bytemuck::cast_slice(VERTICES),
@@ -111,7 +92,7 @@ impl RenderContext {
                 entry_point: Some("fragment"),
                 compilation_options: Default::default(),
                 targets: &[Some(ColorTargetState {
-                    format: surface_config.format,
+                    format: surface_format,
                     blend: Some(BlendState::ALPHA_BLENDING),
                     write_mask: ColorWrites::all(),
                 })],
@@ -120,37 +101,63 @@ impl RenderContext {
             cache: None,
         });
         RenderContext {
-            _target: Box::new(target),
             surface,
-            surface_config: Mutex::new(surface_config),
             device,
             queue,
             vertex_buffer,
             pipeline,
         }
     }
+}
 
-    pub fn change_size(&self, width: u32, height: u32) {
-        log::debug!("RenderContext::change_size({width}, {height})");
+pub struct Snapshot {
+    surface_changed: bool,
+    size: (u32, u32),
+}
 
-        let mut surface_config = self.surface_config.lock().unwrap();
-        if surface_config.width == width || surface_config.height == height {
-            return;
+impl Renderer for RenderContext {
+    type Snapshot = Snapshot;
+
+    fn snapshot(
+        &mut self,
+        lock_result: DrawingSurfaceLockResult,
+        info: &DrawingSurfaceInfo<'_>,
+    ) -> Snapshot {
+        let mut surface_changed = false;
+        if lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED) {
+            match PlatformRenderTarget::from_drawing_surface_info(info) {
+                Ok(target) => {
+                    *self.surface.target_mut() = target;
+                    surface_changed = true;
+                }
+                Err(e) => log::error!("Render target error: {e}"),
+            }
+        } else if lock_result.contains(DrawingSurfaceLockResult::BOUNDS_CHANGED) {
+            self.surface.target_mut().update(info);
+        }
+        Snapshot {
+            surface_changed,
+            size: self.surface.target().size(),
         }
-
-        surface_config.width = width;
-        surface_config.height = height;
-        self.surface.configure(&self.device, &surface_config);
     }
 
-    pub fn render(&self) {
-        log::debug!("RenderContext::render()");
+    fn render(&mut self, snapshot: Snapshot) -> Frame {
+        if snapshot.surface_changed {
+            log::debug!("RenderContext::render(): surface changed");
+            if let Err(e) = self.surface.recreate(&self.device) {
+                log::error!("Surface error: {e}");
+                return Frame::Skipped;
+            }
+        }
+        let (width, height) = snapshot.size;
+        self.surface.resize(&self.device, width, height);
 
-        let frame = match self.surface.get_current_texture() {
-            Ok(frame) => frame,
+        let frame = match self.surface.acquire(&self.device) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => return Frame::Skipped,
             Err(e) => {
-                log::error!("Swap-chain error: {e:?}");
-                return;
+                log::error!("Swap-chain error: {e}");
+                return Frame::Skipped;
             }
         };
         let frame_view = frame.texture.create_view(&TextureViewDescriptor::default());
@@ -178,10 +185,9 @@ impl RenderContext {
             pass.draw(0..VERTICES.len() as u32, 0..1);
         }
 
-        log::debug!("RenderContext::render(): submit");
-
         self.queue.submit(Some(encoder.finish()));
         frame.present();
+        Frame::Presented
     }
 }
 
@@ -6,7 +6,9 @@ use std::ffi::c_void;
 use std::ptr::{self, NonNull};
 use std::sync::OnceLock;
 
+use jawt::render_loop::{RenderLoop, RenderLoopOptions};
 use jawt::{Awt, AwtVersion};
+use jawt_wgpu::PlatformRenderTarget;
 use jni::objects::{JClass, JObject};
 use jni::sys::{jint, jlong, JNI_VERSION_1_8};
 use jni::{JNIEnv, JavaVM};
@@ -37,101 +39,62 @@ pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_create(
 
     let awt = awt(&env);
 
-    let mut drawing_surface = awt
-        .drawing_surface(&env, target)
-        .expect("could not retrieve DrawingSurface");
-
-    let mut lock = drawing_surface
-        .lock()
-        .expect("could not lock the DrawingSurface")
-        .1;
-
-    let dsi = lock
-        .drawing_surface_info()
-        .expect("could not retrieve DrawingSurfaceInfo");
-
-    #[cfg(target_os = "windows")]
-    let render_target = crate::graphics::windows::WindowRenderTarget::new(
-        dsi.platform_info()
-            .surface_kind()
-            .and_then(|s| s.window())
-            .expect("surface doesn't have a HWND handle"),
-    );
-
-    #[cfg(target_os = "macos")]
-    let render_target = unsafe {
-        use jawt::macos::SurfaceLayers;
-        use objc2_quartz_core::{CAMetalLayer, CATransaction};
-
-        use crate::graphics::macos::CAMetalLayerRenderTarget;
-
-        let platform_info = dsi.platform_info();
-        let layer = CAMetalLayer::layer();
-        let window_layer = platform_info.window_layer();
-
-        layer.setContentsScale(window_layer.contentsScale());
-        layer.setFrame(platform_info.window_layer().bounds());
-
-        CATransaction::begin();
-        platform_info.set_layer(Some(&layer));
-        window_layer.addSublayer(&layer);
-        CATransaction::commit();
-        CATransaction::flush();
+    let render_loop = RenderLoop::spawn(
+        &env,
+        awt,
+        &target,
+        RenderLoopOptions::default(),
+        |_env, drawing_surface| {
+            let Some((_, mut lock)) = drawing_surface.lock() else {
+                log::error!("could not lock the DrawingSurface");
+                return None;
+            };
+            let Some(dsi) = lock.drawing_surface_info() else {
+                log::error!("could not retrieve DrawingSurfaceInfo");
+                return None;
+            };
+            let target = match PlatformRenderTarget::from_drawing_surface_info(&dsi) {
+                Ok(target) => target,
+                Err(e) => {
+                    log::error!("could not create the render target: {e}");
+                    return None;
+                }
+            };
+            Some(RenderContext::new(target))
+        },
+    )
+    .expect("could not spawn the render loop");
+    let render_loop = Box::new(render_loop);
+
+    Box::into_raw(render_loop).expose_provenance() as jlong
+}
 
-        CAMetalLayerRenderTarget::new(&layer)
+fn with_render_loop(render_loop: jlong, f: impl FnOnce(&RenderLoop)) {
+    let render_loop = ptr::with_exposed_provenance_mut::<RenderLoop>(render_loop as usize);
+    let Some(render_loop) = NonNull::new(render_loop) else {
+        return;
     };
-
-    #[cfg(all(
-        target_family = "unix",
-        not(target_vendor = "apple"),
-        not(target_os = "android")
-    ))]
-    let render_target = crate::graphics::unix::X11RenderTarget::new(
-        dsi.platform_info().display(),
-        dsi.platform_info()
-            .window()
-            .expect("surface doesn't have a window drawable"),
-    );
-
-    let render_context = RenderContext::new(render_target);
-    let render_context = Box::new(render_context);
-
-    Box::into_raw(render_context).expose_provenance() as jlong
+    f(unsafe { render_loop.as_ref() });
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_render(
+pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_pause(
     _env: JNIEnv,
     _class: JClass,
-    render_context: jlong,
+    render_loop: jlong,
 ) {
-    let render_context = ptr::with_exposed_provenance_mut::<RenderContext>(render_context as usize);
-    let Some(render_context) = NonNull::new(render_context) else {
-        return;
-    };
-    let render_context = unsafe { render_context.as_ref() };
-    render_context.render();
+    with_render_loop(render_loop, RenderLoop::pause);
 }
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_resize(
+pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_resume(
     _env: JNIEnv,
     _class: JClass,
-    render_context: jlong,
-    width: jint,
-    height: jint,
+    render_loop: jlong,
 ) {
-    let render_context = ptr::with_exposed_provenance_mut::<RenderContext>(render_context as usize);
-    let Some(render_context) = NonNull::new(render_context) else {
-        return;
-    };
-    let render_context = unsafe { render_context.as_ref() };
-    render_context.change_size(
-        u32::try_from(width).unwrap_or_default(),
-        u32::try_from(height).unwrap_or_default(),
-    );
+    with_render_loop(render_loop, RenderLoop::resume);
 }
 
 #[allow(non_snake_case)]
@@ -139,12 +102,15 @@ pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_resize(
 pub extern "C" fn Java_dev_gobley_jawt_tests_RustCanvas_destroy(
     _env: JNIEnv,
     _class: JClass,
-    render_context: jlong,
+    render_loop: jlong,
 ) {
-    let render_context = ptr::with_exposed_provenance_mut::<RenderContext>(render_context as usize);
-    let Some(render_context) = NonNull::new(render_context) else {
+    let render_loop = ptr::with_exposed_provenance_mut::<RenderLoop>(render_loop as usize);
+    let Some(render_loop) = NonNull::new(render_loop) else {
         return;
     };
     // destroy
-    let _ = unsafe { Box::from_raw(render_context.as_ptr()) };
+    let render_loop = unsafe { Box::from_raw(render_loop.as_ptr()) };
+    if render_loop.stop().is_err() {
+        log::error!("the render loop panicked");
+    }
 }
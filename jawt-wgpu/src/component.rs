@@ -0,0 +1,87 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [AwtComponent] struct.
+
+use jawt::{DrawingSurface, DrawingSurfaceLockResult};
+use wgpu::{CreateSurfaceError, Instance, Surface};
+
+use crate::{AwtSurfaceError, PlatformRenderTarget, RenderTarget};
+
+/// A component such as a `java.awt.Canvas` rendered to with wgpu. Owns the [DrawingSurface] of the
+/// component and the [PlatformRenderTarget] created from it. Like [DrawingSurface], it must only be
+/// used on the thread the drawing surface was created on.
+pub struct AwtComponent {
+    target: PlatformRenderTarget,
+    drawing_surface: DrawingSurface,
+}
+
+impl AwtComponent {
+    /// Locks `drawing_surface` and creates the render target of its native window or layer.
+    pub fn new(mut drawing_surface: DrawingSurface) -> Result<Self, AwtSurfaceError> {
+        let target = Self::create_target(&mut drawing_surface)?;
+        Ok(Self {
+            target,
+            drawing_surface,
+        })
+    }
+
+    fn create_target(
+        drawing_surface: &mut DrawingSurface,
+    ) -> Result<PlatformRenderTarget, AwtSurfaceError> {
+        let (_, mut guard) = drawing_surface.lock().ok_or(AwtSurfaceError::LockFailed)?;
+        let dsi = guard
+            .drawing_surface_info()
+            .ok_or(AwtSurfaceError::DrawingSurfaceInfoUnavailable)?;
+        PlatformRenderTarget::from_drawing_surface_info(&dsi)
+    }
+
+    /// Returns the render target of the native window or layer of the component.
+    pub fn target(&self) -> &PlatformRenderTarget {
+        &self.target
+    }
+
+    /// Returns the drawing surface of the component.
+    pub fn drawing_surface(&mut self) -> &mut DrawingSurface {
+        &mut self.drawing_surface
+    }
+
+    /// Locks and unlocks the drawing surface, and returns what changed since it was last locked.
+    /// Re-creates the render target when the surface itself has changed, and updates it when the
+    /// bounds have changed.
+    pub fn poll_changes(&mut self) -> Result<DrawingSurfaceLockResult, AwtSurfaceError> {
+        let (lock_result, mut guard) = self
+            .drawing_surface
+            .lock()
+            .ok_or(AwtSurfaceError::LockFailed)?;
+        if lock_result.intersects(
+            DrawingSurfaceLockResult::SURFACE_CHANGED | DrawingSurfaceLockResult::BOUNDS_CHANGED,
+        ) {
+            let dsi = guard
+                .drawing_surface_info()
+                .ok_or(AwtSurfaceError::DrawingSurfaceInfoUnavailable)?;
+            if lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED) {
+                self.target = PlatformRenderTarget::from_drawing_surface_info(&dsi)?;
+            } else {
+                self.target.update(&dsi);
+            }
+        }
+        Ok(lock_result)
+    }
+}
+
+impl RenderTarget for AwtComponent {
+    fn size(&self) -> (u32, u32) {
+        self.target.size()
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.target.scale_factor()
+    }
+
+    unsafe fn create_surface(
+        &self,
+        instance: &Instance,
+    ) -> Result<Surface<'static>, CreateSurfaceError> {
+        unsafe { self.target.create_surface(instance) }
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [AwtSurfaceError] enum.
+
+use std::error::Error;
+use std::fmt;
+
+use jawt::PlatformInfoError;
+use wgpu::{BufferAsyncError, CreateSurfaceError, SurfaceError, TextureFormat};
+
+/// Errors [AwtComponent], [AwtSurface] and [ImageSurface] can return.
+///
+/// [AwtComponent]: crate::AwtComponent
+/// [AwtSurface]: crate::AwtSurface
+/// [ImageSurface]: crate::ImageSurface
+#[derive(Debug, Clone)]
+pub enum AwtSurfaceError {
+    /// The drawing surface could not be locked.
+    LockFailed,
+    /// The drawing surface information could not be retrieved.
+    DrawingSurfaceInfoUnavailable,
+    /// The drawing surface has no native window or layer to present to, for example because it is
+    /// backed by a pixmap.
+    UnsupportedSurface,
+    /// The platform information of the drawing surface can't be used, for example because it was
+    /// created by the Wayland toolkit.
+    PlatformInfo(PlatformInfoError),
+    /// wgpu failed to create a surface for the target.
+    CreateSurface(CreateSurfaceError),
+    /// The adapter cannot present to the surface.
+    IncompatibleAdapter,
+    /// Acquiring a frame failed with an error other than the surface being lost or outdated.
+    Surface(SurfaceError),
+    /// A frame was presented to a surface it was not acquired from.
+    ForeignFrame,
+    /// An [ImageSurface](crate::ImageSurface) can't read back frames of this format.
+    UnsupportedFormat(TextureFormat),
+    /// Reading back a frame of an [ImageSurface](crate::ImageSurface) failed.
+    Readback(BufferAsyncError),
+    /// A JNI call failed. Contains the description of the JNI error.
+    Jni(String),
+}
+
+impl fmt::Display for AwtSurfaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LockFailed => write!(f, "failed to lock the drawing surface"),
+            Self::DrawingSurfaceInfoUnavailable => {
+                write!(f, "failed to get the drawing surface information")
+            }
+            Self::UnsupportedSurface => {
+                write!(
+                    f,
+                    "the drawing surface has no window or layer to present to"
+                )
+            }
+            Self::PlatformInfo(error) => write!(f, "invalid platform information: {error}"),
+            Self::CreateSurface(error) => write!(f, "failed to create a surface: {error}"),
+            Self::IncompatibleAdapter => write!(f, "the adapter cannot present to the surface"),
+            Self::Surface(error) => write!(f, "failed to acquire a frame: {error}"),
+            Self::ForeignFrame => write!(f, "the frame was acquired from another surface"),
+            Self::UnsupportedFormat(format) => {
+                write!(f, "frames of format {format:?} can't be read back")
+            }
+            Self::Readback(error) => write!(f, "failed to read back a frame: {error}"),
+            Self::Jni(message) => write!(f, "a JNI call failed: {message}"),
+        }
+    }
+}
+
+impl Error for AwtSurfaceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::PlatformInfo(error) => Some(error),
+            Self::CreateSurface(error) => Some(error),
+            Self::Surface(error) => Some(error),
+            Self::Readback(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<CreateSurfaceError> for AwtSurfaceError {
+    fn from(value: CreateSurfaceError) -> Self {
+        Self::CreateSurface(value)
+    }
+}
+
+impl From<PlatformInfoError> for AwtSurfaceError {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::PlatformInfo(value)
+    }
+}
+
+impl From<SurfaceError> for AwtSurfaceError {
+    fn from(value: SurfaceError) -> Self {
+        Self::Surface(value)
+    }
+}
+
+impl From<BufferAsyncError> for AwtSurfaceError {
+    fn from(value: BufferAsyncError) -> Self {
+        Self::Readback(value)
+    }
+}
+
+impl From<jni::errors::Error> for AwtSurfaceError {
+    fn from(value: jni::errors::Error) -> Self {
+        Self::Jni(value.to_string())
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [ComponentSurface] struct.
+
+use jawt::Awt;
+use jni::objects::{GlobalRef, JObject};
+use jni::{JNIEnv, JavaVM};
+use wgpu::{Adapter, Device, Instance, Queue, TextureFormat};
+
+use crate::image::is_supported_format;
+use crate::{
+    AwtComponent, AwtSurface, AwtSurfaceError, FrameSurface, ImagePresenter, ImageSurface,
+    SurfaceFrame,
+};
+
+/// A surface for a `java.awt.Component` that presents natively through JAWT when it can, and
+/// falls back to an [ImageSurface] when it can't.
+///
+/// The native path is tried when the surface is created. If JAWT is unavailable or the drawing
+/// surface of the component can't be presented to, frames go through a `BufferedImage` instead.
+/// The surface also switches to the fallback later if [ComponentSurface::poll()] finds that the
+/// drawing surface can no longer be presented to, or fails to lock it
+/// [ComponentSurface::MAX_LOCK_FAILURES] times in a row, such as after the component is moved
+/// into a window JAWT can't lock. It never switches back.
+///
+/// Either way, `paint()` of the component should call [ImagePresenter::paint()] with
+/// [ComponentSurface::presenter()], which draws nothing while frames are presented natively.
+pub struct ComponentSurface {
+    vm: JavaVM,
+    component: GlobalRef,
+    presenter: ImagePresenter,
+    inner: Inner,
+    lock_failures: u32,
+}
+
+enum Inner {
+    Native(AwtSurface<AwtComponent>),
+    Image(ImageSurface),
+}
+
+impl ComponentSurface {
+    /// Number of consecutive [ComponentSurface::poll()] calls that must fail to lock the drawing
+    /// surface before the surface falls back to an [ImageSurface]. Fewer failures, such as while
+    /// the peer of the component is being re-created, are returned as errors instead.
+    pub const MAX_LOCK_FAILURES: u32 = 30;
+
+    /// Creates a surface for `component`, natively if `awt` is given and the drawing surface of the
+    /// component has a native window or layer.
+    ///
+    /// # Safety
+    ///
+    /// See [AwtSurface::new()].
+    pub unsafe fn new(
+        env: &mut JNIEnv,
+        awt: Option<&Awt>,
+        instance: &Instance,
+        component: &JObject,
+    ) -> Result<Self, AwtSurfaceError> {
+        let presenter = ImagePresenter::default();
+        let native = awt.and_then(|awt| {
+            // Safety: `component` outlives the new reference.
+            let target = unsafe { JObject::from_raw(component.as_raw()) };
+            let drawing_surface = awt.drawing_surface(env, target)?;
+            let component = AwtComponent::new(drawing_surface).ok()?;
+            unsafe { AwtSurface::new(instance, component) }.ok()
+        });
+        let inner = match native {
+            Some(surface) => Inner::Native(surface),
+            None => Inner::Image(ImageSurface::with_presenter(
+                env,
+                component,
+                ImageSurface::DEFAULT_FORMAT,
+                presenter.clone(),
+            )?),
+        };
+        Ok(Self {
+            vm: env.get_java_vm()?,
+            component: env.new_global_ref(component)?,
+            presenter,
+            inner,
+            lock_failures: 0,
+        })
+    }
+
+    /// Returns `true` if frames are presented natively rather than through a `BufferedImage`.
+    pub fn is_native(&self) -> bool {
+        matches!(self.inner, Inner::Native(_))
+    }
+
+    /// The presenter `paint()` of the component should draw the fallback frames with.
+    pub fn presenter(&self) -> &ImagePresenter {
+        &self.presenter
+    }
+
+    /// Configures a native surface with [AwtSurface::configure()], falling back to an
+    /// [ImageSurface] if `adapter` can't present to it.
+    pub fn configure(&mut self, adapter: &Adapter, device: &Device) -> Result<(), AwtSurfaceError> {
+        let Inner::Native(surface) = &mut self.inner else {
+            return Ok(());
+        };
+        match surface.configure(adapter, device) {
+            Err(AwtSurfaceError::IncompatibleAdapter) => self.fall_back(device),
+            result => result,
+        }
+    }
+
+    /// Applies changes to the component: a native surface is polled with [AwtSurface::poll()] and
+    /// falls back to an [ImageSurface] if the drawing surface can no longer be presented to, and an
+    /// [ImageSurface] is resized with [ImageSurface::poll()].
+    ///
+    /// If the drawing surface can't be locked, the error is returned and the next call tries
+    /// again. The surface falls back only after [ComponentSurface::MAX_LOCK_FAILURES] consecutive
+    /// failures. The format of the frames may change when the surface falls back.
+    pub fn poll(&mut self, device: &Device) -> Result<(), AwtSurfaceError> {
+        match &mut self.inner {
+            Inner::Native(surface) => match surface.poll(device) {
+                Ok(_) => {
+                    self.lock_failures = 0;
+                    Ok(())
+                }
+                Err(
+                    error @ (AwtSurfaceError::LockFailed
+                    | AwtSurfaceError::DrawingSurfaceInfoUnavailable),
+                ) => {
+                    self.lock_failures += 1;
+                    if self.lock_failures < Self::MAX_LOCK_FAILURES {
+                        return Err(error);
+                    }
+                    self.fall_back(device)
+                }
+                Err(AwtSurfaceError::UnsupportedSurface | AwtSurfaceError::PlatformInfo(_)) => {
+                    self.fall_back(device)
+                }
+                Err(error) => Err(error),
+            },
+            Inner::Image(surface) => surface.poll(device),
+        }
+    }
+
+    fn fall_back(&mut self, device: &Device) -> Result<(), AwtSurfaceError> {
+        let format = self
+            .inner_ref()
+            .format()
+            .filter(|format| is_supported_format(*format))
+            .unwrap_or(ImageSurface::DEFAULT_FORMAT);
+        let mut env = self.vm.attach_current_thread()?;
+        let mut surface = ImageSurface::with_presenter(
+            &mut env,
+            self.component.as_obj(),
+            format,
+            self.presenter.clone(),
+        )?;
+        drop(env);
+        surface.poll(device)?;
+        self.inner = Inner::Image(surface);
+        Ok(())
+    }
+
+    fn inner_ref(&self) -> &dyn FrameSurface {
+        match &self.inner {
+            Inner::Native(surface) => surface,
+            Inner::Image(surface) => surface,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn FrameSurface {
+        match &mut self.inner {
+            Inner::Native(surface) => surface,
+            Inner::Image(surface) => surface,
+        }
+    }
+}
+
+impl FrameSurface for ComponentSurface {
+    fn format(&self) -> Option<TextureFormat> {
+        self.inner_ref().format()
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.inner_ref().size()
+    }
+
+    fn resize(&mut self, device: &Device, width: u32, height: u32) {
+        self.inner_mut().resize(device, width, height);
+    }
+
+    fn acquire_frame(&mut self, device: &Device) -> Result<Option<SurfaceFrame>, AwtSurfaceError> {
+        self.inner_mut().acquire_frame(device)
+    }
+
+    fn present_frame(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        frame: SurfaceFrame,
+    ) -> Result<(), AwtSurfaceError> {
+        self.inner_mut().present_frame(device, queue, frame)
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [FrameSurface] trait.
+
+use wgpu::{Device, Queue, SurfaceTexture, Texture, TextureFormat};
+
+use crate::{AwtSurface, AwtSurfaceError, RenderTarget};
+
+/// A frame acquired with [FrameSurface::acquire_frame()].
+#[derive(Debug)]
+pub enum SurfaceFrame {
+    /// A frame of a native surface.
+    Surface(SurfaceTexture),
+    /// An offscreen texture of an [ImageSurface](crate::ImageSurface).
+    Image(Texture),
+}
+
+impl SurfaceFrame {
+    /// The texture to render the frame to.
+    pub fn texture(&self) -> &Texture {
+        match self {
+            SurfaceFrame::Surface(frame) => &frame.texture,
+            SurfaceFrame::Image(texture) => texture,
+        }
+    }
+}
+
+/// Where frames of a component go: a native surface with [AwtSurface], or a `BufferedImage` with
+/// [ImageSurface](crate::ImageSurface). Renderers written against this trait work with both.
+///
+/// Render to [SurfaceFrame::texture()] of an acquired frame, submit the commands, and pass the
+/// frame to [FrameSurface::present_frame()].
+pub trait FrameSurface {
+    /// Format of the frames, or [None] if the surface is not configured yet.
+    fn format(&self) -> Option<TextureFormat>;
+
+    /// Size of the frames in physical pixels, which may be zero.
+    fn size(&self) -> (u32, u32);
+
+    /// Resizes the frames to `width` by `height` physical pixels.
+    fn resize(&mut self, device: &Device, width: u32, height: u32);
+
+    /// Acquires the next frame. Returns [None] if no frame can be rendered now, for example
+    /// because the surface has no area.
+    fn acquire_frame(&mut self, device: &Device) -> Result<Option<SurfaceFrame>, AwtSurfaceError>;
+
+    /// Presents a frame acquired from this surface.
+    fn present_frame(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        frame: SurfaceFrame,
+    ) -> Result<(), AwtSurfaceError>;
+}
+
+impl<T: RenderTarget> FrameSurface for AwtSurface<T> {
+    fn format(&self) -> Option<TextureFormat> {
+        self.config().map(|config| config.format)
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.config()
+            .map_or((0, 0), |config| (config.width, config.height))
+    }
+
+    fn resize(&mut self, device: &Device, width: u32, height: u32) {
+        AwtSurface::resize(self, device, width, height);
+    }
+
+    fn acquire_frame(&mut self, device: &Device) -> Result<Option<SurfaceFrame>, AwtSurfaceError> {
+        Ok(self.acquire(device)?.map(SurfaceFrame::Surface))
+    }
+
+    fn present_frame(
+        &mut self,
+        _device: &Device,
+        _queue: &Queue,
+        frame: SurfaceFrame,
+    ) -> Result<(), AwtSurfaceError> {
+        match frame {
+            SurfaceFrame::Surface(frame) => {
+                frame.present();
+                Ok(())
+            }
+            SurfaceFrame::Image(_) => Err(AwtSurfaceError::ForeignFrame),
+        }
+    }
+}
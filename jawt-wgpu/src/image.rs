@@ -0,0 +1,341 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [ImageSurface] and [ImagePresenter].
+
+use std::fmt;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use jawt::ScaledImage;
+use jni::objects::{GlobalRef, JObject};
+use jni::{JNIEnv, JavaVM};
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Device, Extent3d, Maintain,
+    MapMode, Queue, TexelCopyBufferInfo, TexelCopyBufferLayout, TexelCopyTextureInfo, Texture,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+use crate::{AwtSurfaceError, FrameSurface, SurfaceFrame};
+
+/// Renders frames into an offscreen texture and hands them to AWT as a `BufferedImage`, for
+/// components whose drawing surface has no native window or layer wgpu can present to, such as
+/// lightweight components or components in a remote session.
+///
+/// Presenting a frame reads it back to the CPU, stores it in the [ImagePresenter] of the surface
+/// and calls `repaint()` of the component. The `paint()` method of the component then draws the
+/// latest frame with [ImagePresenter::paint()].
+///
+/// Like [LightweightSurface](jawt::LightweightSurface), frames are in physical pixels: the size
+/// of the component multiplied by the scale of its `GraphicsConfiguration`. They are drawn into
+/// the bounds of the component through a [ScaledImage], so Java2D copies them without resampling
+/// on HiDPI displays. Frames have premultiplied alpha.
+pub struct ImageSurface {
+    vm: JavaVM,
+    component: GlobalRef,
+    format: TextureFormat,
+    /// The size of the component in the AWT coordinate space.
+    component_size: (u32, u32),
+    size: (u32, u32),
+    texture: Option<Texture>,
+    readback: Option<Buffer>,
+    presenter: ImagePresenter,
+}
+
+impl fmt::Debug for ImageSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageSurface")
+            .field("component", &self.component.as_obj())
+            .field("format", &self.format)
+            .field("component_size", &self.component_size)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl ImageSurface {
+    /// The format of the frames unless another one is given to [ImageSurface::with_format()].
+    pub const DEFAULT_FORMAT: TextureFormat = TextureFormat::Bgra8UnormSrgb;
+
+    /// Creates a surface for `component`, which must be a `java.awt.Component`, sized to the
+    /// current size and scale of the component.
+    pub fn new(env: &mut JNIEnv, component: &JObject) -> Result<Self, AwtSurfaceError> {
+        Self::with_format(env, component, Self::DEFAULT_FORMAT)
+    }
+
+    /// Creates a surface whose frames have `format`, which must be one of the 8-bit RGBA or BGRA
+    /// formats.
+    pub fn with_format(
+        env: &mut JNIEnv,
+        component: &JObject,
+        format: TextureFormat,
+    ) -> Result<Self, AwtSurfaceError> {
+        Self::with_presenter(env, component, format, ImagePresenter::default())
+    }
+
+    pub(crate) fn with_presenter(
+        env: &mut JNIEnv,
+        component: &JObject,
+        format: TextureFormat,
+        presenter: ImagePresenter,
+    ) -> Result<Self, AwtSurfaceError> {
+        if !is_supported_format(format) {
+            return Err(AwtSurfaceError::UnsupportedFormat(format));
+        }
+        let (component_size, size) = component_sizes(env, component)?;
+        Ok(Self {
+            vm: env.get_java_vm()?,
+            component: env.new_global_ref(component)?,
+            format,
+            component_size,
+            size,
+            texture: None,
+            readback: None,
+            presenter,
+        })
+    }
+
+    /// The presenter the component paints the frames of this surface with.
+    pub fn presenter(&self) -> &ImagePresenter {
+        &self.presenter
+    }
+
+    /// Resizes the surface to the current size and scale of the component.
+    pub fn poll(&mut self, device: &Device) -> Result<(), AwtSurfaceError> {
+        let (component_size, (width, height)) = {
+            let mut env = self.vm.attach_current_thread()?;
+            component_sizes(&mut env, self.component.as_obj())?
+        };
+        self.component_size = component_size;
+        self.resize(device, width, height);
+        Ok(())
+    }
+
+    fn texture(&mut self, device: &Device) -> Texture {
+        let (width, height) = self.size;
+        self.texture
+            .get_or_insert_with(|| {
+                device.create_texture(&TextureDescriptor {
+                    label: Some("jawt-wgpu image surface"),
+                    size: Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: self.format,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                })
+            })
+            .clone()
+    }
+
+    fn bytes_per_row(&self) -> u32 {
+        (self.size.0 * 4).next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+    }
+
+    /// Copies `texture` to the CPU and converts it to premultiplied `0xAARRGGBB` pixels.
+    fn read_back(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        texture: &Texture,
+    ) -> Result<Vec<u32>, AwtSurfaceError> {
+        let (width, height) = self.size;
+        let bytes_per_row = self.bytes_per_row();
+        let readback = self.readback.get_or_insert_with(|| {
+            device.create_buffer(&BufferDescriptor {
+                label: Some("jawt-wgpu image surface readback"),
+                size: u64::from(bytes_per_row) * u64::from(height),
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        });
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: Default::default(),
+                aspect: Default::default(),
+            },
+            TexelCopyBufferInfo {
+                buffer: readback,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device.poll(Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+
+        let bgra = matches!(
+            self.format,
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb
+        );
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks_exact(bytes_per_row as usize) {
+                pixels.extend(row[..width as usize * 4].chunks_exact(4).map(|texel| {
+                    let [r, g, b, a] = if bgra {
+                        [texel[2], texel[1], texel[0], texel[3]]
+                    } else {
+                        [texel[0], texel[1], texel[2], texel[3]]
+                    };
+                    u32::from_be_bytes([a, r, g, b])
+                }));
+            }
+        }
+        readback.unmap();
+        Ok(pixels)
+    }
+}
+
+impl FrameSurface for ImageSurface {
+    fn format(&self) -> Option<TextureFormat> {
+        Some(self.format)
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn resize(&mut self, _device: &Device, width: u32, height: u32) {
+        if self.size == (width, height) {
+            return;
+        }
+        self.size = (width, height);
+        self.texture = None;
+        self.readback = None;
+    }
+
+    fn acquire_frame(&mut self, device: &Device) -> Result<Option<SurfaceFrame>, AwtSurfaceError> {
+        if self.size.0 == 0 || self.size.1 == 0 {
+            return Ok(None);
+        }
+        Ok(Some(SurfaceFrame::Image(self.texture(device))))
+    }
+
+    fn present_frame(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        frame: SurfaceFrame,
+    ) -> Result<(), AwtSurfaceError> {
+        let SurfaceFrame::Image(texture) = frame else {
+            return Err(AwtSurfaceError::ForeignFrame);
+        };
+        if self.texture.as_ref() != Some(&texture) {
+            // Acquired before the last resize.
+            return Ok(());
+        }
+        let pixels = self.read_back(device, queue, &texture)?;
+        self.presenter.set(self.component_size, self.size, pixels);
+
+        let mut env = self.vm.attach_current_thread()?;
+        env.call_method(self.component.as_obj(), "repaint", "()V", &[])?;
+        Ok(())
+    }
+}
+
+/// Paints the latest frame of an [ImageSurface]. Clones share the frame, so a clone can be moved
+/// to wherever `paint()` of the component is implemented.
+#[derive(Clone, Default)]
+pub struct ImagePresenter {
+    state: Arc<Mutex<PresenterState>>,
+}
+
+#[derive(Default)]
+struct PresenterState {
+    component_size: (u32, u32),
+    size: (u32, u32),
+    pixels: Vec<u32>,
+    dirty: bool,
+    image: ScaledImage,
+}
+
+impl fmt::Debug for ImagePresenter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImagePresenter")
+            .field("size", &self.state().size)
+            .finish()
+    }
+}
+
+impl ImagePresenter {
+    fn state(&self) -> MutexGuard<'_, PresenterState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn set(&self, component_size: (u32, u32), size: (u32, u32), pixels: Vec<u32>) {
+        let mut state = self.state();
+        state.component_size = component_size;
+        state.size = size;
+        state.pixels = pixels;
+        state.dirty = true;
+    }
+
+    /// Draws the latest frame onto `graphics`, a `java.awt.Graphics`, into the bounds of the
+    /// component. Returns `false` if no frame has been presented yet.
+    pub fn paint(&self, env: &mut JNIEnv, graphics: &JObject) -> jni::errors::Result<bool> {
+        let mut state = self.state();
+        if state.pixels.is_empty() || state.component_size.0 == 0 || state.component_size.1 == 0 {
+            return Ok(false);
+        }
+        let component_size = (state.component_size.0 as i32, state.component_size.1 as i32);
+        let size = (state.size.0 as i32, state.size.1 as i32);
+        let state = &mut *state;
+        let (image, created) = state.image.resize(env, component_size, size)?;
+        if state.dirty || created {
+            image.write_pixels(env, 0, &state.pixels)?;
+            state.dirty = false;
+        }
+        state.image.draw(env, graphics)
+    }
+}
+
+pub(crate) fn is_supported_format(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::Bgra8Unorm
+            | TextureFormat::Bgra8UnormSrgb
+            | TextureFormat::Rgba8Unorm
+            | TextureFormat::Rgba8UnormSrgb
+    )
+}
+
+/// Returns the size of `component` in the AWT coordinate space and in physical pixels.
+fn component_sizes(
+    env: &mut JNIEnv,
+    component: &JObject,
+) -> jni::errors::Result<((u32, u32), (u32, u32))> {
+    let width = env
+        .call_method(component, "getWidth", "()I", &[])?
+        .i()?
+        .max(0);
+    let height = env
+        .call_method(component, "getHeight", "()I", &[])?
+        .i()?
+        .max(0);
+    let scale = ScaledImage::component_scale(env, component)?;
+    let (physical_width, physical_height) = ScaledImage::physical_size((width, height), scale);
+    Ok((
+        (width as u32, height as u32),
+        (physical_width as u32, physical_height as u32),
+    ))
+}
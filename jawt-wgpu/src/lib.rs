@@ -0,0 +1,35 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! [wgpu] surfaces for Java AWT components. [AwtComponent] creates the native target of a
+//! `java.awt.Canvas` from its [DrawingSurface], and [AwtSurface] keeps a [wgpu::Surface] for it
+//! configured as the Canvas is resized, rescaled, hidden or re-created.
+//!
+//! Components JAWT can't present to natively are rendered through a `BufferedImage` with
+//! [ImageSurface]. [ComponentSurface] picks between the two automatically, and both implement
+//! [FrameSurface].
+//!
+//! [DrawingSurface]: jawt::DrawingSurface
+
+mod component;
+pub use component::*;
+
+mod error;
+pub use error::*;
+
+mod fallback;
+pub use fallback::*;
+
+mod frame;
+pub use frame::*;
+
+mod image;
+pub use image::*;
+
+mod md;
+pub use md::*;
+
+mod surface;
+pub use surface::*;
+
+mod target;
+pub use target::*;
@@ -0,0 +1,38 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements platform-specific render targets.
+
+#[cfg(target_os = "windows")]
+pub mod windows;
+
+#[cfg(target_os = "macos")]
+pub mod macos;
+
+#[cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+pub mod unix;
+
+/// The [RenderTarget] of the current platform.
+///
+/// [RenderTarget]: crate::RenderTarget
+#[cfg(target_os = "windows")]
+pub type PlatformRenderTarget = windows::WindowRenderTarget;
+
+/// The [RenderTarget] of the current platform.
+///
+/// [RenderTarget]: crate::RenderTarget
+#[cfg(target_os = "macos")]
+pub type PlatformRenderTarget = macos::CAMetalLayerRenderTarget;
+
+/// The [RenderTarget] of the current platform.
+///
+/// [RenderTarget]: crate::RenderTarget
+#[cfg(all(
+    target_family = "unix",
+    not(target_vendor = "apple"),
+    not(target_os = "android")
+))]
+pub type PlatformRenderTarget = unix::X11RenderTarget;
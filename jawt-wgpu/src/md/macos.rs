@@ -0,0 +1,84 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [CAMetalLayerRenderTarget].
+
+use jawt::macos::SurfaceLayers;
+use jawt::DrawingSurfaceInfo;
+use objc2::rc::Retained;
+use objc2::Message;
+use objc2_quartz_core::{CAMetalLayer, CATransaction};
+use wgpu::*;
+
+use crate::{AwtSurfaceError, RenderTarget};
+
+/// A `CAMetalLayer`, which is removed from its superlayer when the target is dropped.
+#[repr(transparent)]
+pub struct CAMetalLayerRenderTarget(Retained<CAMetalLayer>);
+
+unsafe impl Send for CAMetalLayerRenderTarget {}
+
+unsafe impl Sync for CAMetalLayerRenderTarget {}
+
+impl CAMetalLayerRenderTarget {
+    pub fn new(inner: &CAMetalLayer) -> Self {
+        Self(inner.retain())
+    }
+
+    /// Creates a `CAMetalLayer` covering the window layer of a drawing surface and sets it as the
+    /// layer of the drawing surface.
+    pub fn from_drawing_surface_info(dsi: &DrawingSurfaceInfo) -> Result<Self, AwtSurfaceError> {
+        let platform_info = dsi.try_platform_info()?;
+        let layer = unsafe { CAMetalLayer::layer() };
+        let window_layer = platform_info.window_layer();
+
+        layer.setContentsScale(window_layer.contentsScale());
+        layer.setFrame(window_layer.bounds());
+
+        CATransaction::begin();
+        platform_info.set_layer(Some(&layer));
+        window_layer.addSublayer(&layer);
+        CATransaction::commit();
+        CATransaction::flush();
+
+        Ok(Self::new(&layer))
+    }
+
+    /// Does nothing, as the size is queried from the layer directly. Exists so that code using
+    /// [PlatformRenderTarget](crate::PlatformRenderTarget) can call it on every platform.
+    pub fn update(&mut self, _dsi: &DrawingSurfaceInfo) {}
+}
+
+impl RenderTarget for CAMetalLayerRenderTarget {
+    fn size(&self) -> (u32, u32) {
+        let size = self.0.bounds().size;
+        let scale = self.0.contentsScale();
+        (
+            (size.width * scale).round() as u32,
+            (size.height * scale).round() as u32,
+        )
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.0.contentsScale()
+    }
+
+    unsafe fn create_surface(
+        &self,
+        instance: &Instance,
+    ) -> Result<Surface<'static>, CreateSurfaceError> {
+        unsafe {
+            instance.create_surface_unsafe(SurfaceTargetUnsafe::CoreAnimationLayer(
+                Retained::as_ptr(&self.0) as _,
+            ))
+        }
+    }
+}
+
+impl Drop for CAMetalLayerRenderTarget {
+    fn drop(&mut self) {
+        CATransaction::begin();
+        self.0.removeFromSuperlayer();
+        CATransaction::commit();
+        CATransaction::flush();
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [X11RenderTarget].
+
+use std::ffi::c_int;
+use std::ptr::NonNull;
+
+use jawt::DrawingSurfaceInfo;
+use wgpu::rwh::*;
+use wgpu::*;
+use x11_dl::xlib::{Display, Window};
+
+use crate::{AwtSurfaceError, RenderTarget};
+
+/// An X11 window, such as the drawable of a Canvas.
+///
+/// The size of the window is recorded from the bounds of the drawing surface rather than queried
+/// from the X server, as the target is used while the drawing surface is unlocked and AWT's
+/// display connection may only be used while it is locked.
+pub struct X11RenderTarget {
+    display: *mut Display,
+    window: Window,
+    screen: c_int,
+    size: (u32, u32),
+}
+
+unsafe impl Send for X11RenderTarget {}
+
+unsafe impl Sync for X11RenderTarget {}
+
+impl X11RenderTarget {
+    pub fn new(display: *mut Display, window: Window, screen: c_int, size: (u32, u32)) -> Self {
+        Self {
+            display,
+            window,
+            screen,
+            size,
+        }
+    }
+
+    /// Creates a target for the window of a drawing surface. Fails with
+    /// [AwtSurfaceError::UnsupportedSurface] if the drawing surface is backed by a pixmap, and with
+    /// [AwtSurfaceError::PlatformInfo] if it was not created by the X11 toolkit.
+    pub fn from_drawing_surface_info(dsi: &DrawingSurfaceInfo) -> Result<Self, AwtSurfaceError> {
+        let platform_info = dsi.try_platform_info()?;
+        let window = platform_info
+            .window()
+            .map_err(|_| AwtSurfaceError::UnsupportedSurface)?;
+        let screen = platform_info.screen_number().unwrap_or_default();
+        Ok(Self::new(
+            platform_info.display(),
+            window,
+            screen,
+            Self::size_of(dsi),
+        ))
+    }
+
+    /// Records the size of the window from the bounds of `dsi`. Call this whenever
+    /// [DrawingSurface::lock()] reports [DrawingSurfaceLockResult::BOUNDS_CHANGED];
+    /// [AwtComponent::poll_changes()] does so.
+    ///
+    /// [DrawingSurface::lock()]: jawt::DrawingSurface::lock()
+    /// [DrawingSurfaceLockResult::BOUNDS_CHANGED]: jawt::DrawingSurfaceLockResult::BOUNDS_CHANGED
+    /// [AwtComponent::poll_changes()]: crate::AwtComponent::poll_changes()
+    pub fn update(&mut self, dsi: &DrawingSurfaceInfo) {
+        self.size = Self::size_of(dsi);
+    }
+
+    fn size_of(dsi: &DrawingSurfaceInfo) -> (u32, u32) {
+        // The scale factor of the target is 1, so the bounds are in physical pixels already.
+        let bounds = dsi.bounds();
+        (
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+        )
+    }
+}
+
+impl RenderTarget for X11RenderTarget {
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    unsafe fn create_surface(
+        &self,
+        instance: &Instance,
+    ) -> Result<Surface<'static>, CreateSurfaceError> {
+        unsafe {
+            instance.create_surface_unsafe(SurfaceTargetUnsafe::RawHandle {
+                raw_display_handle: RawDisplayHandle::Xlib(XlibDisplayHandle::new(
+                    NonNull::new(self.display.cast()),
+                    self.screen,
+                )),
+                raw_window_handle: RawWindowHandle::Xlib(XlibWindowHandle::new(self.window)),
+            })
+        }
+    }
+}
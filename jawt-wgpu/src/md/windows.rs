@@ -0,0 +1,79 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [WindowRenderTarget].
+
+use std::mem::MaybeUninit;
+use std::num::NonZeroIsize;
+
+use jawt::DrawingSurfaceInfo;
+use wgpu::rwh::*;
+use wgpu::*;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use windows::Win32::UI::WindowsAndMessaging::{GetClientRect, USER_DEFAULT_SCREEN_DPI};
+
+use crate::{AwtSurfaceError, RenderTarget};
+
+/// A Win32 window, such as the `HWND` of a Canvas.
+#[repr(transparent)]
+pub struct WindowRenderTarget(HWND);
+
+unsafe impl Send for WindowRenderTarget {}
+
+unsafe impl Sync for WindowRenderTarget {}
+
+impl WindowRenderTarget {
+    pub fn new(inner: HWND) -> Self {
+        Self(inner)
+    }
+
+    /// Creates a target for the window of a drawing surface. Fails with
+    /// [AwtSurfaceError::UnsupportedSurface] if the drawing surface is backed by a bitmap.
+    pub fn from_drawing_surface_info(dsi: &DrawingSurfaceInfo) -> Result<Self, AwtSurfaceError> {
+        dsi.try_platform_info()?
+            .surface_kind()
+            .and_then(|kind| kind.window())
+            .map(Self::new)
+            .ok_or(AwtSurfaceError::UnsupportedSurface)
+    }
+
+    /// Does nothing, as the size is queried from the window directly. Exists so that code using
+    /// [PlatformRenderTarget](crate::PlatformRenderTarget) can call it on every platform.
+    pub fn update(&mut self, _dsi: &DrawingSurfaceInfo) {}
+}
+
+impl RenderTarget for WindowRenderTarget {
+    fn size(&self) -> (u32, u32) {
+        let rect = unsafe {
+            let mut rect = MaybeUninit::uninit();
+            if GetClientRect(self.0, rect.as_mut_ptr()).is_err() {
+                return (0, 0);
+            }
+            rect.assume_init()
+        };
+        (
+            u32::try_from(rect.right - rect.left).unwrap_or_default(),
+            u32::try_from(rect.bottom - rect.top).unwrap_or_default(),
+        )
+    }
+
+    fn scale_factor(&self) -> f64 {
+        match unsafe { GetDpiForWindow(self.0) } {
+            0 => 1.0,
+            dpi => f64::from(dpi) / f64::from(USER_DEFAULT_SCREEN_DPI),
+        }
+    }
+
+    unsafe fn create_surface(
+        &self,
+        instance: &Instance,
+    ) -> Result<Surface<'static>, CreateSurfaceError> {
+        let window = NonZeroIsize::new(self.0 .0 as isize).expect("the HWND is null");
+        unsafe {
+            instance.create_surface_unsafe(SurfaceTargetUnsafe::RawHandle {
+                raw_display_handle: RawDisplayHandle::Windows(WindowsDisplayHandle::new()),
+                raw_window_handle: RawWindowHandle::Win32(Win32WindowHandle::new(window)),
+            })
+        }
+    }
+}
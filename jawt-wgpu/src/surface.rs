@@ -0,0 +1,185 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [AwtSurface] struct.
+
+use jawt::DrawingSurfaceLockResult;
+use wgpu::{
+    Adapter, Device, Instance, Surface, SurfaceConfiguration, SurfaceError, SurfaceTexture,
+};
+
+use crate::{AwtComponent, AwtSurfaceError, RenderTarget};
+
+/// A [Surface] kept configured for the size of its [RenderTarget].
+///
+/// A surface can't be configured with a width or height of zero, which AWT components have while
+/// they are collapsed or not laid out yet. While the target has no area, [AwtSurface::acquire()]
+/// returns [None] instead of a frame.
+pub struct AwtSurface<T> {
+    instance: Instance,
+    surface: Surface<'static>,
+    config: Option<SurfaceConfiguration>,
+    configured: bool,
+    // Dropped after `surface`.
+    target: T,
+}
+
+impl<T: RenderTarget> AwtSurface<T> {
+    /// Creates a surface for `target`. Call [AwtSurface::configure()] before acquiring frames.
+    ///
+    /// # Safety
+    ///
+    /// The native window or layer of `target` must outlive the returned [AwtSurface]. For a
+    /// Canvas, drop it before `removeNotify()` returns.
+    pub unsafe fn new(instance: &Instance, target: T) -> Result<Self, AwtSurfaceError> {
+        let surface = unsafe { target.create_surface(instance)? };
+        Ok(Self {
+            instance: instance.clone(),
+            surface,
+            config: None,
+            configured: false,
+            target,
+        })
+    }
+
+    pub fn surface(&self) -> &Surface<'static> {
+        &self.surface
+    }
+
+    pub fn target(&self) -> &T {
+        &self.target
+    }
+
+    pub fn target_mut(&mut self) -> &mut T {
+        &mut self.target
+    }
+
+    /// The configuration of the surface, or [None] if it is not configured yet. Its width and height
+    /// may be zero.
+    pub fn config(&self) -> Option<&SurfaceConfiguration> {
+        self.config.as_ref()
+    }
+
+    /// Configures the surface with the preferred format and present mode of `adapter` and the
+    /// current size of the target.
+    pub fn configure(&mut self, adapter: &Adapter, device: &Device) -> Result<(), AwtSurfaceError> {
+        let (width, height) = self.target.size();
+        let mut config = self
+            .surface
+            .get_default_config(adapter, width.max(1), height.max(1))
+            .ok_or(AwtSurfaceError::IncompatibleAdapter)?;
+        config.width = width;
+        config.height = height;
+        self.configure_with(device, config);
+        Ok(())
+    }
+
+    /// Configures the surface with `config`. Its width and height are kept as they are.
+    pub fn configure_with(&mut self, device: &Device, config: SurfaceConfiguration) {
+        self.config = Some(config);
+        self.apply(device);
+    }
+
+    fn apply(&mut self, device: &Device) {
+        self.configured = false;
+        let Some(config) = &self.config else {
+            return;
+        };
+        if config.width == 0 || config.height == 0 {
+            return;
+        }
+        self.surface.configure(device, config);
+        self.configured = true;
+    }
+
+    /// Resizes the surface to `width` by `height` physical pixels.
+    pub fn resize(&mut self, device: &Device, width: u32, height: u32) {
+        let Some(config) = &mut self.config else {
+            return;
+        };
+        if config.width == width && config.height == height {
+            return;
+        }
+        config.width = width;
+        config.height = height;
+        self.apply(device);
+    }
+
+    /// Resizes the surface to a size in the AWT coordinate space, such as the width and height of
+    /// a `java.awt.Component`, using [RenderTarget::scale_factor()].
+    pub fn resize_logical(&mut self, device: &Device, width: i32, height: i32) {
+        let scale_factor = self.target.scale_factor();
+        let to_physical = |length: i32| (f64::from(length.max(0)) * scale_factor).round() as u32;
+        self.resize(device, to_physical(width), to_physical(height));
+    }
+
+    /// Re-creates the surface after the native window or layer of the target has changed.
+    pub fn recreate(&mut self, device: &Device) -> Result<(), AwtSurfaceError> {
+        // Safety: the caller of `new()` guarantees the target outlives `self`.
+        self.surface = unsafe { self.target.create_surface(&self.instance)? };
+        self.refresh(device);
+        Ok(())
+    }
+
+    /// Reconfigures the surface for the current size of the target.
+    fn refresh(&mut self, device: &Device) {
+        let (width, height) = self.target.size();
+        if let Some(config) = &mut self.config {
+            config.width = width;
+            config.height = height;
+        }
+        self.apply(device);
+    }
+
+    /// Applies what [DrawingSurface::lock()] reported: re-creates the surface if the surface itself
+    /// has changed, and resizes it if the bounds have changed.
+    ///
+    /// [DrawingSurface::lock()]: jawt::DrawingSurface::lock()
+    pub fn update(
+        &mut self,
+        device: &Device,
+        lock_result: DrawingSurfaceLockResult,
+    ) -> Result<(), AwtSurfaceError> {
+        if lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED) {
+            self.recreate(device)?;
+        } else if lock_result.contains(DrawingSurfaceLockResult::BOUNDS_CHANGED) {
+            let (width, height) = self.target.size();
+            self.resize(device, width, height);
+        }
+        Ok(())
+    }
+
+    /// Acquires the next frame. Returns [None] if the target has no area or no frame could be
+    /// acquired in time. A lost or outdated surface is reconfigured once before giving up on the
+    /// frame.
+    pub fn acquire(&mut self, device: &Device) -> Result<Option<SurfaceTexture>, AwtSurfaceError> {
+        if !self.configured {
+            return Ok(None);
+        }
+        match self.surface.get_current_texture() {
+            Ok(frame) => return Ok(Some(frame)),
+            Err(SurfaceError::Timeout) => return Ok(None),
+            Err(SurfaceError::Lost | SurfaceError::Outdated) => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        self.refresh(device);
+        if !self.configured {
+            return Ok(None);
+        }
+        match self.surface.get_current_texture() {
+            Ok(frame) => Ok(Some(frame)),
+            Err(SurfaceError::Timeout | SurfaceError::Lost | SurfaceError::Outdated) => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+impl AwtSurface<AwtComponent> {
+    /// Polls the drawing surface of the component for changes with [AwtComponent::poll_changes()]
+    /// and applies them with [AwtSurface::update()].
+    pub fn poll(&mut self, device: &Device) -> Result<DrawingSurfaceLockResult, AwtSurfaceError> {
+        let lock_result = self.target.poll_changes()?;
+        self.update(device, lock_result)?;
+        Ok(lock_result)
+    }
+}
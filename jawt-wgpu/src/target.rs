@@ -0,0 +1,44 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [RenderTarget] trait.
+
+use wgpu::{CreateSurfaceError, Instance, Surface};
+
+/// A native window or layer a [Surface] can be created for.
+pub trait RenderTarget {
+    /// Size of the target in physical pixels.
+    fn size(&self) -> (u32, u32);
+
+    /// Number of physical pixels per unit of the AWT coordinate space, such as the width of a
+    /// `java.awt.Component`.
+    fn scale_factor(&self) -> f64 {
+        1.0
+    }
+
+    /// Creates a [Surface] presenting to the target.
+    ///
+    /// # Safety
+    ///
+    /// The native window or layer of the target must outlive the returned [Surface].
+    unsafe fn create_surface(
+        &self,
+        instance: &Instance,
+    ) -> Result<Surface<'static>, CreateSurfaceError>;
+}
+
+impl<T: RenderTarget + ?Sized> RenderTarget for Box<T> {
+    fn size(&self) -> (u32, u32) {
+        (**self).size()
+    }
+
+    fn scale_factor(&self) -> f64 {
+        (**self).scale_factor()
+    }
+
+    unsafe fn create_surface(
+        &self,
+        instance: &Instance,
+    ) -> Result<Surface<'static>, CreateSurfaceError> {
+        unsafe { (**self).create_surface(instance) }
+    }
+}
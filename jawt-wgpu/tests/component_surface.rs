@@ -0,0 +1,117 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+
+use futures::executor::block_on;
+use jawt::sys::{JAWT_DrawingSurface, JAWT, JAWT_LOCK_ERROR};
+use jawt::Awt;
+use jawt_test_harness::{run_with_canvas_or_skip, skip_unless_ci, HarnessOptions};
+use jawt_wgpu::{AwtSurfaceError, ComponentSurface};
+use jni::objects::JObject;
+use jni::sys::{jint, jobject, JNIEnv};
+use wgpu::*;
+
+type GetDrawingSurface = unsafe extern "C" fn(*mut JNIEnv, jobject) -> *mut JAWT_DrawingSurface;
+type Lock = unsafe extern "C" fn(*mut JAWT_DrawingSurface) -> jint;
+
+static REAL_GET_DRAWING_SURFACE: OnceLock<GetDrawingSurface> = OnceLock::new();
+static REAL_LOCK: OnceLock<Lock> = OnceLock::new();
+static FAILING_LOCKS: AtomicU32 = AtomicU32::new(0);
+
+/// Returns the drawing surfaces of the real JAWT with a `Lock` that fails while
+/// [FAILING_LOCKS] is positive.
+unsafe extern "C" fn get_drawing_surface(
+    env: *mut JNIEnv,
+    target: jobject,
+) -> *mut JAWT_DrawingSurface {
+    let ds = unsafe { REAL_GET_DRAWING_SURFACE.get().unwrap()(env, target) };
+    if !ds.is_null() {
+        unsafe {
+            REAL_LOCK.get_or_init(|| (*ds).Lock.unwrap());
+            (*ds).Lock = Some(lock);
+        }
+    }
+    ds
+}
+
+unsafe extern "C" fn lock(ds: *mut JAWT_DrawingSurface) -> jint {
+    // Only the test thread locks the drawing surface.
+    let failing = FAILING_LOCKS.load(Ordering::SeqCst);
+    if failing > 0 {
+        FAILING_LOCKS.store(failing - 1, Ordering::SeqCst);
+        return JAWT_LOCK_ERROR;
+    }
+    unsafe { REAL_LOCK.get().unwrap()(ds) }
+}
+
+fn with_failing_locks(awt: &Awt) -> Awt {
+    let real = awt.as_ref();
+    REAL_GET_DRAWING_SURFACE.get_or_init(|| real.GetDrawingSurface.unwrap());
+    // Safety: every function but `GetDrawingSurface` is the one of the real JAWT, which forwards
+    // to it.
+    unsafe {
+        Awt::from_inner(JAWT {
+            version: real.version,
+            GetDrawingSurface: Some(get_drawing_surface),
+            FreeDrawingSurface: real.FreeDrawingSurface,
+            Lock: real.Lock,
+            Unlock: real.Unlock,
+            GetComponent: real.GetComponent,
+            CreateEmbeddedFrame: real.CreateEmbeddedFrame,
+            SetBounds: real.SetBounds,
+            SynthesizeWindowActivation: real.SynthesizeWindowActivation,
+        })
+    }
+}
+
+#[test]
+fn lock_failures_fall_back_only_when_they_persist() {
+    let options = HarnessOptions::default();
+    run_with_canvas_or_skip(&options, |context| {
+        let awt = with_failing_locks(context.awt);
+        let canvas = unsafe { JObject::from_raw(context.canvas.as_raw()) };
+        let instance = Instance::new(&InstanceDescriptor::default());
+        // Safety: the surface is dropped before the harness disposes the Frame.
+        let mut surface =
+            unsafe { ComponentSurface::new(context.env, Some(&awt), &instance, &canvas) }
+                .expect("failed to create the surface");
+        assert!(surface.is_native());
+
+        let Some(adapter) = block_on(instance.request_adapter(&RequestAdapterOptions {
+            power_preference: PowerPreference::LowPower,
+            compatible_surface: None,
+            force_fallback_adapter: true,
+        })) else {
+            skip_unless_ci("no fallback adapter is available");
+            return;
+        };
+        let (device, _) = block_on(adapter.request_device(&DeviceDescriptor::default(), None))
+            .expect("failed to get a device");
+        surface.poll(&device).expect("failed to poll the surface");
+
+        // A single failure is reported and retried on the next poll.
+        FAILING_LOCKS.store(1, Ordering::SeqCst);
+        assert!(matches!(
+            surface.poll(&device),
+            Err(AwtSurfaceError::LockFailed)
+        ));
+        assert!(surface.is_native());
+        surface.poll(&device).expect("failed to poll the surface");
+        assert!(surface.is_native());
+
+        // Persistent failures switch to the BufferedImage path.
+        FAILING_LOCKS.store(ComponentSurface::MAX_LOCK_FAILURES, Ordering::SeqCst);
+        for _ in 1..ComponentSurface::MAX_LOCK_FAILURES {
+            assert!(matches!(
+                surface.poll(&device),
+                Err(AwtSurfaceError::LockFailed)
+            ));
+            assert!(surface.is_native());
+        }
+        surface
+            .poll(&device)
+            .expect("failed to fall back to a BufferedImage");
+        assert!(!surface.is_native());
+    });
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use futures::executor::block_on;
+use jawt_test_harness::{run_with_canvas_or_skip, skip_unless_ci, HarnessOptions};
+use jawt_wgpu::{AwtComponent, AwtSurface, RenderTarget};
+use jni::objects::JObject;
+use wgpu::*;
+
+#[test]
+fn fallback_adapter_renders_and_survives_zero_size() {
+    let options = HarnessOptions::default();
+    run_with_canvas_or_skip(&options, |context| {
+        let canvas = unsafe { JObject::from_raw(context.canvas.as_raw()) };
+        let drawing_surface = context
+            .awt
+            .drawing_surface(context.env, canvas)
+            .expect("failed to get a drawing surface");
+        let component = AwtComponent::new(drawing_surface).expect("failed to create the target");
+
+        let instance = Instance::new(&InstanceDescriptor::default());
+        // Safety: the surface is dropped before the harness disposes the Frame.
+        let mut surface =
+            unsafe { AwtSurface::new(&instance, component) }.expect("failed to create the surface");
+        let Some(adapter) = block_on(instance.request_adapter(&RequestAdapterOptions {
+            power_preference: PowerPreference::LowPower,
+            compatible_surface: Some(surface.surface()),
+            force_fallback_adapter: true,
+        })) else {
+            // CI installs Mesa, whose lavapipe and llvmpipe drivers are fallback adapters.
+            skip_unless_ci("no fallback adapter is available");
+            return;
+        };
+        let (device, queue) = block_on(adapter.request_device(&DeviceDescriptor::default(), None))
+            .expect("failed to get a device");
+
+        surface
+            .configure(&adapter, &device)
+            .expect("failed to configure the surface");
+        let config = surface.config().unwrap();
+        let scale_factor = surface.target().scale_factor();
+        assert_eq!(
+            (config.width, config.height),
+            (
+                (f64::from(options.width) * scale_factor).round() as u32,
+                (f64::from(options.height) * scale_factor).round() as u32,
+            )
+        );
+
+        surface.poll(&device).expect("failed to poll the surface");
+        let frame = surface
+            .acquire(&device)
+            .expect("failed to acquire a frame")
+            .expect("no frame was acquired");
+        clear(&device, &queue, &frame);
+        frame.present();
+
+        surface.resize(&device, 0, 0);
+        assert!(surface
+            .acquire(&device)
+            .expect("failed to acquire a frame")
+            .is_none());
+
+        surface.resize_logical(&device, options.width, options.height);
+        let frame = surface
+            .acquire(&device)
+            .expect("failed to acquire a frame")
+            .expect("no frame was acquired after resizing back");
+        clear(&device, &queue, &frame);
+        frame.present();
+    });
+}
+
+fn clear(device: &Device, queue: &Queue, frame: &SurfaceTexture) {
+    let view = frame.texture.create_view(&TextureViewDescriptor::default());
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+    encoder.begin_render_pass(&RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &view,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(Color::BLACK),
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    queue.submit(Some(encoder.finish()));
+}
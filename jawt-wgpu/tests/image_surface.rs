@@ -0,0 +1,114 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+use futures::executor::block_on;
+use jawt::{BufferedImage, BufferedImageType};
+use jawt_test_harness::{headless_java_vm, image_rgb, or_skip, skip_unless_ci};
+use jawt_wgpu::{ComponentSurface, FrameSurface, SurfaceFrame};
+use jni::objects::JValue;
+use wgpu::*;
+
+#[test]
+fn frames_fall_back_to_a_buffered_image() {
+    let Some(java_vm) = or_skip(headless_java_vm()) else {
+        return;
+    };
+    let mut env = java_vm.attach_current_thread().unwrap();
+    let canvas = env.new_object("java/awt/Canvas", "()V", &[]).unwrap();
+    env.call_method(
+        &canvas,
+        "setSize",
+        "(II)V",
+        &[JValue::Int(64), JValue::Int(48)],
+    )
+    .unwrap();
+
+    let instance = Instance::new(&InstanceDescriptor::default());
+    // Safety: no native surface is created without JAWT.
+    let mut surface = unsafe { ComponentSurface::new(&mut env, None, &instance, &canvas) }
+        .expect("failed to create the surface");
+    assert!(!surface.is_native());
+    assert_eq!(surface.size(), (64, 48));
+
+    let Some(adapter) = block_on(instance.request_adapter(&RequestAdapterOptions {
+        power_preference: PowerPreference::LowPower,
+        compatible_surface: None,
+        force_fallback_adapter: true,
+    })) else {
+        skip_unless_ci("no fallback adapter is available");
+        return;
+    };
+    let (device, queue) = block_on(adapter.request_device(&DeviceDescriptor::default(), None))
+        .expect("failed to get a device");
+    surface
+        .configure(&adapter, &device)
+        .expect("failed to configure the surface");
+    surface.poll(&device).expect("failed to poll the surface");
+
+    let frame = surface
+        .acquire_frame(&device)
+        .expect("failed to acquire a frame")
+        .expect("no frame was acquired");
+    clear(&device, &queue, &frame, Color::RED);
+    surface
+        .present_frame(&device, &queue, frame)
+        .expect("failed to present the frame");
+
+    // The frame is drawn into the bounds of the Canvas whatever the scale of the Graphics.
+    let target = BufferedImage::new(&mut env, 128, 96, BufferedImageType::IntRgb).unwrap();
+    let graphics = env
+        .call_method(
+            target.as_obj(),
+            "createGraphics",
+            "()Ljava/awt/Graphics2D;",
+            &[],
+        )
+        .unwrap()
+        .l()
+        .unwrap();
+    env.call_method(
+        &graphics,
+        "scale",
+        "(DD)V",
+        &[JValue::Double(2.0), JValue::Double(2.0)],
+    )
+    .unwrap();
+    assert!(surface.presenter().paint(&mut env, &graphics).unwrap());
+    let pixel = |env: &mut _, x, y| image_rgb(env, target.as_obj(), x, y).unwrap() & 0xffffff;
+    assert_eq!(pixel(&mut env, 0, 0), 0xff0000);
+    assert_eq!(pixel(&mut env, 127, 95), 0xff0000);
+
+    env.call_method(
+        &canvas,
+        "setSize",
+        "(II)V",
+        &[JValue::Int(0), JValue::Int(0)],
+    )
+    .unwrap();
+    surface.poll(&device).expect("failed to poll the surface");
+    assert!(surface
+        .acquire_frame(&device)
+        .expect("failed to acquire a frame")
+        .is_none());
+}
+
+fn clear(device: &Device, queue: &Queue, frame: &SurfaceFrame, color: Color) {
+    let view = frame
+        .texture()
+        .create_view(&TextureViewDescriptor::default());
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+    encoder.begin_render_pass(&RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &view,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(color),
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    queue.submit(Some(encoder.finish()));
+}
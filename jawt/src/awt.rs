@@ -287,6 +287,8 @@ impl Awt {
         if unsafe { get_awt(env.get_raw(), &mut inner) } == JNI_FALSE {
             return None;
         }
+        #[cfg(feature = "validation")]
+        let inner = unsafe { crate::validation::wrap(inner) };
         Some(Self(inner))
     }
 
@@ -488,10 +490,15 @@ impl Awt {
     /// Using usual `get/setLocation()` and `get/setBounds()` together with this new
     /// method is not recommended.
     ///
+    /// Must be called on the AWT Event Dispatch Thread, which debug builds assert when the `edt`
+    /// feature is enabled. See `jawt::edt`.
+    ///
     /// [java.awt.Frame]: https://docs.oracle.com/en/java/javase/11/docs/api/java.desktop/java/awt/Frame.html
     /// [java.awt.Component.setLocation()]: https://docs.oracle.com/en/java/javase/11/docs/api/java.desktop/java/awt/Component.html#setLocation(int,int)
     /// [java.awt.Component.setBounds()]: https://docs.oracle.com/en/java/javase/11/docs/api/java.desktop/java/awt/Component.html#setBounds(int,int,int,int)
     pub fn set_bounds(&self, env: &JNIEnv, embedded_frame: AwtEmbeddedFrame, new_location: Rect) {
+        #[cfg(feature = "edt")]
+        crate::edt::debug_assert_dispatch_thread(env);
         unsafe {
             self.0.SetBounds.expect("JAWT.SetBounds is not available")(
                 env.get_raw(),
@@ -510,12 +517,17 @@ impl Awt {
     /// Synthesize a native message to activate or deactivate an EmbeddedFrame window depending on
     /// the value of parameter `do_activate`, if `true` activates the window; otherwise, deactivates
     /// the window.
+    ///
+    /// Must be called on the AWT Event Dispatch Thread, which debug builds assert when the `edt`
+    /// feature is enabled.
     pub fn synthesize_window_activation(
         &self,
         env: &JNIEnv,
         embedded_frame: AwtEmbeddedFrame,
         activate: bool,
     ) {
+        #[cfg(feature = "edt")]
+        crate::edt::debug_assert_dispatch_thread(env);
         unsafe {
             self.0
                 .SynthesizeWindowActivation
@@ -0,0 +1,384 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the [BufferedImage] struct.
+
+use std::fmt;
+use std::ops::Range;
+use std::slice;
+
+use jni::objects::{GlobalRef, JIntArray, JObject, JValue, ReleaseMode};
+use jni::sys::jint;
+use jni::JNIEnv;
+
+use crate::rect::Rect;
+
+/// Pixel layouts of a [BufferedImage] backed by an `int[]`. Every pixel is a `u32` of the form
+/// `0xAARRGGBB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BufferedImageType {
+    /// `TYPE_INT_RGB`. The alpha byte is ignored.
+    IntRgb,
+    /// `TYPE_INT_ARGB`, with straight alpha.
+    IntArgb,
+    /// `TYPE_INT_ARGB_PRE`, with premultiplied alpha.
+    IntArgbPre,
+}
+
+impl BufferedImageType {
+    /// The value of the corresponding `BufferedImage.TYPE_*` constant.
+    pub const fn into_raw(self) -> jint {
+        match self {
+            BufferedImageType::IntRgb => 1,
+            BufferedImageType::IntArgb => 2,
+            BufferedImageType::IntArgbPre => 3,
+        }
+    }
+}
+
+/// A [java.awt.image.BufferedImage] whose pixels Rust writes directly, for components that can't
+/// be rendered to natively and are painted with `Graphics.drawImage()` instead.
+///
+/// The pixels are copied in and out of the `int[]` backing the image inside a short
+/// `GetPrimitiveArrayCritical` section that does nothing but the copy. As Java2D can't track changes made this way, the image is never cached in
+/// video memory.
+///
+/// [java.awt.image.BufferedImage]: https://docs.oracle.com/en/java/javase/17/docs/api/java.desktop/java/awt/image/BufferedImage.html
+pub struct BufferedImage {
+    image: GlobalRef,
+    data: GlobalRef,
+    width: i32,
+    height: i32,
+    image_type: BufferedImageType,
+}
+
+impl fmt::Debug for BufferedImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedImage")
+            .field("image", &self.image.as_obj())
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("image_type", &self.image_type)
+            .finish()
+    }
+}
+
+impl BufferedImage {
+    /// Creates a `width` by `height` image. Both must be positive.
+    pub fn new(
+        env: &mut JNIEnv,
+        width: i32,
+        height: i32,
+        image_type: BufferedImageType,
+    ) -> jni::errors::Result<Self> {
+        env.with_local_frame(4, |env| {
+            let image = env.new_object(
+                "java/awt/image/BufferedImage",
+                "(III)V",
+                &[
+                    JValue::Int(width),
+                    JValue::Int(height),
+                    JValue::Int(image_type.into_raw()),
+                ],
+            )?;
+            let raster = env
+                .call_method(
+                    &image,
+                    "getRaster",
+                    "()Ljava/awt/image/WritableRaster;",
+                    &[],
+                )?
+                .l()?;
+            let data_buffer = env
+                .call_method(
+                    &raster,
+                    "getDataBuffer",
+                    "()Ljava/awt/image/DataBuffer;",
+                    &[],
+                )?
+                .l()?;
+            let data = env.call_method(&data_buffer, "getData", "()[I", &[])?.l()?;
+            Ok(Self {
+                image: env.new_global_ref(image)?,
+                data: env.new_global_ref(data)?,
+                width,
+                height,
+                image_type,
+            })
+        })
+    }
+
+    /// The `java.awt.image.BufferedImage`.
+    pub fn as_obj(&self) -> &JObject<'static> {
+        self.image.as_obj()
+    }
+
+    pub const fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub const fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub const fn image_type(&self) -> BufferedImageType {
+        self.image_type
+    }
+
+    /// Copies `pixels` into the image starting at pixel `start`, counted row by row from the top
+    /// left. Fails with an `ArrayIndexOutOfBoundsException` if they don't fit.
+    pub fn write_pixels(
+        &self,
+        env: &mut JNIEnv,
+        start: i32,
+        pixels: &[u32],
+    ) -> jni::errors::Result<()> {
+        let range = self.pixel_range(env, start, pixels.len())?;
+        // Safety: `jint` and `u32` have the same size and alignment.
+        let pixels = unsafe { slice::from_raw_parts(pixels.as_ptr().cast::<jint>(), pixels.len()) };
+        let data = self.data();
+        // Safety: the critical section only copies, and this is the only view of the array Rust
+        // creates.
+        let mut elements =
+            unsafe { env.get_array_elements_critical(&data, ReleaseMode::CopyBack)? };
+        elements[range].copy_from_slice(pixels);
+        Ok(())
+    }
+
+    /// Copies the pixels of the image starting at pixel `start`, counted row by row from the top
+    /// left, into `pixels`. Fails with an `ArrayIndexOutOfBoundsException` if they don't fit.
+    pub fn read_pixels(
+        &self,
+        env: &mut JNIEnv,
+        start: i32,
+        pixels: &mut [u32],
+    ) -> jni::errors::Result<()> {
+        let range = self.pixel_range(env, start, pixels.len())?;
+        // Safety: `jint` and `u32` have the same size and alignment.
+        let pixels =
+            unsafe { slice::from_raw_parts_mut(pixels.as_mut_ptr().cast::<jint>(), pixels.len()) };
+        let data = self.data();
+        // Safety: as in `write_pixels()`.
+        let elements = unsafe { env.get_array_elements_critical(&data, ReleaseMode::NoCopyBack)? };
+        pixels.copy_from_slice(&elements[range]);
+        Ok(())
+    }
+
+    /// Returns the indices of `len` pixels starting at `start`, or throws an
+    /// `ArrayIndexOutOfBoundsException` if they don't fit, as `Set/GetIntArrayRegion` would.
+    fn pixel_range(
+        &self,
+        env: &mut JNIEnv,
+        start: i32,
+        len: usize,
+    ) -> jni::errors::Result<Range<usize>> {
+        let pixel_count = self.width as usize * self.height as usize;
+        let range = usize::try_from(start)
+            .ok()
+            .and_then(|start| Some(start..start.checked_add(len)?))
+            .filter(|range| range.end <= pixel_count);
+        match range {
+            Some(range) => Ok(range),
+            None => {
+                env.throw_new(
+                    "java/lang/ArrayIndexOutOfBoundsException",
+                    format!("{len} pixels starting at {start} out of {pixel_count}"),
+                )?;
+                Err(jni::errors::Error::JavaException)
+            }
+        }
+    }
+
+    fn data(&self) -> JIntArray<'_> {
+        // Safety: the reference is an `int[]` as returned by `DataBufferInt.getData()`, and the
+        // returned wrapper doesn't outlive the global reference.
+        unsafe { JIntArray::from_raw(self.data.as_obj().as_raw()) }
+    }
+
+    /// Draws the image with its top-left corner at `x` and `y` with
+    /// `Graphics.drawImage(Image, int, int, ImageObserver)`.
+    pub fn draw(
+        &self,
+        env: &mut JNIEnv,
+        graphics: &JObject,
+        x: i32,
+        y: i32,
+    ) -> jni::errors::Result<bool> {
+        env.call_method(
+            graphics,
+            "drawImage",
+            "(Ljava/awt/Image;IILjava/awt/image/ImageObserver;)Z",
+            &[
+                JValue::Object(self.as_obj()),
+                JValue::Int(x),
+                JValue::Int(y),
+                JValue::Object(&JObject::null()),
+            ],
+        )?
+        .z()
+    }
+
+    /// Draws the image scaled to `bounds` with
+    /// `Graphics.drawImage(Image, int, int, int, int, ImageObserver)`, such as an image with
+    /// physical pixels into the logical bounds of a component.
+    pub fn draw_scaled(
+        &self,
+        env: &mut JNIEnv,
+        graphics: &JObject,
+        bounds: Rect,
+    ) -> jni::errors::Result<bool> {
+        env.call_method(
+            graphics,
+            "drawImage",
+            "(Ljava/awt/Image;IIIILjava/awt/image/ImageObserver;)Z",
+            &[
+                JValue::Object(self.as_obj()),
+                JValue::Int(bounds.x),
+                JValue::Int(bounds.y),
+                JValue::Int(bounds.width),
+                JValue::Int(bounds.height),
+                JValue::Object(&JObject::null()),
+            ],
+        )?
+        .z()
+    }
+}
+
+/// A [BufferedImage] with premultiplied alpha holding the physical pixels of a component, drawn
+/// into the bounds of the component in the AWT coordinate space. On HiDPI displays, Java2D copies
+/// it without resampling when its size matches the scale of the `Graphics`.
+///
+/// Used by [LightweightSurface](crate::LightweightSurface) and by the `BufferedImage` fallback of
+/// `jawt-wgpu`.
+#[derive(Debug, Default)]
+pub struct ScaledImage {
+    image: Option<BufferedImage>,
+    size: (i32, i32),
+}
+
+impl ScaledImage {
+    pub const fn new() -> Self {
+        Self {
+            image: None,
+            size: (0, 0),
+        }
+    }
+
+    /// The image, or [None] before [ScaledImage::resize()] succeeds.
+    pub fn image(&self) -> Option<&BufferedImage> {
+        self.image.as_ref()
+    }
+
+    /// The size of the component the image is drawn into, in the AWT coordinate space.
+    pub const fn size(&self) -> (i32, i32) {
+        self.size
+    }
+
+    /// Makes the image `physical_size` for a component of `size` and returns it, along with `true`
+    /// if it was re-created, in which case all of its pixels are transparent. Both sizes must be
+    /// positive.
+    pub fn resize(
+        &mut self,
+        env: &mut JNIEnv,
+        size: (i32, i32),
+        physical_size: (i32, i32),
+    ) -> jni::errors::Result<(&BufferedImage, bool)> {
+        self.size = size;
+        let (width, height) = physical_size;
+        let (image, created) = match self.image.take() {
+            Some(image) if (image.width(), image.height()) == (width, height) => (image, false),
+            _ => (
+                BufferedImage::new(env, width, height, BufferedImageType::IntArgbPre)?,
+                true,
+            ),
+        };
+        Ok((self.image.insert(image), created))
+    }
+
+    /// Draws the image into the bounds of the component onto `graphics`, a `java.awt.Graphics`.
+    /// Returns `false` if there is no image.
+    pub fn draw(&self, env: &mut JNIEnv, graphics: &JObject) -> jni::errors::Result<bool> {
+        let Some(image) = &self.image else {
+            return Ok(false);
+        };
+        let (width, height) = self.size;
+        image.draw_scaled(
+            env,
+            graphics,
+            Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            },
+        )
+    }
+
+    /// The size in physical pixels of a component of `size` at `scale`, rounded up so that the
+    /// image covers the whole component.
+    pub fn physical_size(size: (i32, i32), scale: (f64, f64)) -> (i32, i32) {
+        (to_physical(size.0, scale.0), to_physical(size.1, scale.1))
+    }
+
+    /// Returns the scale of the transform of `graphics`, or `(1.0, 1.0)` if it is not a
+    /// `java.awt.Graphics2D`.
+    pub fn graphics_scale(env: &mut JNIEnv, graphics: &JObject) -> jni::errors::Result<(f64, f64)> {
+        if !env.is_instance_of(graphics, "java/awt/Graphics2D")? {
+            return Ok((1.0, 1.0));
+        }
+        env.with_local_frame(2, |env| {
+            let transform = env
+                .call_method(
+                    graphics,
+                    "getTransform",
+                    "()Ljava/awt/geom/AffineTransform;",
+                    &[],
+                )?
+                .l()?;
+            transform_scale(env, &transform)
+        })
+    }
+
+    /// Returns the scale of the default transform of the `GraphicsConfiguration` of `component`,
+    /// which the `Graphics` it is painted with start from, or `(1.0, 1.0)` if the component is not
+    /// displayable. Use it to size frames before the component is painted.
+    pub fn component_scale(
+        env: &mut JNIEnv,
+        component: &JObject,
+    ) -> jni::errors::Result<(f64, f64)> {
+        env.with_local_frame(2, |env| {
+            let configuration = env
+                .call_method(
+                    component,
+                    "getGraphicsConfiguration",
+                    "()Ljava/awt/GraphicsConfiguration;",
+                    &[],
+                )?
+                .l()?;
+            if configuration.is_null() {
+                return Ok((1.0, 1.0));
+            }
+            let transform = env
+                .call_method(
+                    &configuration,
+                    "getDefaultTransform",
+                    "()Ljava/awt/geom/AffineTransform;",
+                    &[],
+                )?
+                .l()?;
+            transform_scale(env, &transform)
+        })
+    }
+}
+
+/// Calls `getScaleX()` and `getScaleY()` of `transform`, a `java.awt.geom.AffineTransform`,
+/// replacing scales that are not positive with `1.0`.
+fn transform_scale(env: &mut JNIEnv, transform: &JObject) -> jni::errors::Result<(f64, f64)> {
+    let scale_x = env.call_method(transform, "getScaleX", "()D", &[])?.d()?;
+    let scale_y = env.call_method(transform, "getScaleY", "()D", &[])?.d()?;
+    let valid = |scale: f64| if scale > 0.0 { scale } else { 1.0 };
+    Ok((valid(scale_x), valid(scale_y)))
+}
+
+pub(crate) fn to_physical(length: i32, scale: f64) -> i32 {
+    (f64::from(length) * scale).ceil() as i32
+}
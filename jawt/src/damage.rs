@@ -0,0 +1,194 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [DamageRegion] and [DamageTracker].
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::JNIEnv;
+
+use crate::dsi::DrawingSurfaceInfo;
+use crate::rect::Rect;
+
+/// A set of dirty rectangles. Rectangles are merged as they are added whenever their union covers
+/// no more pixels than the two of them do, so overlapping and adjacent damage becomes one
+/// rectangle while distant damage stays apart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DamageRegion {
+    rects: Vec<Rect>,
+}
+
+impl DamageRegion {
+    /// Most rectangles a region keeps. Adding more collapses the region into its bounds.
+    pub const MAX_RECTS: usize = 16;
+
+    pub const fn new() -> Self {
+        Self { rects: Vec::new() }
+    }
+
+    /// Returns `true` if nothing is damaged.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The damaged rectangles, none of which is empty.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// The smallest rectangle containing all damage, or [None] if nothing is damaged.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.rects
+            .iter()
+            .copied()
+            .reduce(|bounds, rect| bounds.union(&rect))
+    }
+
+    /// Marks `rect` as damaged. Empty rectangles are ignored.
+    pub fn add(&mut self, mut rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        while let Some(index) = self.rects.iter().position(|other| {
+            let union = other.union(&rect);
+            union.area() <= other.area() + rect.area()
+        }) {
+            rect = self.rects.swap_remove(index).union(&rect);
+        }
+        self.rects.push(rect);
+        if self.rects.len() > Self::MAX_RECTS {
+            let bounds = self.bounds();
+            self.rects.clear();
+            self.rects.extend(bounds);
+        }
+    }
+
+    /// Marks every rectangle of `other` as damaged.
+    pub fn extend(&mut self, other: &DamageRegion) {
+        for rect in &other.rects {
+            self.add(*rect);
+        }
+    }
+
+    /// Returns the damage inside `clip`, a set of rectangles in the same coordinate space such as
+    /// the clip of a drawing surface. The returned rectangles may overlap if `clip` does.
+    pub fn clip(&self, clip: &[Rect]) -> Vec<Rect> {
+        self.rects
+            .iter()
+            .flat_map(|rect| clip.iter().filter_map(|clip| rect.intersection(clip)))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+}
+
+/// Collects damage of a `java.awt.Component` rendered from Rust, and turns it into repaint
+/// requests and into the list of rectangles a renderer has to redraw.
+///
+/// Rectangles are in component coordinates, whose origin is the top-left corner of the component.
+/// A tracker can be shared between threads:
+///
+/// 1. When Rust state changes, call [DamageTracker::add()] and [DamageTracker::request_repaint()]
+///    from any thread. AWT coalesces the requests and eventually calls `paint()`.
+/// 2. In `paint()`, add the clip bounds of the `Graphics`, which also cover damage AWT found on
+///    its own such as exposed areas.
+/// 3. While the drawing surface is locked, take the damage with [DamageTracker::take()] and
+///    redraw only those rectangles.
+pub struct DamageTracker {
+    component: GlobalRef,
+    region: Mutex<DamageRegion>,
+}
+
+impl fmt::Debug for DamageTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DamageTracker")
+            .field("component", &self.component.as_obj())
+            .field("region", &*self.region())
+            .finish()
+    }
+}
+
+impl DamageTracker {
+    /// Creates a tracker for `component`, which must be a `java.awt.Component`.
+    pub fn new(env: &JNIEnv, component: &JObject) -> jni::errors::Result<Self> {
+        Ok(Self {
+            component: env.new_global_ref(component)?,
+            region: Mutex::new(DamageRegion::new()),
+        })
+    }
+
+    /// The component damage is tracked for.
+    pub fn component(&self) -> &JObject<'static> {
+        self.component.as_obj()
+    }
+
+    fn region(&self) -> MutexGuard<'_, DamageRegion> {
+        self.region
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Marks `rect` as damaged.
+    pub fn add(&self, rect: Rect) {
+        self.region().add(rect);
+    }
+
+    /// Marks the whole component as damaged, given its current `width` and `height`.
+    pub fn add_all(&self, width: i32, height: i32) {
+        self.add(Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+    }
+
+    /// Returns a copy of the damage collected so far.
+    pub fn damage(&self) -> DamageRegion {
+        self.region().clone()
+    }
+
+    /// Calls `repaint(x, y, width, height)` of the component with the bounds of the damage
+    /// collected so far, and returns them. Does nothing and returns [None] if nothing is damaged.
+    /// The damage is kept until [DamageTracker::take()] is called.
+    pub fn request_repaint(&self, env: &mut JNIEnv) -> jni::errors::Result<Option<Rect>> {
+        let Some(bounds) = self.region().bounds() else {
+            return Ok(None);
+        };
+        env.call_method(
+            &self.component,
+            "repaint",
+            "(IIII)V",
+            &[
+                JValue::Int(bounds.x),
+                JValue::Int(bounds.y),
+                JValue::Int(bounds.width),
+                JValue::Int(bounds.height),
+            ],
+        )?;
+        Ok(Some(bounds))
+    }
+
+    /// Removes and returns all damage collected so far, for renderers that keep their own back
+    /// buffer such as [LightweightSurface](crate::LightweightSurface).
+    pub fn take_all(&self) -> DamageRegion {
+        std::mem::take(&mut *self.region())
+    }
+
+    /// Removes the damage collected so far and returns the part of it inside the clip of `info`,
+    /// in component coordinates. Returns no damage if the clip is empty, as nothing of the surface
+    /// is visible. Call this while the drawing surface of the component is locked.
+    pub fn take(&self, info: &DrawingSurfaceInfo<'_>) -> Vec<Rect> {
+        let region = self.take_all();
+        let bounds = info.bounds();
+        let clip: Vec<Rect> = info
+            .clip()
+            .iter()
+            .map(|rect| rect.translate(-bounds.x, -bounds.y))
+            .collect();
+        region.clip(&clip)
+    }
+}
@@ -4,6 +4,7 @@
 
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::ptr::NonNull;
 
 use jawt_sys::*;
@@ -75,7 +76,8 @@ impl DrawingSurface {
     /// Destructs [DrawingSurface] into a raw [JAWT_DrawingSurface] and a pointer to its destroying
     /// function.
     pub fn into_raw_parts(self) -> (NonNull<JAWT_DrawingSurface>, DrawingSurfaceFree) {
-        (self.inner, self.free)
+        let this = ManuallyDrop::new(self);
+        (this.inner, this.free)
     }
 
     /// Lock the surface of the target component for native rendering.
@@ -2,14 +2,18 @@
 
 //! Implements the [DrawingSurfaceInfo] struct.
 
+use std::error::Error;
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::ptr::NonNull;
 use std::slice;
 
 use jawt_sys::*;
 
+use crate::awt::Awt;
 use crate::ds::DrawingSurfaceGuard;
+use crate::version::AwtVersion;
 use crate::Rect;
 
 type DrawingSurfaceInfoFree = unsafe extern "C" fn(dsi: *mut JAWT_DrawingSurfaceInfo);
@@ -28,6 +32,66 @@ pub type DrawingSurfacePlatformInfo =
 ))]
 pub type DrawingSurfacePlatformInfo = crate::md::unix::X11DrawingSurfaceInfo;
 
+/// Error returned when the platform information of a [DrawingSurfaceInfo] can't be used.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlatformInfoError {
+    /// `platformInfo` is null.
+    Null,
+    /// The toolkit that created the drawing surface, and therefore the layout of its platform
+    /// information, could not be determined.
+    UnknownToolkit,
+    /// The platform information does not have the layout of its toolkit, for example because a
+    /// required handle is null.
+    InvalidLayout,
+    /// The platform information was created by a toolkit [DrawingSurfacePlatformInfo] does not
+    /// describe, such as the Wayland toolkit on Unix-like systems.
+    OtherToolkit,
+}
+
+impl fmt::Display for PlatformInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlatformInfoError::Null => write!(f, "the platform information is null"),
+            PlatformInfoError::UnknownToolkit => {
+                write!(f, "the toolkit of the drawing surface is unknown")
+            }
+            PlatformInfoError::InvalidLayout => {
+                write!(f, "the platform information has an unexpected layout")
+            }
+            PlatformInfoError::OtherToolkit => {
+                write!(f, "the platform information was created by another toolkit")
+            }
+        }
+    }
+}
+
+impl Error for PlatformInfoError {}
+
+/// Platform information checked by [DrawingSurfaceInfo::try_platform_info_for()]. The accessors
+/// of fields every JAWT version has are forwarded, and fields introduced in later JAWT versions
+/// are only accessible through it if the [Awt] it was checked against has at least that version.
+#[derive(Debug, Clone, Copy)]
+pub struct VersionedPlatformInfo<'a> {
+    info: &'a DrawingSurfacePlatformInfo,
+    version: AwtVersion,
+}
+
+impl<'a> VersionedPlatformInfo<'a> {
+    /// Wraps `info`, which was returned by a JAWT of `version`.
+    pub const fn new(info: &'a DrawingSurfacePlatformInfo, version: AwtVersion) -> Self {
+        Self { info, version }
+    }
+
+    /// The version of the [Awt] the information was checked against.
+    pub const fn version(&self) -> AwtVersion {
+        self.version
+    }
+
+    pub(crate) const fn inner(&self) -> &'a DrawingSurfacePlatformInfo {
+        self.info
+    }
+}
+
 /// Structure for containing the underlying drawing information of a component.
 pub struct DrawingSurfaceInfo<'a> {
     pub(crate) inner: NonNull<JAWT_DrawingSurfaceInfo>,
@@ -81,14 +145,65 @@ impl DrawingSurfaceInfo<'_> {
     /// Destructs [DrawingSurfaceInfo] into a raw [JAWT_DrawingSurfaceInfo] and a pointer to its destroying
     /// function.
     pub fn into_raw_parts(self) -> (NonNull<JAWT_DrawingSurfaceInfo>, DrawingSurfaceInfoFree) {
-        (self.inner, self.free)
+        let this = ManuallyDrop::new(self);
+        (this.inner, this.free)
     }
 
     /// Pointer to the platform-specific information.
+    ///
+    /// The pointer is not checked. On Unix-like systems, the information is always interpreted as
+    /// X11. Prefer [DrawingSurfaceInfo::try_platform_info()].
     pub const fn platform_info(&self) -> &DrawingSurfacePlatformInfo {
         unsafe { &*(self.as_ref().platformInfo as *const DrawingSurfacePlatformInfo) }
     }
 
+    /// The platform-specific information, or an error if it is null or does not have the layout
+    /// of [DrawingSurfacePlatformInfo]. On Unix-like systems, the active toolkit is detected with
+    /// `UnixToolkit::detect()` and must be X11.
+    pub fn try_platform_info(&self) -> Result<&DrawingSurfacePlatformInfo, PlatformInfoError> {
+        let platform_info = self.as_ref().platformInfo;
+        if platform_info.is_null() {
+            return Err(PlatformInfoError::Null);
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let info = self.platform_info();
+            if info.0.hdc.is_null() {
+                return Err(PlatformInfoError::InvalidLayout);
+            }
+            Ok(info)
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.platform_info())
+        }
+
+        #[cfg(all(
+            target_family = "unix",
+            not(target_vendor = "apple"),
+            not(target_os = "android")
+        ))]
+        {
+            self.unix_platform_info()?
+                .x11()
+                .ok_or(PlatformInfoError::OtherToolkit)
+        }
+    }
+
+    /// [DrawingSurfaceInfo::try_platform_info()] for a drawing surface obtained from `awt`. Fields
+    /// of the returned information are only exposed if the version of `awt` supports them.
+    pub fn try_platform_info_for(
+        &self,
+        awt: &Awt,
+    ) -> Result<VersionedPlatformInfo<'_>, PlatformInfoError> {
+        Ok(VersionedPlatformInfo::new(
+            self.try_platform_info()?,
+            awt.version(),
+        ))
+    }
+
     /// Bounding rectangle of the drawing surface.
     pub const fn bounds(&self) -> Rect {
         let bounds = &self.as_ref().bounds;
@@ -102,6 +217,10 @@ impl DrawingSurfaceInfo<'_> {
 
     /// Clip rectangle array.
     pub const fn clip(&self) -> &[Rect] {
+        // The array may be null when it is empty.
+        if self.as_ref().clipSize <= 0 {
+            return &[];
+        }
         unsafe {
             slice::from_raw_parts(
                 self.as_ref().clip as *const Rect,
@@ -110,3 +229,9 @@ impl DrawingSurfaceInfo<'_> {
         }
     }
 }
+
+impl Drop for DrawingSurfaceInfo<'_> {
+    fn drop(&mut self) {
+        unsafe { (self.free)(self.inner.as_ptr()) };
+    }
+}
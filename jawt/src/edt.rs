@@ -0,0 +1,304 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Runs Rust closures on the AWT Event Dispatch Thread (EDT).
+//!
+//! Most of AWT is not thread-safe, and operations such as moving embedded frames, registering
+//! listeners or calling `repaint()` must happen on the EDT. [invoke_later()] and
+//! [invoke_and_wait()] wrap a closure in a `java.lang.Runnable` implemented natively and post it
+//! with [java.awt.EventQueue]. The `Runnable` class is defined on first use in a class loader of
+//! its own, so several libraries embedding `jawt` can share a JVM.
+//!
+//! [java.awt.EventQueue]: https://docs.oracle.com/en/java/javase/17/docs/api/java.desktop/java/awt/EventQueue.html
+
+use std::error::Error;
+use std::ffi::c_void;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+use jni::objects::{GlobalRef, JClass, JObject, JValue};
+use jni::sys::jlong;
+use jni::{JNIEnv, NativeMethod};
+use once_cell::sync::OnceCell;
+
+const EVENT_QUEUE: &str = "java/awt/EventQueue";
+const NATIVE_RUNNABLE: &str = "dev/gobley/jawt/NativeRunnable";
+/// Compiled from `edt/NativeRunnable.java`.
+const NATIVE_RUNNABLE_CLASS: &[u8] = include_bytes!("edt/NativeRunnable.class");
+
+/// Error returned by the functions of [jawt::edt](self).
+#[derive(Debug)]
+pub enum EdtError {
+    /// A JNI call failed without throwing a Java exception.
+    Jni(jni::errors::Error),
+    /// A Java exception was thrown, for example an `InterruptedException` while waiting in
+    /// [invoke_and_wait()]. Contains the result of `Throwable.toString()`. The exception is
+    /// cleared.
+    Java(String),
+}
+
+impl fmt::Display for EdtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdtError::Jni(error) => write!(f, "JNI error: {error}"),
+            EdtError::Java(message) => write!(f, "a Java exception was thrown: {message}"),
+        }
+    }
+}
+
+impl Error for EdtError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EdtError::Jni(error) => Some(error),
+            EdtError::Java(_) => None,
+        }
+    }
+}
+
+/// Returns `true` if the current thread is the EDT.
+pub fn is_dispatch_thread(env: &mut JNIEnv) -> Result<bool, EdtError> {
+    call(env, |env| {
+        env.call_static_method(EVENT_QUEUE, "isDispatchThread", "()Z", &[])?
+            .z()
+    })
+}
+
+/// Panics if the current thread is not the EDT, or if that can't be determined.
+#[track_caller]
+pub fn assert_dispatch_thread(env: &mut JNIEnv) {
+    match is_dispatch_thread(env) {
+        Ok(true) => {}
+        Ok(false) => panic!("must be called on the AWT Event Dispatch Thread"),
+        Err(error) => panic!("failed to check for the AWT Event Dispatch Thread: {error}"),
+    }
+}
+
+/// [assert_dispatch_thread()] for debug builds, callable with the shared [JNIEnv] the rest of
+/// `jawt` takes.
+#[track_caller]
+pub(crate) fn debug_assert_dispatch_thread(env: &JNIEnv) {
+    if cfg!(debug_assertions) {
+        // Safety: no local reference outlives this call.
+        assert_dispatch_thread(&mut unsafe { env.unsafe_clone() });
+    }
+}
+
+/// Posts `f` to the EDT with `EventQueue.invokeLater()` and returns without waiting for it. The
+/// result of `f`, or the panic it raised, can be retrieved through the returned [EdtTask].
+pub fn invoke_later<F, R>(env: &mut JNIEnv, f: F) -> Result<EdtTask<R>, EdtError>
+where
+    F: FnOnce(&mut JNIEnv) -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let task = EdtTask::new();
+    post(env, "invokeLater", task.wrap(f))?;
+    Ok(task)
+}
+
+/// Runs `f` on the EDT with `EventQueue.invokeAndWait()` and returns its result. If `f` panics,
+/// the panic is resumed on the current thread. If the current thread is the EDT, `f` is called
+/// directly.
+///
+/// A Java exception left pending by `f` is returned as [EdtError::Java].
+pub fn invoke_and_wait<F, R>(env: &mut JNIEnv, f: F) -> Result<R, EdtError>
+where
+    F: FnOnce(&mut JNIEnv) -> R + Send + 'static,
+    R: Send + 'static,
+{
+    if is_dispatch_thread(env)? {
+        return Ok(f(env));
+    }
+    let task = EdtTask::new();
+    post(env, "invokeAndWait", task.wrap(f))?;
+    match task.join() {
+        Ok(result) => Ok(result),
+        Err(panic) => panic::resume_unwind(panic),
+    }
+}
+
+/// The result of a closure posted with [invoke_later()].
+pub struct EdtTask<R> {
+    slot: Arc<Slot<R>>,
+}
+
+impl<R> fmt::Debug for EdtTask<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EdtTask")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+struct Slot<R> {
+    result: Mutex<Option<thread::Result<R>>>,
+    condvar: Condvar,
+}
+
+type Task = Box<dyn FnOnce(&mut JNIEnv) + Send>;
+
+impl<R: Send + 'static> EdtTask<R> {
+    fn new() -> Self {
+        Self {
+            slot: Arc::new(Slot {
+                result: Mutex::new(None),
+                condvar: Condvar::new(),
+            }),
+        }
+    }
+
+    fn wrap<F>(&self, f: F) -> Task
+    where
+        F: FnOnce(&mut JNIEnv) -> R + Send + 'static,
+    {
+        let slot = self.slot.clone();
+        Box::new(move |env| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(env)));
+            *slot
+                .result
+                .lock()
+                .unwrap_or_else(|error| error.into_inner()) = Some(result);
+            slot.condvar.notify_all();
+        })
+    }
+}
+
+impl<R> EdtTask<R> {
+    /// Returns `true` if the closure has returned or panicked.
+    pub fn is_finished(&self) -> bool {
+        self.slot
+            .result
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .is_some()
+    }
+
+    /// Waits for the closure to return, and returns its result or the payload of its panic.
+    ///
+    /// Calling this on the EDT deadlocks, as the closure can't run until the current event
+    /// returns.
+    pub fn join(self) -> thread::Result<R> {
+        let mut result = self
+            .slot
+            .result
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        loop {
+            if let Some(result) = result.take() {
+                return result;
+            }
+            result = self
+                .slot
+                .condvar
+                .wait(result)
+                .unwrap_or_else(|error| error.into_inner());
+        }
+    }
+}
+
+/// A task shared by its poster and its `NativeRunnable`. Whichever takes it first runs or cancels
+/// it.
+type TaskCell = Mutex<Option<Task>>;
+
+/// Wraps `task` in a `NativeRunnable` and passes it to the static `EventQueue` method `method`.
+fn post(env: &mut JNIEnv, method: &str, task: Task) -> Result<(), EdtError> {
+    let class = native_runnable_class(env)?;
+    let cell = Arc::new(Mutex::new(Some(task)));
+    let posted = call(env, |env| {
+        env.with_local_frame(4, |env| -> jni::errors::Result<()> {
+            // Released by `run()` of the runnable once it is created.
+            let handle = Arc::into_raw(cell.clone());
+            let runnable = env
+                .new_object(class, "(J)V", &[JValue::Long(handle as usize as jlong)])
+                .map_err(|error| {
+                    // Safety: no runnable owns the handle.
+                    drop(unsafe { Arc::from_raw(handle) });
+                    error
+                })?;
+            env.call_static_method(
+                EVENT_QUEUE,
+                method,
+                "(Ljava/lang/Runnable;)V",
+                &[JValue::Object(&runnable)],
+            )?;
+            Ok(())
+        })
+    });
+    if posted.is_err() {
+        // The runnable may run later or never; cancel the task either way.
+        cell.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .take();
+    }
+    posted
+}
+
+/// Returns the `NativeRunnable` class, defining it and registering its native method first if
+/// needed.
+fn native_runnable_class(env: &mut JNIEnv) -> Result<&'static GlobalRef, EdtError> {
+    static CLASS: OnceCell<GlobalRef> = OnceCell::new();
+
+    CLASS.get_or_try_init(|| {
+        call(env, |env| {
+            env.with_local_frame(4, |env| {
+                // A class loader of its own, whose parent is the bootstrap class loader.
+                let urls = env.new_object_array(0, "java/net/URL", JObject::null())?;
+                let loader = env.new_object(
+                    "java/net/URLClassLoader",
+                    "([Ljava/net/URL;Ljava/lang/ClassLoader;)V",
+                    &[JValue::Object(&urls), JValue::Object(&JObject::null())],
+                )?;
+                let class = env.define_class(NATIVE_RUNNABLE, &loader, NATIVE_RUNNABLE_CLASS)?;
+                env.register_native_methods(
+                    &class,
+                    &[NativeMethod {
+                        name: "run".into(),
+                        sig: "(J)V".into(),
+                        fn_ptr: run as *mut c_void,
+                    }],
+                )?;
+                env.new_global_ref(class)
+            })
+        })
+    })
+}
+
+/// `NativeRunnable.run(long)`.
+extern "system" fn run(mut env: JNIEnv, _class: JClass, handle: jlong) {
+    // Safety: `handle` was created by `post()` and is passed to `run()` at most once.
+    let cell = unsafe { Arc::from_raw(handle as usize as *const TaskCell) };
+    let task = cell
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .take();
+    if let Some(task) = task {
+        // Tasks catch panics themselves, but nothing may unwind into the JVM.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| task(&mut env)));
+    }
+}
+
+/// Runs a JNI call, converting and clearing the pending Java exception if it throws one.
+fn call<'local, T>(
+    env: &mut JNIEnv<'local>,
+    f: impl FnOnce(&mut JNIEnv<'local>) -> jni::errors::Result<T>,
+) -> Result<T, EdtError> {
+    match f(env) {
+        Ok(value) => Ok(value),
+        Err(jni::errors::Error::JavaException) => Err(EdtError::Java(describe_exception(env))),
+        Err(error) => Err(EdtError::Jni(error)),
+    }
+}
+
+fn describe_exception(env: &mut JNIEnv) -> String {
+    let Ok(throwable) = env.exception_occurred() else {
+        return "unknown exception".to_string();
+    };
+    let _ = env.exception_clear();
+    let description = env
+        .call_method(&throwable, "toString", "()Ljava/lang/String;", &[])
+        .and_then(|description| description.l())
+        .and_then(|description| env.get_string(&description.into()).map(String::from));
+    let _ = env.exception_clear();
+    let _ = env.delete_local_ref(throwable);
+    description.unwrap_or_else(|_| "unknown exception".to_string())
+}
@@ -0,0 +1,34 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+// The compiled class is embedded in `jawt` by `edt.rs`. After editing this file, regenerate
+// `NativeRunnable.class` with JDK 17 from the repository root with:
+//
+//     javac --release 8 -d target/edt jawt/src/edt/NativeRunnable.java
+//     cp target/edt/dev/gobley/jawt/NativeRunnable.class jawt/src/edt/
+//
+// CI compiles this file the same way and fails if the result differs from the committed class.
+
+package dev.gobley.jawt;
+
+/**
+ * A {@link Runnable} running a Rust closure. Instances are created and posted to the
+ * {@link java.awt.EventQueue} by {@code jawt::edt}.
+ */
+final class NativeRunnable implements Runnable {
+    private long handle;
+
+    private NativeRunnable(long handle) {
+        this.handle = handle;
+    }
+
+    @Override
+    public void run() {
+        long handle = this.handle;
+        this.handle = 0;
+        if (handle != 0) {
+            run(handle);
+        }
+    }
+
+    private static native void run(long handle);
+}
@@ -0,0 +1,258 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Detects whether AWT can render natively before any component is created.
+//!
+//! [Awt::from_version()](crate::Awt::from_version()) and
+//! [Awt::drawing_surface()](crate::Awt::drawing_surface()) return [None] without a reason when AWT
+//! is headless, the runtime lacks the `java.desktop` module or the toolkit has no display to
+//! connect to. [probe()] reports these up front, so an application can choose between a native
+//! Canvas, a [LightweightSurface](crate::LightweightSurface) or no AWT at all.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+use jni::objects::{JObject, JString, JValue};
+use jni::JNIEnv;
+
+/// The `java.awt.Toolkit` implementation of a JVM.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Toolkit {
+    /// `sun.awt.X11.XToolkit`, used on Linux and other Unix-like systems.
+    X11,
+    /// `sun.awt.windows.WToolkit`.
+    Windows,
+    /// `sun.lwawt.macosx.LWCToolkit`.
+    MacOS,
+    /// `sun.awt.wl.WLToolkit`, the native Wayland toolkit of newer JDKs.
+    Wayland,
+    /// `sun.awt.HeadlessToolkit`, used when AWT is headless.
+    Headless,
+    /// Any other toolkit. Contains the name of its class.
+    Other(String),
+}
+
+impl Toolkit {
+    /// Returns the toolkit implemented by the class named `name`, such as
+    /// `"sun.awt.X11.XToolkit"`.
+    pub fn from_class_name(name: &str) -> Self {
+        match name {
+            "sun.awt.X11.XToolkit" => Toolkit::X11,
+            "sun.awt.windows.WToolkit" => Toolkit::Windows,
+            "sun.lwawt.macosx.LWCToolkit" => Toolkit::MacOS,
+            "sun.awt.wl.WLToolkit" => Toolkit::Wayland,
+            "sun.awt.HeadlessToolkit" => Toolkit::Headless,
+            _ => Toolkit::Other(name.to_string()),
+        }
+    }
+}
+
+/// Why [Awt::drawing_surface()](crate::Awt::drawing_surface()) can't be expected to work. See
+/// [Environment::drawing_surface_support()].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Unsupported {
+    /// The runtime lacks the `java.desktop` module, for example because it was linked with
+    /// `jlink` without it.
+    MissingDesktopModule,
+    /// AWT is headless, for example because `java.awt.headless` is `true`.
+    Headless,
+    /// The toolkit failed to load. Contains the result of `Throwable.toString()`.
+    ToolkitUnavailable(String),
+    /// The X11 toolkit is active but `DISPLAY` is not set, or the Wayland toolkit is active but
+    /// `WAYLAND_DISPLAY` is not set.
+    NoDisplay,
+    /// JAWT has no platform information for surfaces of this toolkit.
+    UnsupportedToolkit(Toolkit),
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unsupported::MissingDesktopModule => {
+                write!(f, "the runtime lacks the java.desktop module")
+            }
+            Unsupported::Headless => write!(f, "AWT is headless"),
+            Unsupported::ToolkitUnavailable(message) => {
+                write!(f, "the AWT toolkit failed to load: {message}")
+            }
+            Unsupported::NoDisplay => write!(f, "no display is available for the toolkit"),
+            Unsupported::UnsupportedToolkit(toolkit) => {
+                write!(f, "drawing surfaces of {toolkit:?} are not supported")
+            }
+        }
+    }
+}
+
+impl Error for Unsupported {}
+
+/// What [probe()] found out about the JVM and the process.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Environment {
+    /// `true` if `java.awt.GraphicsEnvironment.isHeadless()` returns `true`.
+    pub headless: bool,
+    /// `true` if the runtime has the `java.desktop` module, which contains AWT.
+    pub desktop_module: bool,
+    /// The active toolkit, or [None] if the runtime has no AWT or the toolkit failed to load.
+    pub toolkit: Option<Toolkit>,
+    /// The exception thrown while loading the toolkit, as returned by `Throwable.toString()`.
+    pub toolkit_error: Option<String>,
+    /// The `java.version` system property.
+    pub java_version: Option<String>,
+    /// The `java.vendor` system property.
+    pub java_vendor: Option<String>,
+    /// `true` if the `DISPLAY` environment variable is set and not empty.
+    pub display: bool,
+    /// `true` if the `WAYLAND_DISPLAY` environment variable is set and not empty.
+    pub wayland_display: bool,
+}
+
+impl Environment {
+    /// Returns `Ok(())` if [Awt::drawing_surface()](crate::Awt::drawing_surface()) can be
+    /// expected to return drawing surfaces with platform information for heavyweight components,
+    /// or the reason it can't.
+    pub fn drawing_surface_support(&self) -> Result<(), Unsupported> {
+        if !self.desktop_module {
+            return Err(Unsupported::MissingDesktopModule);
+        }
+        if self.headless {
+            return Err(Unsupported::Headless);
+        }
+        if let Some(message) = &self.toolkit_error {
+            return Err(Unsupported::ToolkitUnavailable(message.clone()));
+        }
+        match &self.toolkit {
+            Some(Toolkit::Windows | Toolkit::MacOS) => Ok(()),
+            Some(Toolkit::X11) if self.display => Ok(()),
+            Some(Toolkit::Wayland) if self.wayland_display => Ok(()),
+            Some(Toolkit::X11 | Toolkit::Wayland) => Err(Unsupported::NoDisplay),
+            Some(Toolkit::Headless) => Err(Unsupported::Headless),
+            Some(toolkit) => Err(Unsupported::UnsupportedToolkit(toolkit.clone())),
+            None => Err(Unsupported::ToolkitUnavailable(
+                "no toolkit was loaded".to_string(),
+            )),
+        }
+    }
+
+    /// Returns `true` if [Environment::drawing_surface_support()] returns `Ok(())`.
+    pub fn drawing_surface_expected(&self) -> bool {
+        self.drawing_surface_support().is_ok()
+    }
+}
+
+/// Inspects the JVM of `env` and the environment variables of the process.
+///
+/// Unless the runtime lacks AWT, this loads the default toolkit with `Toolkit.getDefaultToolkit()`,
+/// which AWT does anyway when the first component is created.
+/// Java exceptions thrown while probing are cleared and reported in the returned [Environment].
+pub fn probe(env: &mut JNIEnv) -> jni::errors::Result<Environment> {
+    let java_version = system_property(env, "java.version")?;
+    let java_vendor = system_property(env, "java.vendor")?;
+    let display = env_var_set("DISPLAY");
+    let wayland_display = env_var_set("WAYLAND_DISPLAY");
+    let mut environment = Environment {
+        headless: false,
+        desktop_module: false,
+        toolkit: None,
+        toolkit_error: None,
+        java_version,
+        java_vendor,
+        display,
+        wayland_display,
+    };
+
+    match catch(env, |env| env.find_class("java/awt/GraphicsEnvironment"))? {
+        Ok(class) => env.delete_local_ref(class)?,
+        Err(_) => return Ok(environment),
+    }
+    environment.desktop_module = true;
+
+    environment.headless = match catch(env, |env| {
+        env.call_static_method("java/awt/GraphicsEnvironment", "isHeadless", "()Z", &[])?
+            .z()
+    })? {
+        Ok(headless) => headless,
+        Err(message) => {
+            environment.toolkit_error = Some(message);
+            return Ok(environment);
+        }
+    };
+
+    match catch(env, toolkit_class_name)? {
+        Ok(name) => environment.toolkit = Some(Toolkit::from_class_name(&name)),
+        Err(message) => environment.toolkit_error = Some(message),
+    }
+    Ok(environment)
+}
+
+/// Returns the class name of `Toolkit.getDefaultToolkit()`.
+fn toolkit_class_name(env: &mut JNIEnv) -> jni::errors::Result<String> {
+    env.with_local_frame(4, |env| {
+        let toolkit = env
+            .call_static_method(
+                "java/awt/Toolkit",
+                "getDefaultToolkit",
+                "()Ljava/awt/Toolkit;",
+                &[],
+            )?
+            .l()?;
+        let class = env.get_object_class(&toolkit)?;
+        let name = env
+            .call_method(&class, "getName", "()Ljava/lang/String;", &[])?
+            .l()?;
+        Ok(env.get_string(&JString::from(name))?.into())
+    })
+}
+
+fn system_property(env: &mut JNIEnv, key: &str) -> jni::errors::Result<Option<String>> {
+    let value = catch(env, |env| {
+        env.with_local_frame(4, |env| {
+            let key = env.new_string(key)?;
+            let value = env
+                .call_static_method(
+                    "java/lang/System",
+                    "getProperty",
+                    "(Ljava/lang/String;)Ljava/lang/String;",
+                    &[JValue::Object(&key)],
+                )?
+                .l()?;
+            if value.is_null() {
+                return Ok(None);
+            }
+            Ok(Some(env.get_string(&JString::from(value))?.into()))
+        })
+    })?;
+    // A `SecurityException` means the property is not readable.
+    Ok(value.unwrap_or(None))
+}
+
+fn env_var_set(name: &str) -> bool {
+    env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+/// Runs a JNI call, returning the result of `Throwable.toString()` and clearing the exception if
+/// it throws one.
+fn catch<'local, T>(
+    env: &mut JNIEnv<'local>,
+    f: impl FnOnce(&mut JNIEnv<'local>) -> jni::errors::Result<T>,
+) -> jni::errors::Result<Result<T, String>> {
+    match f(env) {
+        Ok(value) => Ok(Ok(value)),
+        Err(jni::errors::Error::JavaException) => Ok(Err(describe_exception(env)?)),
+        Err(error) => Err(error),
+    }
+}
+
+fn describe_exception(env: &mut JNIEnv) -> jni::errors::Result<String> {
+    let throwable = env.exception_occurred()?;
+    env.exception_clear()?;
+    let description = env
+        .call_method(&throwable, "toString", "()Ljava/lang/String;", &[])
+        .and_then(|description| description.l())
+        .and_then(|description| {
+            env.get_string(&JString::from(description))
+                .map(String::from)
+        });
+    env.exception_clear()?;
+    env.delete_local_ref(JObject::from(throwable))?;
+    Ok(description.unwrap_or_else(|_| "unknown exception".to_string()))
+}
@@ -0,0 +1,285 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [RgbaImage] and [diff_images], used to compare pixels read back from a drawing
+//! surface against expected output.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::dsi::DrawingSurfaceInfo;
+use crate::Rect;
+
+/// An 8-bit RGBA image stored row by row without padding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates an image filled with transparent black.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Wraps `pixels` as an image. Returns [None] if the length of `pixels` is not
+    /// `width * height * 4`.
+    pub fn from_raw(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        (pixels.len() == width as usize * height as usize * 4).then_some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    pub fn into_raw(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    fn offset(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height)
+            .then(|| (y as usize * self.width as usize + x as usize) * 4)
+    }
+
+    /// Returns the pixel at (`x`, `y`), or [None] if it is out of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        let offset = self.offset(x, y)?;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[offset..offset + 4]);
+        Some(pixel)
+    }
+
+    /// Overwrites the pixel at (`x`, `y`). Pixels out of the image are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, pixel: [u8; 4]) {
+        if let Some(offset) = self.offset(x, y) {
+            self.pixels[offset..offset + 4].copy_from_slice(&pixel);
+        }
+    }
+}
+
+/// Selects which pixels of an image take part in [diff_images].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImageMask {
+    width: u32,
+    height: u32,
+    included: Vec<bool>,
+}
+
+impl ImageMask {
+    /// Creates a mask including every pixel if `included` is `true`, or none otherwise.
+    pub fn new(width: u32, height: u32, included: bool) -> Self {
+        Self {
+            width,
+            height,
+            included: vec![included; width as usize * height as usize],
+        }
+    }
+
+    /// Creates a mask including only the pixels inside `rects`.
+    pub fn from_rects(width: u32, height: u32, rects: &[Rect]) -> Self {
+        let mut mask = Self::new(width, height, false);
+        for rect in rects {
+            mask.set_rect(*rect, true);
+        }
+        mask
+    }
+
+    /// Creates a mask matching [DrawingSurfaceInfo::bounds()] in size that includes the pixels
+    /// inside [DrawingSurfaceInfo::clip()], which is the region read-back APIs fill in.
+    pub fn from_clip(dsi: &DrawingSurfaceInfo) -> Self {
+        let bounds = dsi.bounds();
+        let rects = dsi
+            .clip()
+            .iter()
+            .map(|clip| clip.translate(-bounds.x, -bounds.y))
+            .collect::<Vec<_>>();
+        Self::from_rects(
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+            &rects,
+        )
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns whether the pixel at (`x`, `y`) is included. Pixels out of the mask are not.
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x < self.width
+            && y < self.height
+            && self.included[y as usize * self.width as usize + x as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, included: bool) {
+        if x < self.width && y < self.height {
+            self.included[y as usize * self.width as usize + x as usize] = included;
+        }
+    }
+
+    /// Includes or excludes every pixel inside `rect`.
+    pub fn set_rect(&mut self, rect: Rect, included: bool) {
+        let extent = Rect {
+            x: 0,
+            y: 0,
+            width: i32::try_from(self.width).unwrap_or(i32::MAX),
+            height: i32::try_from(self.height).unwrap_or(i32::MAX),
+        };
+        let Some(rect) = rect.intersection(&extent) else {
+            return;
+        };
+        for y in rect.y..rect.y + rect.height {
+            for x in rect.x..rect.x + rect.width {
+                self.set(x as u32, y as u32, included);
+            }
+        }
+    }
+}
+
+/// Options of [diff_images].
+#[derive(Debug, Clone, Copy)]
+pub struct ImageDiffOptions<'a> {
+    /// Largest per-channel difference still considered equal. Absorbs rounding differences
+    /// between renderers.
+    pub tolerance: u8,
+    /// Pixels to compare. Every pixel is compared when [None].
+    pub mask: Option<&'a ImageMask>,
+    /// Whether the alpha channel is compared. Read-back windows are usually opaque, so comparing
+    /// alpha only matters for offscreen images.
+    pub compare_alpha: bool,
+}
+
+impl Default for ImageDiffOptions<'_> {
+    fn default() -> Self {
+        Self {
+            tolerance: 0,
+            mask: None,
+            compare_alpha: true,
+        }
+    }
+}
+
+/// Result of [diff_images].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImageDiff {
+    /// Number of pixels included by the mask.
+    pub compared_pixels: u64,
+    /// Number of compared pixels with a channel differing by more than the tolerance.
+    pub mismatched_pixels: u64,
+    /// Largest per-channel difference among the compared pixels.
+    pub max_difference: u8,
+    /// Position of the first mismatched pixel in row-major order.
+    pub first_mismatch: Option<(u32, u32)>,
+    /// Mask of the mismatched pixels, useful for dumping where a comparison failed.
+    pub mismatches: ImageMask,
+}
+
+impl ImageDiff {
+    /// Returns `true` if no compared pixel differs by more than the tolerance.
+    pub fn is_match(&self) -> bool {
+        self.mismatched_pixels == 0
+    }
+}
+
+/// Error returned by [diff_images].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImageDiffError {
+    /// The images, or an image and the mask, have different sizes.
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+}
+
+impl fmt::Display for ImageDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageDiffError::SizeMismatch { expected, actual } => write!(
+                f,
+                "expected a {}x{} image, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+        }
+    }
+}
+
+impl Error for ImageDiffError {}
+
+/// Compares `actual` against `expected` pixel by pixel.
+pub fn diff_images(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    options: &ImageDiffOptions<'_>,
+) -> Result<ImageDiff, ImageDiffError> {
+    let size = (expected.width, expected.height);
+    for other in [
+        Some((actual.width, actual.height)),
+        options.mask.map(|mask| (mask.width, mask.height)),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if other != size {
+            return Err(ImageDiffError::SizeMismatch {
+                expected: size,
+                actual: other,
+            });
+        }
+    }
+
+    let channels = if options.compare_alpha { 4 } else { 3 };
+    let mut diff = ImageDiff {
+        compared_pixels: 0,
+        mismatched_pixels: 0,
+        max_difference: 0,
+        first_mismatch: None,
+        mismatches: ImageMask::new(size.0, size.1, false),
+    };
+    for y in 0..size.1 {
+        for x in 0..size.0 {
+            if options.mask.is_some_and(|mask| !mask.contains(x, y)) {
+                continue;
+            }
+            diff.compared_pixels += 1;
+            let offset = expected.offset(x, y).unwrap_or_default();
+            let difference = expected.pixels[offset..offset + channels]
+                .iter()
+                .zip(&actual.pixels[offset..offset + channels])
+                .map(|(expected, actual)| expected.abs_diff(*actual))
+                .max()
+                .unwrap_or_default();
+            diff.max_difference = diff.max_difference.max(difference);
+            if difference > options.tolerance {
+                diff.mismatched_pixels += 1;
+                diff.first_mismatch.get_or_insert((x, y));
+                diff.mismatches.set(x, y, true);
+            }
+        }
+    }
+    Ok(diff)
+}
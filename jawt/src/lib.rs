@@ -8,18 +8,41 @@
 mod awt;
 pub use awt::*;
 
+mod buffered_image;
+pub use buffered_image::*;
+
+mod damage;
+pub use damage::*;
+
 mod ds;
 pub use ds::*;
 
 mod dsi;
 pub use dsi::*;
 
+#[cfg(feature = "edt")]
+pub mod edt;
+
+pub mod environment;
+
+mod image;
+pub use image::*;
+
+mod lightweight;
+pub use lightweight::*;
+
 mod md;
 pub use md::*;
 
 mod rect;
 pub use rect::*;
 
+#[cfg(feature = "render-loop")]
+pub mod render_loop;
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
 mod version;
 pub use version::*;
 
@@ -0,0 +1,211 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Renders Rust pixels into lightweight Swing components.
+//!
+//! JAWT only works for heavyweight components, which have a native window of their own. A
+//! heavyweight Canvas is always drawn above the lightweight components of its window, so Swing
+//! popups and menus that overlap it are hidden behind it, and it can't be translucent or take part
+//! in Swing's double buffering. [LightweightSurface] renders into a `BufferedImage` instead, which
+//! `paintComponent()` of a `JComponent` draws like any other Swing content.
+//!
+//! Choose the heavyweight mode, such as a Canvas rendered with `jawt-wgpu` or the `render-loop`
+//! feature, when frames are rendered on the GPU at a high rate, as it presents without copying
+//! pixels through the JVM. Choose the lightweight mode when the view is small or mostly static,
+//! must overlap popups, menus and other Swing components, or must be translucent, and as a
+//! fallback where JAWT is unavailable.
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use jni::objects::JObject;
+use jni::JNIEnv;
+
+use crate::buffered_image::{to_physical, ScaledImage};
+use crate::damage::{DamageRegion, DamageTracker};
+use crate::rect::Rect;
+
+/// A frame of a [LightweightSurface] passed to the render callback of
+/// [LightweightSurface::paint()].
+///
+/// Sizes and rectangles are in physical pixels: the size of the component multiplied by the
+/// scale of the `Graphics` it is painted with.
+pub struct LightweightFrame<'a> {
+    pixels: &'a mut [u32],
+    width: i32,
+    height: i32,
+    scale: (f64, f64),
+    damage: &'a [Rect],
+}
+
+impl<'a> LightweightFrame<'a> {
+    /// The pixels of the frame, row by row from the top, as premultiplied `0xAARRGGBB`. Pixels
+    /// outside [LightweightFrame::damage()] hold the previous frame.
+    pub fn pixels(&mut self) -> &mut [u32] {
+        self.pixels
+    }
+
+    pub const fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub const fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// The horizontal and vertical scale from the AWT coordinate space to physical pixels.
+    pub const fn scale(&self) -> (f64, f64) {
+        self.scale
+    }
+
+    /// The rectangles that must be redrawn. The whole frame is damaged after it is resized.
+    pub fn damage(&self) -> &'a [Rect] {
+        self.damage
+    }
+}
+
+/// Renders a lightweight component such as a `javax.swing.JComponent` from Rust.
+///
+/// Frames are rendered into a buffer owned by Rust, so rendering may take as long as needed, and
+/// only their damaged rectangles are copied into a `BufferedImage`. Damage is collected with the
+/// [DamageTracker] of the surface like for a heavyweight component, in component coordinates.
+///
+/// Call [LightweightSurface::paint()] from `paintComponent(Graphics)`:
+///
+/// 1. When Rust state changes, add damage with [LightweightSurface::damage()] and call
+///    [DamageTracker::request_repaint()].
+/// 2. In `paintComponent()`, [LightweightSurface::paint()] renders the damage, if any, and draws
+///    the image. Areas Swing repaints on its own, such as those exposed by a closed popup, are
+///    drawn from the image without rendering again.
+pub struct LightweightSurface {
+    damage: DamageTracker,
+    buffer: Mutex<Buffer>,
+}
+
+#[derive(Default)]
+struct Buffer {
+    pixels: Vec<u32>,
+    image: ScaledImage,
+}
+
+impl fmt::Debug for LightweightSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let buffer = self.buffer();
+        f.debug_struct("LightweightSurface")
+            .field("damage", &self.damage)
+            .field("image", &buffer.image.image())
+            .finish()
+    }
+}
+
+impl LightweightSurface {
+    /// Creates a surface for `component`, which must be a `java.awt.Component`.
+    pub fn new(env: &JNIEnv, component: &JObject) -> jni::errors::Result<Self> {
+        Ok(Self {
+            damage: DamageTracker::new(env, component)?,
+            buffer: Mutex::new(Buffer::default()),
+        })
+    }
+
+    /// The damage of the component, in component coordinates.
+    pub fn damage(&self) -> &DamageTracker {
+        &self.damage
+    }
+
+    fn buffer(&self) -> MutexGuard<'_, Buffer> {
+        self.buffer
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Paints the component onto `graphics`, the `java.awt.Graphics` passed to
+    /// `paintComponent()`. If the component is damaged or its size in physical pixels changed,
+    /// `render` is called first to redraw the damage.
+    ///
+    /// The image is sized for the scale of `graphics` and drawn into the bounds of the component,
+    /// so Java2D copies it without resampling on HiDPI displays.
+    pub fn paint(
+        &self,
+        env: &mut JNIEnv,
+        graphics: &JObject,
+        render: impl FnOnce(&mut LightweightFrame),
+    ) -> jni::errors::Result<()> {
+        let component = self.damage.component();
+        let bounds = Rect {
+            x: 0,
+            y: 0,
+            width: env.call_method(component, "getWidth", "()I", &[])?.i()?,
+            height: env.call_method(component, "getHeight", "()I", &[])?.i()?,
+        };
+        if bounds.is_empty() {
+            return Ok(());
+        }
+        let scale = ScaledImage::graphics_scale(env, graphics)?;
+        let (width, height) = ScaledImage::physical_size((bounds.width, bounds.height), scale);
+
+        let mut buffer = self.buffer();
+        let buffer = &mut *buffer;
+        let mut damage = self.damage.take_all();
+        let (image, created) =
+            buffer
+                .image
+                .resize(env, (bounds.width, bounds.height), (width, height))?;
+        if created {
+            buffer.pixels = vec![0; width as usize * height as usize];
+            damage = DamageRegion::new();
+            damage.add(bounds);
+        }
+
+        let frame_bounds = Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let damage: Vec<Rect> = damage
+            .rects()
+            .iter()
+            .filter_map(|rect| scale_out(rect, scale).intersection(&frame_bounds))
+            .collect();
+        if !damage.is_empty() {
+            render(&mut LightweightFrame {
+                pixels: &mut buffer.pixels,
+                width,
+                height,
+                scale,
+                damage: &damage,
+            });
+            for rect in &damage {
+                let start = rect.y * width + rect.x;
+                if rect.x == 0 && rect.width == width {
+                    // Full rows are contiguous, so they are written with a single copy.
+                    let end = start + rect.height * width;
+                    image.write_pixels(env, start, &buffer.pixels[start as usize..end as usize])?;
+                    continue;
+                }
+                for row in 0..rect.height {
+                    let start = start + row * width;
+                    let end = start + rect.width;
+                    image.write_pixels(env, start, &buffer.pixels[start as usize..end as usize])?;
+                }
+            }
+        }
+
+        buffer.image.draw(env, graphics)?;
+        Ok(())
+    }
+}
+
+/// Scales `rect` to physical pixels, rounding outwards so that partially covered pixels are
+/// included.
+fn scale_out(rect: &Rect, (scale_x, scale_y): (f64, f64)) -> Rect {
+    let left = (f64::from(rect.x) * scale_x).floor() as i32;
+    let top = (f64::from(rect.y) * scale_y).floor() as i32;
+    let right = to_physical(rect.x.saturating_add(rect.width), scale_x);
+    let bottom = to_physical(rect.y.saturating_add(rect.height), scale_y);
+    Rect {
+        x: left,
+        y: top,
+        width: right.saturating_sub(left),
+        height: bottom.saturating_sub(top),
+    }
+}
@@ -5,6 +5,8 @@ use objc2::rc::Retained;
 use objc2::runtime::NSObjectProtocol;
 use objc2_quartz_core::CALayer;
 
+use crate::dsi::VersionedPlatformInfo;
+
 extern_protocol! {
     #[allow(clippy::missing_safety_doc)]
     #[name = "JAWT_SurfaceLayers"]
@@ -19,3 +21,20 @@ extern_protocol! {
         fn window_layer(&self) -> Retained<CALayer>;
     }
 }
+
+impl VersionedPlatformInfo<'_> {
+    /// See [SurfaceLayers::layer()].
+    pub fn layer(&self) -> Option<Retained<CALayer>> {
+        self.inner().layer()
+    }
+
+    /// See [SurfaceLayers::set_layer()].
+    pub fn set_layer(&self, layer: Option<&CALayer>) {
+        self.inner().set_layer(layer)
+    }
+
+    /// See [SurfaceLayers::window_layer()].
+    pub fn window_layer(&self) -> Retained<CALayer> {
+        self.inner().window_layer()
+    }
+}
@@ -8,7 +8,38 @@ use jawt_sys::*;
 use x11_dl::error::OpenError;
 use x11_dl::xlib::*;
 
-use crate::dsi::DrawingSurfaceInfo;
+use crate::dsi::{DrawingSurfaceInfo, PlatformInfoError, VersionedPlatformInfo};
+
+mod xlib;
+pub use xlib::*;
+
+mod child;
+pub use child::*;
+
+#[cfg(feature = "egl")]
+mod egl;
+#[cfg(feature = "egl")]
+pub use egl::*;
+
+#[cfg(feature = "glx")]
+mod glx;
+#[cfg(feature = "glx")]
+pub use glx::*;
+
+mod readback;
+
+mod wayland;
+pub use wayland::*;
+
+#[cfg(feature = "vulkan")]
+mod vulkan;
+#[cfg(feature = "vulkan")]
+pub use vulkan::*;
+
+#[cfg(feature = "xcb")]
+mod xcb;
+#[cfg(feature = "xcb")]
+pub use xcb::*;
 
 #[repr(transparent)]
 pub struct X11DrawingSurfaceInfo(pub(crate) JAWT_X11DrawingSurfaceInfo);
@@ -29,7 +60,11 @@ impl fmt::Debug for X11DrawingSurfaceInfo {
 #[derive(Debug, Clone)]
 pub enum WindowRetrievalError {
     XlibOpenFailed(OpenError),
+    XcbOpenFailed(OpenError),
     DrawableIsNotWindow(Drawable),
+    Protocol(XProtocolError),
+    /// The platform information of the drawing surface can't be used as X11.
+    PlatformInfo(PlatformInfoError),
 }
 
 impl fmt::Display for WindowRetrievalError {
@@ -38,37 +73,194 @@ impl fmt::Display for WindowRetrievalError {
             WindowRetrievalError::XlibOpenFailed(open_error) => {
                 write!(f, "failed to open X11: {open_error}")
             }
+            WindowRetrievalError::XcbOpenFailed(open_error) => {
+                write!(f, "failed to open X11-xcb: {open_error}")
+            }
             WindowRetrievalError::DrawableIsNotWindow(drawable) => {
                 write!(f, "drawable {drawable} is not a window")
             }
+            WindowRetrievalError::Protocol(protocol_error) => protocol_error.fmt(f),
+            WindowRetrievalError::PlatformInfo(platform_info_error) => platform_info_error.fmt(f),
         }
     }
 }
 
 impl Error for WindowRetrievalError {}
 
+impl From<X11Error> for WindowRetrievalError {
+    fn from(value: X11Error) -> Self {
+        match value {
+            X11Error::XlibOpenFailed(open_error) => Self::XlibOpenFailed(open_error),
+            X11Error::XcbOpenFailed(open_error) => Self::XcbOpenFailed(open_error),
+            X11Error::Protocol(protocol_error) => Self::Protocol(protocol_error),
+            X11Error::PlatformInfo(platform_info_error) => Self::PlatformInfo(platform_info_error),
+        }
+    }
+}
+
+impl From<PlatformInfoError> for WindowRetrievalError {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::PlatformInfo(value)
+    }
+}
+
+/// Geometry of an X11 drawable as reported by `XGetGeometry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct X11Geometry {
+    /// Root window of the screen the drawable belongs to.
+    pub root: Window,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Always zero for pixmaps.
+    pub border_width: u32,
+    pub depth: u32,
+}
+
+impl X11Geometry {
+    /// Issues `XGetGeometry`. Failures are reported through the active [XErrorTrap].
+    fn query(xlib: &Xlib, display: *mut Display, drawable: Drawable) -> Self {
+        let mut geometry = X11Geometry {
+            root: 0,
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            border_width: 0,
+            depth: 0,
+        };
+        unsafe {
+            (xlib.XGetGeometry)(
+                display,
+                drawable,
+                &mut geometry.root,
+                &mut geometry.x,
+                &mut geometry.y,
+                &mut geometry.width,
+                &mut geometry.height,
+                &mut geometry.border_width,
+                &mut geometry.depth,
+            )
+        };
+        geometry
+    }
+}
+
+/// Represents a native window or pixmap drawable. AWT hands out pixmaps for offscreen and
+/// printing peers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum X11SurfaceKind {
+    Window(Window),
+    Pixmap(Pixmap, X11Geometry),
+}
+
+impl X11SurfaceKind {
+    pub fn window(self) -> Option<Window> {
+        match self {
+            X11SurfaceKind::Window(window) => Some(window),
+            X11SurfaceKind::Pixmap(..) => None,
+        }
+    }
+
+    pub fn unwrap_window(self) -> Window {
+        self.window().expect("different kind of surface")
+    }
+
+    pub fn pixmap(self) -> Option<(Pixmap, X11Geometry)> {
+        match self {
+            X11SurfaceKind::Window(_) => None,
+            X11SurfaceKind::Pixmap(pixmap, geometry) => Some((pixmap, geometry)),
+        }
+    }
+
+    pub fn unwrap_pixmap(self) -> (Pixmap, X11Geometry) {
+        self.pixmap().expect("different kind of surface")
+    }
+
+    /// The way renderers can present frames to this kind of drawable.
+    pub fn present_path(self) -> X11PresentPath {
+        match self {
+            X11SurfaceKind::Window(_) => X11PresentPath::WindowSurface,
+            X11SurfaceKind::Pixmap(..) => X11PresentPath::CopyToPixmap,
+        }
+    }
+}
+
+/// Supported way of presenting rendered frames to an [X11SurfaceKind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum X11PresentPath {
+    /// Window-system surfaces such as Vulkan swapchains and EGL or GLX window surfaces can be
+    /// created for the window and present to it directly.
+    WindowSurface,
+    /// Pixmaps cannot back a swapchain. Render offscreen, then copy the frame into the pixmap
+    /// with `XPutImage` or `XCopyArea` while the drawing surface is locked.
+    CopyToPixmap,
+}
+
 impl X11DrawingSurfaceInfo {
     pub fn drawable(&self) -> Drawable {
         self.0.drawable
     }
 
+    /// Tells whether the drawable is a window or a pixmap.
+    pub fn surface_kind(&self) -> Result<X11SurfaceKind, X11Error> {
+        let display = self.0.display;
+        let drawable = self.0.drawable;
+        let trap = unsafe { XErrorTrap::new(display)? };
+        let xlib = trap.xlib();
+
+        let mut attributes = MaybeUninit::uninit();
+        let status =
+            unsafe { (xlib.XGetWindowAttributes)(display, drawable, attributes.as_mut_ptr()) };
+        match trap.check() {
+            Ok(()) if status != 0 => return Ok(X11SurfaceKind::Window(drawable)),
+            Ok(()) => {}
+            Err(error) if error.error_code == BadWindow => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let geometry = X11Geometry::query(xlib, display, drawable);
+        trap.finish()?;
+        Ok(X11SurfaceKind::Pixmap(drawable, geometry))
+    }
+
     pub fn window(&self) -> Result<Window, WindowRetrievalError> {
-        let xlib = Xlib::open().map_err(WindowRetrievalError::XlibOpenFailed)?;
-        unsafe {
-            let mut attributes = MaybeUninit::uninit();
-            if (xlib.XGetWindowAttributes)(self.0.display, self.0.drawable, attributes.as_mut_ptr())
-                == 0
-            {
-                return Err(WindowRetrievalError::DrawableIsNotWindow(self.0.drawable));
+        match self.surface_kind() {
+            Ok(X11SurfaceKind::Window(window)) => Ok(window),
+            Ok(X11SurfaceKind::Pixmap(..)) => {
+                Err(WindowRetrievalError::DrawableIsNotWindow(self.0.drawable))
             }
-        };
-        Ok(self.0.drawable)
+            Err(X11Error::Protocol(error)) if error.error_code == BadDrawable => {
+                Err(WindowRetrievalError::DrawableIsNotWindow(self.0.drawable))
+            }
+            Err(error) => Err(error.into()),
+        }
     }
 
     pub fn display(&self) -> *mut Display {
         self.0.display
     }
 
+    /// Geometry of the drawable.
+    pub fn geometry(&self) -> Result<X11Geometry, X11Error> {
+        let display = self.0.display;
+        let drawable = self.0.drawable;
+        unsafe { trap_errors(display, |xlib| X11Geometry::query(xlib, display, drawable)) }
+    }
+
+    /// Index of the screen whose root window the drawable belongs to.
+    pub fn screen_number(&self) -> Result<i32, X11Error> {
+        let display = self.0.display;
+        let root = self.geometry()?.root;
+        let xlib = xlib()?;
+        unsafe {
+            Ok((0..(xlib.XScreenCount)(display))
+                .find(|&screen| (xlib.XRootWindow)(display, screen) == root)
+                .unwrap_or_else(|| (xlib.XDefaultScreen)(display)))
+        }
+    }
+
     pub fn visual_id(&self) -> VisualID {
         self.0.visualID
     }
@@ -81,19 +273,93 @@ impl X11DrawingSurfaceInfo {
         self.0.depth as _
     }
 
+    /// Since [1.4](crate::AwtVersion::VERSION_1_4)
+    ///
+    /// Returns the pixel value for the color `r`, `g` and `b` in the colormap of the drawable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the JAWT does not provide `GetAWTColor`.
     #[cfg(feature = "java-1-4")]
+    #[deprecated(note = "panics if `GetAWTColor` is missing; use `try_get_awt_color()` instead")]
     pub fn get_awt_color(&self, dsi: &DrawingSurfaceInfo, r: i32, g: i32, b: i32) -> i32 {
-        unsafe {
-            (self
-                .0
-                .GetAWTColor
-                .expect("JAWT_X11DrawingSurfaceInfo.GetAWTColor is not available"))(
-                dsi.as_ref().ds,
-                r as _,
-                g as _,
-                b as _,
-            ) as _
+        self.try_get_awt_color(dsi, r, g, b)
+            .expect("JAWT_X11DrawingSurfaceInfo.GetAWTColor is not available")
+    }
+
+    /// Since [1.4](crate::AwtVersion::VERSION_1_4)
+    ///
+    /// Returns the pixel value for the color `r`, `g` and `b` in the colormap of the drawable,
+    /// or [None] if the JAWT does not provide `GetAWTColor`. Use
+    /// [VersionedPlatformInfo::get_awt_color()] to also check the version of the JAWT.
+    #[cfg(feature = "java-1-4")]
+    pub fn try_get_awt_color(
+        &self,
+        dsi: &DrawingSurfaceInfo,
+        r: i32,
+        g: i32,
+        b: i32,
+    ) -> Option<i32> {
+        let get_awt_color = self.0.GetAWTColor?;
+        Some(unsafe { get_awt_color(dsi.as_ref().ds, r as _, g as _, b as _) as _ })
+    }
+}
+
+impl VersionedPlatformInfo<'_> {
+    /// See [X11DrawingSurfaceInfo::drawable()].
+    pub fn drawable(&self) -> Drawable {
+        self.inner().drawable()
+    }
+
+    /// See [X11DrawingSurfaceInfo::display()].
+    pub fn display(&self) -> *mut Display {
+        self.inner().display()
+    }
+
+    /// See [X11DrawingSurfaceInfo::visual_id()].
+    pub fn visual_id(&self) -> VisualID {
+        self.inner().visual_id()
+    }
+
+    /// See [X11DrawingSurfaceInfo::colormap_id()].
+    pub fn colormap_id(&self) -> Colormap {
+        self.inner().colormap_id()
+    }
+
+    /// See [X11DrawingSurfaceInfo::depth()].
+    pub fn depth(&self) -> i32 {
+        self.inner().depth()
+    }
+
+    /// [X11DrawingSurfaceInfo::try_get_awt_color()], or [None] if the version is earlier than
+    /// 1.4.
+    #[cfg(feature = "java-1-4")]
+    pub fn get_awt_color(&self, dsi: &DrawingSurfaceInfo, r: i32, g: i32, b: i32) -> Option<i32> {
+        if !self.version().is_at_least(crate::AwtVersion::VERSION_1_4) {
+            return None;
         }
+        self.inner().try_get_awt_color(dsi, r, g, b)
+    }
+}
+
+#[cfg(feature = "raw-window-handle")]
+impl X11DrawingSurfaceInfo {
+    /// Returns a [raw_window_handle::XlibDisplayHandle] for the display and the screen of the
+    /// drawable.
+    pub fn xlib_display_handle(&self) -> Result<raw_window_handle::XlibDisplayHandle, X11Error> {
+        Ok(raw_window_handle::XlibDisplayHandle::new(
+            std::ptr::NonNull::new(self.0.display.cast()),
+            self.screen_number()?,
+        ))
+    }
+
+    /// Returns a [raw_window_handle::XlibWindowHandle] for the drawable, which must be a window.
+    pub fn xlib_window_handle(
+        &self,
+    ) -> Result<raw_window_handle::XlibWindowHandle, WindowRetrievalError> {
+        let mut handle = raw_window_handle::XlibWindowHandle::new(self.window()?);
+        handle.visual_id = self.0.visualID;
+        Ok(handle)
     }
 }
 
@@ -0,0 +1,429 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [X11ChildWindow] and [X11ChildSurface].
+
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::ptr;
+
+use x11_dl::xlib::*;
+
+use crate::ds::{DrawingSurface, DrawingSurfaceGuard, DrawingSurfaceLockResult};
+use crate::dsi::{DrawingSurfaceInfo, PlatformInfoError};
+use crate::md::unix::{trap_errors, WindowRetrievalError, X11Error, X11Geometry};
+
+/// Visual and colormap of an [X11ChildWindow].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct X11ChildWindowConfig {
+    /// Visual of the child window. A null pointer uses the visual of the canvas drawable.
+    pub visual: *mut Visual,
+    /// Depth of `visual`. Ignored when `visual` is null.
+    pub depth: i32,
+    /// Colormap of the child window. When [None] and `visual` is set, a colormap for `visual` is
+    /// created and freed together with the window.
+    pub colormap: Option<Colormap>,
+}
+
+impl X11ChildWindowConfig {
+    /// Configuration using the visual and the depth of `visual_info`.
+    pub fn from_visual_info(visual_info: &XVisualInfo) -> Self {
+        Self {
+            visual: visual_info.visual,
+            depth: visual_info.depth,
+            colormap: None,
+        }
+    }
+}
+
+impl Default for X11ChildWindowConfig {
+    fn default() -> Self {
+        Self {
+            visual: ptr::null_mut(),
+            depth: CopyFromParent,
+            colormap: None,
+        }
+    }
+}
+
+/// A private X11 window created inside the drawable of a Canvas. Renderers can choose its visual
+/// freely and draw into it without fighting AWT's own repaints of the Canvas.
+///
+/// Methods that issue X requests on AWT's display connection take the [DrawingSurfaceInfo] of the
+/// locked drawing surface. Dropping the window sends no requests; call [X11ChildWindow::destroy()]
+/// to destroy it while the surface is locked, or leave it to the X server, which destroys it
+/// together with the Canvas drawable. [X11ChildSurface] takes care of this.
+pub struct X11ChildWindow {
+    display: *mut Display,
+    parent: Window,
+    window: Window,
+    owned_colormap: Option<Colormap>,
+    config: X11ChildWindowConfig,
+    size: (u32, u32),
+    visible: bool,
+}
+
+impl fmt::Debug for X11ChildWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("X11ChildWindow")
+            .field("display", &self.display)
+            .field("parent", &self.parent)
+            .field("window", &self.window)
+            .field("owned_colormap", &self.owned_colormap)
+            .field("config", &self.config)
+            .field("size", &self.size)
+            .field("visible", &self.visible)
+            .finish()
+    }
+}
+
+impl X11ChildWindow {
+    /// Creates an unmapped child window covering the drawable of `info`, which must be a window.
+    pub fn new(
+        info: &DrawingSurfaceInfo,
+        config: X11ChildWindowConfig,
+    ) -> Result<Self, WindowRetrievalError> {
+        let platform_info = info.try_platform_info()?;
+        let parent = platform_info.window()?;
+        let mut child = Self {
+            display: platform_info.display(),
+            parent,
+            window: 0,
+            owned_colormap: None,
+            config,
+            size: Self::size_of(info),
+            visible: false,
+        };
+        child.create()?;
+        Ok(child)
+    }
+
+    fn size_of(info: &DrawingSurfaceInfo) -> (u32, u32) {
+        let bounds = info.bounds();
+        (
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+        )
+    }
+
+    fn create(&mut self) -> Result<(), X11Error> {
+        let display = self.display;
+        let parent = self.parent;
+        let config = self.config;
+        let (width, height) = self.size;
+        let result = unsafe {
+            trap_errors(display, |xlib| {
+                let mut attributes: XSetWindowAttributes = mem::zeroed();
+                attributes.background_pixmap = 0;
+                attributes.border_pixel = 0;
+                let mut value_mask = CWBackPixmap | CWBorderPixel;
+
+                if !config.visual.is_null() {
+                    let colormap = config.colormap.unwrap_or_else(|| {
+                        // The colormap must belong to the screen of the parent, which is not
+                        // necessarily the default screen of the display.
+                        let root = X11Geometry::query(xlib, display, parent).root;
+                        let colormap =
+                            (xlib.XCreateColormap)(display, root, config.visual, AllocNone);
+                        self.owned_colormap = Some(colormap);
+                        colormap
+                    });
+                    attributes.colormap = colormap;
+                    value_mask |= CWColormap;
+                }
+
+                self.window = (xlib.XCreateWindow)(
+                    display,
+                    parent,
+                    0,
+                    0,
+                    width.max(1),
+                    height.max(1),
+                    0,
+                    if config.visual.is_null() {
+                        CopyFromParent
+                    } else {
+                        config.depth
+                    },
+                    InputOutput as _,
+                    config.visual,
+                    value_mask,
+                    &mut attributes,
+                );
+            })
+        };
+        if let Err(error) = result {
+            // Frees the colormap created for a window that could not be created.
+            self.destroy_resources();
+            return Err(error);
+        }
+        if self.visible {
+            self.apply_visibility()?;
+        }
+        Ok(())
+    }
+
+    fn apply_visibility(&self) -> Result<(), X11Error> {
+        let display = self.display;
+        let window = self.window;
+        let mapped = self.visible && self.size.0 > 0 && self.size.1 > 0;
+        unsafe {
+            trap_errors(display, |xlib| {
+                if mapped {
+                    (xlib.XMapWindow)(display, window);
+                } else {
+                    (xlib.XUnmapWindow)(display, window);
+                }
+            })
+        }
+    }
+
+    fn destroy_resources(&mut self) {
+        let display = self.display;
+        let window = mem::take(&mut self.window);
+        let colormap = self.owned_colormap.take();
+        if window == 0 && colormap.is_none() {
+            return;
+        }
+        // The window is gone already if AWT destroyed the parent, so errors are ignored.
+        let _ = unsafe {
+            trap_errors(display, |xlib| {
+                if window != 0 {
+                    (xlib.XDestroyWindow)(display, window);
+                }
+                if let Some(colormap) = colormap {
+                    (xlib.XFreeColormap)(display, colormap);
+                }
+            })
+        };
+    }
+
+    /// The child window.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// The Canvas drawable the child window is placed in.
+    pub fn parent(&self) -> Window {
+        self.parent
+    }
+
+    pub fn display(&self) -> *mut Display {
+        self.display
+    }
+
+    /// Current width and height of the child window, which follow the bounds of the Canvas.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Maps or unmaps the child window. Call this when the component is shown or hidden. The
+    /// window also stays unmapped while the Canvas has an empty size. `info` must be the
+    /// information of the locked drawing surface the window was created in.
+    pub fn set_visible(
+        &mut self,
+        _info: &DrawingSurfaceInfo,
+        visible: bool,
+    ) -> Result<(), X11Error> {
+        self.visible = visible;
+        self.apply_visibility()
+    }
+
+    /// Destroys the child window and the colormap created for it. `info` must be the information
+    /// of the locked drawing surface the window was created in.
+    pub fn destroy(mut self, _info: &DrawingSurfaceInfo) {
+        self.destroy_resources();
+    }
+
+    /// Follows changes reported by [DrawingSurface::lock()]. The child window is resized on
+    /// [DrawingSurfaceLockResult::BOUNDS_CHANGED] and re-created inside the new drawable when AWT
+    /// replaced the drawable. Returns `true` if the window was re-created.
+    pub fn update(
+        &mut self,
+        info: &DrawingSurfaceInfo,
+        lock_result: DrawingSurfaceLockResult,
+    ) -> Result<bool, WindowRetrievalError> {
+        let size = Self::size_of(info);
+        if lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED) {
+            let parent = info.try_platform_info()?.window()?;
+            if parent != self.parent {
+                self.destroy_resources();
+                self.parent = parent;
+                self.size = size;
+                self.create()?;
+                return Ok(true);
+            }
+        }
+        if lock_result.contains(DrawingSurfaceLockResult::BOUNDS_CHANGED) && size != self.size {
+            let display = self.display;
+            let window = self.window;
+            self.size = size;
+            unsafe {
+                trap_errors(display, |xlib| {
+                    (xlib.XResizeWindow)(display, window, size.0.max(1), size.1.max(1))
+                })?
+            };
+            self.apply_visibility()?;
+        }
+        Ok(false)
+    }
+}
+
+/// Error returned by [X11ChildSurface].
+#[derive(Debug, Clone)]
+pub enum X11ChildSurfaceError {
+    /// [DrawingSurface::lock()] failed.
+    LockFailed,
+    /// [DrawingSurfaceGuard::drawing_surface_info()] failed.
+    DrawingSurfaceInfoUnavailable,
+    Window(WindowRetrievalError),
+    /// The platform information of the drawing surface can't be used as X11.
+    PlatformInfo(PlatformInfoError),
+}
+
+impl fmt::Display for X11ChildSurfaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            X11ChildSurfaceError::LockFailed => write!(f, "failed to lock the drawing surface"),
+            X11ChildSurfaceError::DrawingSurfaceInfoUnavailable => {
+                write!(f, "failed to retrieve the drawing surface info")
+            }
+            X11ChildSurfaceError::Window(error) => error.fmt(f),
+            X11ChildSurfaceError::PlatformInfo(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for X11ChildSurfaceError {}
+
+impl From<WindowRetrievalError> for X11ChildSurfaceError {
+    fn from(value: WindowRetrievalError) -> Self {
+        Self::Window(value)
+    }
+}
+
+impl From<X11Error> for X11ChildSurfaceError {
+    fn from(value: X11Error) -> Self {
+        Self::Window(value.into())
+    }
+}
+
+impl From<PlatformInfoError> for X11ChildSurfaceError {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::PlatformInfo(value)
+    }
+}
+
+/// A [DrawingSurface] that owns an [X11ChildWindow]. The child window tracks the bounds of the
+/// Canvas on every [X11ChildSurface::lock()] and is destroyed when the surface is dropped.
+#[derive(Debug)]
+pub struct X11ChildSurface {
+    child: X11ChildWindow,
+    surface: DrawingSurface,
+}
+
+impl X11ChildSurface {
+    /// Creates a child window inside the drawable of `surface`.
+    pub fn new(
+        mut surface: DrawingSurface,
+        config: X11ChildWindowConfig,
+    ) -> Result<Self, X11ChildSurfaceError> {
+        let child = {
+            let (_, mut guard) = surface.lock().ok_or(X11ChildSurfaceError::LockFailed)?;
+            let info = guard
+                .drawing_surface_info()
+                .ok_or(X11ChildSurfaceError::DrawingSurfaceInfoUnavailable)?;
+            X11ChildWindow::new(&info, config)?
+        };
+        Ok(Self { child, surface })
+    }
+
+    /// The child window. Its X resources may only be used while the surface is locked.
+    pub fn child(&self) -> &X11ChildWindow {
+        &self.child
+    }
+
+    /// The underlying drawing surface.
+    pub fn drawing_surface(&self) -> &DrawingSurface {
+        &self.surface
+    }
+
+    /// Locks the surface and updates the child window as described in
+    /// [X11ChildWindow::update()].
+    pub fn lock(
+        &mut self,
+    ) -> Result<(DrawingSurfaceLockResult, X11ChildSurfaceGuard<'_>), X11ChildSurfaceError> {
+        let (lock_result, mut guard) = self
+            .surface
+            .lock()
+            .ok_or(X11ChildSurfaceError::LockFailed)?;
+        if lock_result.intersects(
+            DrawingSurfaceLockResult::BOUNDS_CHANGED | DrawingSurfaceLockResult::SURFACE_CHANGED,
+        ) {
+            let info = guard
+                .drawing_surface_info()
+                .ok_or(X11ChildSurfaceError::DrawingSurfaceInfoUnavailable)?;
+            self.child.update(&info, lock_result)?;
+        }
+        Ok((
+            lock_result,
+            X11ChildSurfaceGuard {
+                guard,
+                child: &mut self.child,
+            },
+        ))
+    }
+
+    /// Maps or unmaps the child window while holding the surface lock. Call this from
+    /// `componentShown` and `componentHidden`.
+    pub fn set_visible(&mut self, visible: bool) -> Result<(), X11ChildSurfaceError> {
+        let (_, mut guard) = self.lock()?;
+        guard.set_visible(visible)
+    }
+}
+
+impl Drop for X11ChildSurface {
+    fn drop(&mut self) {
+        // Without the lock, the peer is gone, and the X server destroyed the child window together
+        // with it. The owned colormap can't be freed without the lock, so it is left to the
+        // server, which frees it when AWT closes the display connection.
+        if let Some(_guard) = self.surface.lock() {
+            self.child.destroy_resources();
+        }
+    }
+}
+
+/// An RAII implementation of a scoped lock of an [X11ChildSurface]. The surface is unlocked when
+/// this structure is dropped.
+pub struct X11ChildSurfaceGuard<'a> {
+    guard: DrawingSurfaceGuard<'a>,
+    child: &'a mut X11ChildWindow,
+}
+
+impl X11ChildSurfaceGuard<'_> {
+    pub fn drawing_surface_info(&mut self) -> Option<DrawingSurfaceInfo<'_>> {
+        self.guard.drawing_surface_info()
+    }
+
+    pub fn child(&self) -> &X11ChildWindow {
+        self.child
+    }
+
+    pub fn child_mut(&mut self) -> &mut X11ChildWindow {
+        self.child
+    }
+
+    /// Maps or unmaps the child window as described in [X11ChildWindow::set_visible()].
+    pub fn set_visible(&mut self, visible: bool) -> Result<(), X11ChildSurfaceError> {
+        let info = self
+            .guard
+            .drawing_surface_info()
+            .ok_or(X11ChildSurfaceError::DrawingSurfaceInfoUnavailable)?;
+        self.child.set_visible(&info, visible)?;
+        Ok(())
+    }
+}
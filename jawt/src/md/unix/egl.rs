@@ -0,0 +1,762 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [EglSurface], an EGL context rendering into an AWT Canvas on X11.
+
+use std::error::Error;
+use std::ffi::{c_char, c_void, CStr};
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::thread;
+
+use libc::{dlclose, dlopen, dlsym, RTLD_LAZY, RTLD_LOCAL};
+use x11_dl::xlib::{Display, VisualID, Window};
+
+use crate::ds::DrawingSurfaceLockResult;
+use crate::dsi::{DrawingSurfaceInfo, PlatformInfoError};
+use crate::md::unix::{
+    trap_errors, visual_info, LazyLibrary, WindowRetrievalError, X11ChildWindow,
+    X11ChildWindowConfig, X11Error,
+};
+
+pub type EGLBoolean = u32;
+pub type EGLenum = u32;
+pub type EGLint = i32;
+pub type EGLAttrib = isize;
+pub type EGLDisplay = *mut c_void;
+pub type EGLConfig = *mut c_void;
+pub type EGLSurface = *mut c_void;
+pub type EGLContext = *mut c_void;
+
+pub const EGL_SUCCESS: EGLint = 0x3000;
+pub const EGL_NONE: EGLint = 0x3038;
+pub const EGL_ALPHA_SIZE: EGLint = 0x3021;
+pub const EGL_BLUE_SIZE: EGLint = 0x3022;
+pub const EGL_GREEN_SIZE: EGLint = 0x3023;
+pub const EGL_RED_SIZE: EGLint = 0x3024;
+pub const EGL_DEPTH_SIZE: EGLint = 0x3025;
+pub const EGL_STENCIL_SIZE: EGLint = 0x3026;
+pub const EGL_NATIVE_VISUAL_ID: EGLint = 0x302E;
+pub const EGL_SAMPLES: EGLint = 0x3031;
+pub const EGL_SAMPLE_BUFFERS: EGLint = 0x3032;
+pub const EGL_SURFACE_TYPE: EGLint = 0x3033;
+pub const EGL_WINDOW_BIT: EGLint = 0x0004;
+pub const EGL_RENDERABLE_TYPE: EGLint = 0x3040;
+pub const EGL_OPENGL_ES2_BIT: EGLint = 0x0004;
+pub const EGL_OPENGL_BIT: EGLint = 0x0008;
+pub const EGL_OPENGL_ES3_BIT: EGLint = 0x0040;
+pub const EGL_EXTENSIONS: EGLint = 0x3055;
+pub const EGL_OPENGL_ES_API: EGLenum = 0x30A0;
+pub const EGL_OPENGL_API: EGLenum = 0x30A2;
+pub const EGL_CONTEXT_MAJOR_VERSION: EGLint = 0x3098;
+pub const EGL_CONTEXT_MINOR_VERSION: EGLint = 0x30FB;
+pub const EGL_CONTEXT_OPENGL_PROFILE_MASK: EGLint = 0x30FD;
+pub const EGL_CONTEXT_OPENGL_CORE_PROFILE_BIT: EGLint = 0x0001;
+pub const EGL_CONTEXT_OPENGL_COMPATIBILITY_PROFILE_BIT: EGLint = 0x0002;
+pub const EGL_PLATFORM_X11_KHR: EGLenum = 0x31D5;
+pub const EGL_PLATFORM_X11_SCREEN_KHR: EGLint = 0x31D6;
+
+type GetPlatformDisplayExt =
+    unsafe extern "C" fn(EGLenum, *mut c_void, *const EGLint) -> EGLDisplay;
+type GetPlatformDisplay =
+    unsafe extern "C" fn(EGLenum, *mut c_void, *const EGLAttrib) -> EGLDisplay;
+type CreatePlatformWindowSurfaceExt =
+    unsafe extern "C" fn(EGLDisplay, EGLConfig, *mut c_void, *const EGLint) -> EGLSurface;
+type CreatePlatformWindowSurface =
+    unsafe extern "C" fn(EGLDisplay, EGLConfig, *mut c_void, *const EGLAttrib) -> EGLSurface;
+
+/// Entry points of `libEGL` used by [EglSurface].
+#[allow(non_snake_case)]
+pub struct Egl {
+    pub eglGetProcAddress: unsafe extern "C" fn(*const c_char) -> *mut c_void,
+    pub eglGetError: unsafe extern "C" fn() -> EGLint,
+    pub eglQueryString: unsafe extern "C" fn(EGLDisplay, EGLint) -> *const c_char,
+    pub eglInitialize: unsafe extern "C" fn(EGLDisplay, *mut EGLint, *mut EGLint) -> EGLBoolean,
+    pub eglBindAPI: unsafe extern "C" fn(EGLenum) -> EGLBoolean,
+    pub eglChooseConfig: unsafe extern "C" fn(
+        EGLDisplay,
+        *const EGLint,
+        *mut EGLConfig,
+        EGLint,
+        *mut EGLint,
+    ) -> EGLBoolean,
+    pub eglGetConfigAttrib:
+        unsafe extern "C" fn(EGLDisplay, EGLConfig, EGLint, *mut EGLint) -> EGLBoolean,
+    pub eglDestroySurface: unsafe extern "C" fn(EGLDisplay, EGLSurface) -> EGLBoolean,
+    pub eglCreateContext:
+        unsafe extern "C" fn(EGLDisplay, EGLConfig, EGLContext, *const EGLint) -> EGLContext,
+    pub eglDestroyContext: unsafe extern "C" fn(EGLDisplay, EGLContext) -> EGLBoolean,
+    pub eglMakeCurrent:
+        unsafe extern "C" fn(EGLDisplay, EGLSurface, EGLSurface, EGLContext) -> EGLBoolean,
+    pub eglGetCurrentContext: unsafe extern "C" fn() -> EGLContext,
+    pub eglSwapBuffers: unsafe extern "C" fn(EGLDisplay, EGLSurface) -> EGLBoolean,
+    pub eglSwapInterval: unsafe extern "C" fn(EGLDisplay, EGLint) -> EGLBoolean,
+}
+
+// Safety: `Egl` is just a collection of function pointers.
+unsafe impl Send for Egl {}
+
+// Safety: `Egl` is just a collection of function pointers.
+unsafe impl Sync for Egl {}
+
+impl Egl {
+    unsafe fn open() -> Option<Self> {
+        let mut handle = dlopen(b"libEGL.so.1\0".as_ptr() as _, RTLD_LAZY | RTLD_LOCAL);
+        if handle.is_null() {
+            handle = dlopen(b"libEGL.so\0".as_ptr() as _, RTLD_LAZY | RTLD_LOCAL);
+        }
+        if handle.is_null() {
+            return None;
+        }
+
+        macro_rules! symbol {
+            ($name:literal) => {
+                match load_symbol(handle, concat!($name, "\0").as_bytes()) {
+                    Some(symbol) => symbol,
+                    None => {
+                        dlclose(handle);
+                        return None;
+                    }
+                }
+            };
+        }
+
+        // Once every symbol is found, the library is never closed as the function pointers are
+        // cached for the process.
+        Some(Self {
+            eglGetProcAddress: symbol!("eglGetProcAddress"),
+            eglGetError: symbol!("eglGetError"),
+            eglQueryString: symbol!("eglQueryString"),
+            eglInitialize: symbol!("eglInitialize"),
+            eglBindAPI: symbol!("eglBindAPI"),
+            eglChooseConfig: symbol!("eglChooseConfig"),
+            eglGetConfigAttrib: symbol!("eglGetConfigAttrib"),
+            eglDestroySurface: symbol!("eglDestroySurface"),
+            eglCreateContext: symbol!("eglCreateContext"),
+            eglDestroyContext: symbol!("eglDestroyContext"),
+            eglMakeCurrent: symbol!("eglMakeCurrent"),
+            eglGetCurrentContext: symbol!("eglGetCurrentContext"),
+            eglSwapBuffers: symbol!("eglSwapBuffers"),
+            eglSwapInterval: symbol!("eglSwapInterval"),
+        })
+    }
+
+    unsafe fn proc_address<T: Copy>(&self, name: &[u8]) -> Option<T> {
+        let address = (self.eglGetProcAddress)(name.as_ptr() as _);
+        if address.is_null() {
+            return None;
+        }
+        Some(std::mem::transmute_copy(&address))
+    }
+
+    fn last_error(&self, function: &'static str) -> EglError {
+        EglError::Egl {
+            function,
+            code: unsafe { (self.eglGetError)() },
+        }
+    }
+}
+
+/// Reinterprets the address of `name` in `handle` as the function pointer type `T`.
+unsafe fn load_symbol<T: Copy>(handle: *mut c_void, name: &[u8]) -> Option<T> {
+    let symbol = dlsym(handle, name.as_ptr() as _);
+    if symbol.is_null() {
+        return None;
+    }
+    Some(std::mem::transmute_copy(&symbol))
+}
+
+/// Returns the process-wide [Egl] handle, loading `libEGL` on the first call.
+pub fn egl() -> Option<&'static Egl> {
+    static EGL: LazyLibrary<Egl> = LazyLibrary::new();
+    EGL.get_or_load(|| unsafe { Egl::open() }.ok_or(())).ok()
+}
+
+/// Client API of an [EglSurface].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EglApi {
+    OpenGlEs,
+    /// Desktop OpenGL with the core profile.
+    OpenGlCore,
+    /// Desktop OpenGL with the compatibility profile.
+    OpenGlCompatibility,
+}
+
+/// Requested attributes of an [EglSurface]. The defaults ask for an OpenGL ES 3.0 context, which
+/// Mesa's software EGL provides without a GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EglContextAttributes {
+    pub api: EglApi,
+    pub major_version: i32,
+    pub minor_version: i32,
+    pub alpha_size: i32,
+    pub depth_size: i32,
+    pub stencil_size: i32,
+    /// Number of multisample samples. Zero disables multisampling.
+    pub samples: i32,
+}
+
+impl Default for EglContextAttributes {
+    fn default() -> Self {
+        Self {
+            api: EglApi::OpenGlEs,
+            major_version: 3,
+            minor_version: 0,
+            alpha_size: 0,
+            depth_size: 24,
+            stencil_size: 8,
+            samples: 0,
+        }
+    }
+}
+
+impl EglContextAttributes {
+    fn config_attributes(&self) -> Vec<EGLint> {
+        let renderable_type = match (self.api, self.major_version) {
+            (EglApi::OpenGlEs, 3..) => EGL_OPENGL_ES3_BIT,
+            (EglApi::OpenGlEs, _) => EGL_OPENGL_ES2_BIT,
+            (EglApi::OpenGlCore | EglApi::OpenGlCompatibility, _) => EGL_OPENGL_BIT,
+        };
+        let mut attributes = vec![
+            EGL_SURFACE_TYPE,
+            EGL_WINDOW_BIT,
+            EGL_RENDERABLE_TYPE,
+            renderable_type,
+            EGL_RED_SIZE,
+            8,
+            EGL_GREEN_SIZE,
+            8,
+            EGL_BLUE_SIZE,
+            8,
+            EGL_ALPHA_SIZE,
+            self.alpha_size,
+            EGL_DEPTH_SIZE,
+            self.depth_size,
+            EGL_STENCIL_SIZE,
+            self.stencil_size,
+        ];
+        if self.samples > 0 {
+            attributes.extend([EGL_SAMPLE_BUFFERS, 1, EGL_SAMPLES, self.samples]);
+        }
+        attributes.push(EGL_NONE);
+        attributes
+    }
+
+    fn context_attributes(&self) -> Vec<EGLint> {
+        let mut attributes = vec![EGL_CONTEXT_MAJOR_VERSION, self.major_version];
+        if self.minor_version != 0 {
+            attributes.extend([EGL_CONTEXT_MINOR_VERSION, self.minor_version]);
+        }
+        match self.api {
+            EglApi::OpenGlEs => {}
+            EglApi::OpenGlCore => attributes.extend([
+                EGL_CONTEXT_OPENGL_PROFILE_MASK,
+                EGL_CONTEXT_OPENGL_CORE_PROFILE_BIT,
+            ]),
+            EglApi::OpenGlCompatibility => attributes.extend([
+                EGL_CONTEXT_OPENGL_PROFILE_MASK,
+                EGL_CONTEXT_OPENGL_COMPATIBILITY_PROFILE_BIT,
+            ]),
+        }
+        attributes.push(EGL_NONE);
+        attributes
+    }
+}
+
+/// Error returned by [EglSurface].
+#[derive(Debug, Clone)]
+pub enum EglError {
+    EglOpenFailed,
+    /// Neither `EGL_EXT_platform_x11` nor `EGL_KHR_platform_x11` is available.
+    PlatformUnsupported,
+    /// No EGLConfig satisfies the requested attributes.
+    NoMatchingConfig,
+    /// An EGL function failed with the given `eglGetError()` code.
+    Egl {
+        function: &'static str,
+        code: EGLint,
+    },
+    Window(WindowRetrievalError),
+}
+
+impl fmt::Display for EglError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EglError::EglOpenFailed => write!(f, "failed to open EGL"),
+            EglError::PlatformUnsupported => write!(f, "EGL does not support the X11 platform"),
+            EglError::NoMatchingConfig => write!(f, "no EGLConfig matches the attributes"),
+            EglError::Egl { function, code } => write!(f, "{function} failed with {code:#x}"),
+            EglError::Window(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for EglError {}
+
+impl From<WindowRetrievalError> for EglError {
+    fn from(value: WindowRetrievalError) -> Self {
+        Self::Window(value)
+    }
+}
+
+impl From<X11Error> for EglError {
+    fn from(value: X11Error) -> Self {
+        Self::Window(value.into())
+    }
+}
+
+impl From<PlatformInfoError> for EglError {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::Window(value.into())
+    }
+}
+
+/// The window an [EglSurface] renders into.
+#[derive(Debug)]
+pub enum EglTarget {
+    /// An EGLConfig matched the visual AWT chose, so the surface is created for the Canvas
+    /// drawable directly.
+    CanvasDrawable(Window),
+    /// No EGLConfig matched the visual of the Canvas, so the surface is created for a child window
+    /// with the native visual of the chosen EGLConfig.
+    ChildWindow(X11ChildWindow),
+}
+
+impl EglTarget {
+    pub fn window(&self) -> Window {
+        match self {
+            EglTarget::CanvasDrawable(window) => *window,
+            EglTarget::ChildWindow(child) => child.window(),
+        }
+    }
+
+    fn destroy(self, info: &DrawingSurfaceInfo) {
+        if let EglTarget::ChildWindow(child) = self {
+            child.destroy(info);
+        }
+    }
+}
+
+/// How the `EGLDisplay` of an [EglSurface] was obtained, which decides the function window surfaces
+/// are created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EglPlatform {
+    /// `eglGetPlatformDisplayEXT` of `EGL_EXT_platform_base` and `EGL_EXT_platform_x11`.
+    Ext,
+    /// `eglGetPlatformDisplay` of EGL 1.5 and `EGL_KHR_platform_x11`.
+    Khr,
+}
+
+/// An EGL context and window surface created on the `EGLDisplay` of
+/// [X11DrawingSurfaceInfo::display()] with `EGL_PLATFORM_X11_KHR`.
+///
+/// EGL on X11 issues requests through AWT's display connection, so methods that issue them take
+/// the [DrawingSurfaceInfo] of the locked drawing surface. Dropping the surface sends no requests
+/// and leaks the context and the window surface; call [EglSurface::destroy()] while the surface is
+/// locked instead. Debug builds panic when a surface is dropped without being destroyed. The
+/// `EGLDisplay` is shared with other users of the same X display and is never
+/// terminated.
+///
+/// [X11DrawingSurfaceInfo::display()]: crate::md::unix::X11DrawingSurfaceInfo::display()
+pub struct EglSurface {
+    egl: &'static Egl,
+    x_display: *mut Display,
+    display: EGLDisplay,
+    platform: EglPlatform,
+    config: EGLConfig,
+    context: EGLContext,
+    surface: EGLSurface,
+    target: EglTarget,
+    size: (u32, u32),
+}
+
+impl fmt::Debug for EglSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EglSurface")
+            .field("x_display", &self.x_display)
+            .field("display", &self.display)
+            .field("platform", &self.platform)
+            .field("config", &self.config)
+            .field("context", &self.context)
+            .field("surface", &self.surface)
+            .field("target", &self.target)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl EglSurface {
+    /// Initializes the `EGLDisplay`, chooses an EGLConfig, and creates a context and a window
+    /// surface. The context is not made current.
+    pub fn new(
+        info: &DrawingSurfaceInfo,
+        attributes: &EglContextAttributes,
+    ) -> Result<Self, EglError> {
+        let egl = egl().ok_or(EglError::EglOpenFailed)?;
+        let platform_info = info.try_platform_info()?;
+        let x_display = platform_info.display();
+        let window = platform_info.window()?;
+
+        let (display, platform) =
+            unsafe { Self::platform_display(egl, x_display, platform_info.screen_number()?)? };
+        if unsafe { (egl.eglInitialize)(display, ptr::null_mut(), ptr::null_mut()) } == 0 {
+            return Err(egl.last_error("eglInitialize"));
+        }
+        let api = match attributes.api {
+            EglApi::OpenGlEs => EGL_OPENGL_ES_API,
+            EglApi::OpenGlCore | EglApi::OpenGlCompatibility => EGL_OPENGL_API,
+        };
+        if unsafe { (egl.eglBindAPI)(api) } == 0 {
+            return Err(egl.last_error("eglBindAPI"));
+        }
+
+        let (config, native_visual_id) =
+            unsafe { Self::choose_config(egl, display, platform_info.visual_id(), attributes)? };
+        let target = if native_visual_id == platform_info.visual_id() {
+            EglTarget::CanvasDrawable(window)
+        } else {
+            let visual_info = unsafe { visual_info(x_display, native_visual_id) }
+                .map_err(X11Error::from)?
+                .ok_or(EglError::NoMatchingConfig)?;
+            let mut child =
+                X11ChildWindow::new(info, X11ChildWindowConfig::from_visual_info(&visual_info))?;
+            if let Err(error) = child.set_visible(info, true) {
+                child.destroy(info);
+                return Err(error.into());
+            }
+            EglTarget::ChildWindow(child)
+        };
+
+        let context_attributes = attributes.context_attributes();
+        let context = unsafe {
+            (egl.eglCreateContext)(
+                display,
+                config,
+                ptr::null_mut(),
+                context_attributes.as_ptr(),
+            )
+        };
+        if context.is_null() {
+            let error = egl.last_error("eglCreateContext");
+            target.destroy(info);
+            return Err(error);
+        }
+
+        let bounds = info.bounds();
+        let mut surface = Self {
+            egl,
+            x_display,
+            display,
+            platform,
+            config,
+            context,
+            surface: ptr::null_mut(),
+            target,
+            size: (
+                u32::try_from(bounds.width).unwrap_or_default(),
+                u32::try_from(bounds.height).unwrap_or_default(),
+            ),
+        };
+        if let Err(error) = surface.create_surface() {
+            surface.destroy(info);
+            return Err(error);
+        }
+        Ok(surface)
+    }
+
+    unsafe fn platform_display(
+        egl: &Egl,
+        x_display: *mut Display,
+        screen: i32,
+    ) -> Result<(EGLDisplay, EglPlatform), EglError> {
+        let client_extensions = (egl.eglQueryString)(ptr::null_mut(), EGL_EXTENSIONS);
+        let has_extension = |name: &[u8]| {
+            !client_extensions.is_null()
+                && CStr::from_ptr(client_extensions)
+                    .to_bytes()
+                    .split(|&c| c == b' ')
+                    .any(|extension| extension == name)
+        };
+        // EGL_EXT_platform_x11 extends eglGetPlatformDisplayEXT of EGL_EXT_platform_base, while
+        // EGL_KHR_platform_x11 extends eglGetPlatformDisplay of EGL 1.5, which takes EGLAttribs.
+        let (display, platform, function) =
+            if has_extension(b"EGL_EXT_platform_base") && has_extension(b"EGL_EXT_platform_x11") {
+                let get_platform_display = egl
+                    .proc_address::<GetPlatformDisplayExt>(b"eglGetPlatformDisplayEXT\0")
+                    .ok_or(EglError::PlatformUnsupported)?;
+                let display_attributes = [EGL_PLATFORM_X11_SCREEN_KHR, screen, EGL_NONE];
+                let display = get_platform_display(
+                    EGL_PLATFORM_X11_KHR,
+                    x_display.cast(),
+                    display_attributes.as_ptr(),
+                );
+                (display, EglPlatform::Ext, "eglGetPlatformDisplayEXT")
+            } else if has_extension(b"EGL_KHR_platform_x11") {
+                let get_platform_display = egl
+                    .proc_address::<GetPlatformDisplay>(b"eglGetPlatformDisplay\0")
+                    .ok_or(EglError::PlatformUnsupported)?;
+                let display_attributes = [
+                    EGL_PLATFORM_X11_SCREEN_KHR as EGLAttrib,
+                    screen as EGLAttrib,
+                    EGL_NONE as EGLAttrib,
+                ];
+                let display = get_platform_display(
+                    EGL_PLATFORM_X11_KHR,
+                    x_display.cast(),
+                    display_attributes.as_ptr(),
+                );
+                (display, EglPlatform::Khr, "eglGetPlatformDisplay")
+            } else {
+                return Err(EglError::PlatformUnsupported);
+            };
+        if display.is_null() {
+            return Err(egl.last_error(function));
+        }
+        Ok((display, platform))
+    }
+
+    /// Prefers an EGLConfig whose native visual is the visual of the Canvas.
+    unsafe fn choose_config(
+        egl: &Egl,
+        display: EGLDisplay,
+        visual_id: VisualID,
+        attributes: &EglContextAttributes,
+    ) -> Result<(EGLConfig, VisualID), EglError> {
+        let config_attributes = attributes.config_attributes();
+        let mut count = 0;
+        if (egl.eglChooseConfig)(
+            display,
+            config_attributes.as_ptr(),
+            ptr::null_mut(),
+            0,
+            &mut count,
+        ) == 0
+        {
+            return Err(egl.last_error("eglChooseConfig"));
+        }
+        let mut configs = vec![ptr::null_mut(); count.max(0) as usize];
+        if (egl.eglChooseConfig)(
+            display,
+            config_attributes.as_ptr(),
+            configs.as_mut_ptr(),
+            count,
+            &mut count,
+        ) == 0
+        {
+            return Err(egl.last_error("eglChooseConfig"));
+        }
+        configs.truncate(count.max(0) as usize);
+
+        let native_visual_id = |config| {
+            let mut native_visual_id = 0;
+            (egl.eglGetConfigAttrib)(display, config, EGL_NATIVE_VISUAL_ID, &mut native_visual_id);
+            native_visual_id as VisualID
+        };
+        configs
+            .iter()
+            .map(|&config| (config, native_visual_id(config)))
+            .find(|&(_, native_visual_id)| native_visual_id == visual_id)
+            .or_else(|| {
+                configs
+                    .iter()
+                    .map(|&config| (config, native_visual_id(config)))
+                    .find(|&(_, native_visual_id)| native_visual_id != 0)
+            })
+            .ok_or(EglError::NoMatchingConfig)
+    }
+
+    fn create_surface(&mut self) -> Result<(), EglError> {
+        let egl = self.egl;
+        let mut window = self.target.window();
+        // The platform variants take a pointer to the X11 window. Each one is only looked up on
+        // the path `platform_display()` took, as eglGetProcAddress may return functions the
+        // implementation does not support.
+        let native_window: *mut c_void = (&mut window as *mut Window).cast();
+        let (surface, function) = unsafe {
+            match self.platform {
+                EglPlatform::Ext => {
+                    let create_platform_window_surface = egl
+                        .proc_address::<CreatePlatformWindowSurfaceExt>(
+                            b"eglCreatePlatformWindowSurfaceEXT\0",
+                        )
+                        .ok_or(EglError::PlatformUnsupported)?;
+                    let surface = trap_errors(self.x_display, |_| {
+                        create_platform_window_surface(
+                            self.display,
+                            self.config,
+                            native_window,
+                            [EGL_NONE].as_ptr(),
+                        )
+                    })?;
+                    (surface, "eglCreatePlatformWindowSurfaceEXT")
+                }
+                EglPlatform::Khr => {
+                    let create_platform_window_surface = egl
+                        .proc_address::<CreatePlatformWindowSurface>(
+                            b"eglCreatePlatformWindowSurface\0",
+                        )
+                        .ok_or(EglError::PlatformUnsupported)?;
+                    let surface = trap_errors(self.x_display, |_| {
+                        create_platform_window_surface(
+                            self.display,
+                            self.config,
+                            native_window,
+                            [EGL_NONE as EGLAttrib].as_ptr(),
+                        )
+                    })?;
+                    (surface, "eglCreatePlatformWindowSurface")
+                }
+            }
+        };
+        if surface.is_null() {
+            return Err(egl.last_error(function));
+        }
+        self.surface = surface;
+        Ok(())
+    }
+
+    fn destroy_surface(&mut self, info: &DrawingSurfaceInfo) {
+        if self.surface.is_null() {
+            return;
+        }
+        self.release_current(info);
+        // The window is gone already if AWT replaced the drawable, so errors are ignored.
+        let _ = unsafe {
+            trap_errors(self.x_display, |_| {
+                (self.egl.eglDestroySurface)(self.display, self.surface)
+            })
+        };
+        self.surface = ptr::null_mut();
+    }
+
+    /// The window the surface renders into.
+    pub fn target(&self) -> &EglTarget {
+        &self.target
+    }
+
+    pub fn display(&self) -> EGLDisplay {
+        self.display
+    }
+
+    pub fn config(&self) -> EGLConfig {
+        self.config
+    }
+
+    pub fn context(&self) -> EGLContext {
+        self.context
+    }
+
+    pub fn surface(&self) -> EGLSurface {
+        self.surface
+    }
+
+    /// Width and height of the window, which follow the bounds of the Canvas.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Binds the context and the surface to the calling thread. `info` must be the information of
+    /// the locked drawing surface the surface was created for.
+    pub fn make_current(&self, _info: &DrawingSurfaceInfo) -> Result<(), EglError> {
+        let made_current = unsafe {
+            (self.egl.eglMakeCurrent)(self.display, self.surface, self.surface, self.context)
+        };
+        if made_current == 0 {
+            return Err(self.egl.last_error("eglMakeCurrent"));
+        }
+        Ok(())
+    }
+
+    /// Unbinds the context if it is current on the calling thread.
+    pub fn release_current(&self, _info: &DrawingSurfaceInfo) {
+        unsafe {
+            if (self.egl.eglGetCurrentContext)() == self.context {
+                (self.egl.eglMakeCurrent)(
+                    self.display,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                );
+            }
+        }
+    }
+
+    /// Looks up a client API function, for use with loaders such as `gl::load_with`.
+    pub fn proc_address(&self, name: &CStr) -> *const c_void {
+        unsafe { (self.egl.eglGetProcAddress)(name.as_ptr()) }
+    }
+
+    /// Sets the swap interval of the current surface. Zero disables vsync.
+    pub fn set_swap_interval(
+        &self,
+        _info: &DrawingSurfaceInfo,
+        interval: i32,
+    ) -> Result<(), EglError> {
+        if unsafe { (self.egl.eglSwapInterval)(self.display, interval) } == 0 {
+            return Err(self.egl.last_error("eglSwapInterval"));
+        }
+        Ok(())
+    }
+
+    /// Presents the back buffer.
+    pub fn swap_buffers(&self, _info: &DrawingSurfaceInfo) -> Result<(), EglError> {
+        if unsafe { (self.egl.eglSwapBuffers)(self.display, self.surface) } == 0 {
+            return Err(self.egl.last_error("eglSwapBuffers"));
+        }
+        Ok(())
+    }
+
+    /// Follows changes reported by [DrawingSurface::lock()]. The window surface is re-created on
+    /// [DrawingSurfaceLockResult::SURFACE_CHANGED], and the child window, if any, is resized with
+    /// the Canvas. Make the context current again after this function returns.
+    ///
+    /// [DrawingSurface::lock()]: crate::ds::DrawingSurface::lock()
+    pub fn update(
+        &mut self,
+        info: &DrawingSurfaceInfo,
+        lock_result: DrawingSurfaceLockResult,
+    ) -> Result<(), EglError> {
+        let bounds = info.bounds();
+        self.size = (
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+        );
+        let surface_changed = lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED);
+        if surface_changed {
+            // The old window may be gone already, so the surface must not outlive this call.
+            self.destroy_surface(info);
+        }
+        match &mut self.target {
+            EglTarget::CanvasDrawable(window) => {
+                if surface_changed {
+                    *window = info.try_platform_info()?.window()?;
+                }
+            }
+            EglTarget::ChildWindow(child) => {
+                child.update(info, lock_result)?;
+            }
+        }
+        if surface_changed {
+            self.create_surface()?;
+        }
+        Ok(())
+    }
+
+    /// Destroys the window surface, the context and the child window if there is one. `info` must
+    /// be the information of the locked drawing surface the surface was created for.
+    pub fn destroy(mut self, info: &DrawingSurfaceInfo) {
+        self.destroy_surface(info);
+        let context = mem::replace(&mut self.context, ptr::null_mut());
+        unsafe { (self.egl.eglDestroyContext)(self.display, context) };
+        mem::replace(&mut self.target, EglTarget::CanvasDrawable(0)).destroy(info);
+    }
+}
+
+impl Drop for EglSurface {
+    fn drop(&mut self) {
+        debug_assert!(
+            self.context.is_null() || thread::panicking(),
+            "EglSurface dropped without EglSurface::destroy(), leaking its EGL context"
+        );
+    }
+}
@@ -0,0 +1,490 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [GlxSurface], an OpenGL context rendering into an AWT Canvas.
+
+use std::error::Error;
+use std::ffi::{c_int, c_void, CStr};
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::thread;
+
+use x11_dl::error::OpenError;
+use x11_dl::glx::arb::*;
+use x11_dl::glx::*;
+use x11_dl::xlib::{Display, True, VisualID, Window, XVisualInfo};
+
+use crate::ds::DrawingSurfaceLockResult;
+use crate::dsi::{DrawingSurfaceInfo, PlatformInfoError};
+use crate::md::unix::{
+    trap_errors, xlib, LazyLibrary, WindowRetrievalError, X11ChildWindow, X11ChildWindowConfig,
+    X11Error,
+};
+
+type GlxCreateContextAttribsArb =
+    unsafe extern "C" fn(*mut Display, GLXFBConfig, GLXContext, c_int, *const c_int) -> GLXContext;
+
+/// Returns the process-wide [Glx] handle, loading `libGL` on the first call.
+pub fn glx() -> Result<&'static Glx, OpenError> {
+    static GLX: LazyLibrary<Glx> = LazyLibrary::new();
+    GLX.get_or_load(Glx::open)
+}
+
+/// OpenGL profile of a [GlxSurface].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GlxProfile {
+    Core,
+    Compatibility,
+}
+
+/// Requested attributes of a [GlxSurface]. The defaults ask for a double-buffered OpenGL 3.3 core
+/// context, which Mesa's llvmpipe provides without a GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlxContextAttributes {
+    pub major_version: i32,
+    pub minor_version: i32,
+    pub profile: GlxProfile,
+    pub debug: bool,
+    pub forward_compatible: bool,
+    pub double_buffer: bool,
+    pub alpha_size: i32,
+    pub depth_size: i32,
+    pub stencil_size: i32,
+    /// Number of multisample samples. Zero disables multisampling.
+    pub samples: i32,
+}
+
+impl Default for GlxContextAttributes {
+    fn default() -> Self {
+        Self {
+            major_version: 3,
+            minor_version: 3,
+            profile: GlxProfile::Core,
+            debug: false,
+            forward_compatible: false,
+            double_buffer: true,
+            alpha_size: 0,
+            depth_size: 24,
+            stencil_size: 8,
+            samples: 0,
+        }
+    }
+}
+
+impl GlxContextAttributes {
+    fn config_attributes(&self) -> Vec<c_int> {
+        let mut attributes = vec![
+            GLX_X_RENDERABLE,
+            True,
+            GLX_DRAWABLE_TYPE,
+            GLX_WINDOW_BIT,
+            GLX_RENDER_TYPE,
+            GLX_RGBA_BIT,
+            GLX_DOUBLEBUFFER,
+            self.double_buffer as c_int,
+            GLX_ALPHA_SIZE,
+            self.alpha_size,
+            GLX_DEPTH_SIZE,
+            self.depth_size,
+            GLX_STENCIL_SIZE,
+            self.stencil_size,
+        ];
+        if self.samples > 0 {
+            attributes.extend([GLX_SAMPLE_BUFFERS, 1, GLX_SAMPLES, self.samples]);
+        }
+        attributes.push(0);
+        attributes
+    }
+
+    fn context_attributes(&self) -> [c_int; 9] {
+        let mut flags = 0;
+        if self.debug {
+            flags |= GLX_CONTEXT_DEBUG_BIT_ARB;
+        }
+        if self.forward_compatible {
+            flags |= GLX_CONTEXT_FORWARD_COMPATIBLE_BIT_ARB;
+        }
+        [
+            GLX_CONTEXT_MAJOR_VERSION_ARB,
+            self.major_version,
+            GLX_CONTEXT_MINOR_VERSION_ARB,
+            self.minor_version,
+            GLX_CONTEXT_PROFILE_MASK_ARB,
+            match self.profile {
+                GlxProfile::Core => GLX_CONTEXT_CORE_PROFILE_BIT_ARB,
+                GlxProfile::Compatibility => GLX_CONTEXT_COMPATIBILITY_PROFILE_BIT_ARB,
+            },
+            GLX_CONTEXT_FLAGS_ARB,
+            flags,
+            0,
+        ]
+    }
+}
+
+/// Error returned by [GlxSurface].
+#[derive(Debug, Clone)]
+pub enum GlxError {
+    GlxOpenFailed(OpenError),
+    /// The display does not support GLX 1.3 or later.
+    UnsupportedVersion,
+    /// No FBConfig satisfies the requested attributes.
+    NoMatchingConfig,
+    /// The driver rejected the requested context version, profile or flags.
+    ContextCreationFailed,
+    MakeCurrentFailed,
+    Window(WindowRetrievalError),
+}
+
+impl fmt::Display for GlxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlxError::GlxOpenFailed(open_error) => write!(f, "failed to open GLX: {open_error}"),
+            GlxError::UnsupportedVersion => write!(f, "GLX 1.3 or later is not available"),
+            GlxError::NoMatchingConfig => write!(f, "no GLX FBConfig matches the attributes"),
+            GlxError::ContextCreationFailed => write!(f, "failed to create a GLX context"),
+            GlxError::MakeCurrentFailed => write!(f, "failed to make the GLX context current"),
+            GlxError::Window(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for GlxError {}
+
+impl From<WindowRetrievalError> for GlxError {
+    fn from(value: WindowRetrievalError) -> Self {
+        Self::Window(value)
+    }
+}
+
+impl From<X11Error> for GlxError {
+    fn from(value: X11Error) -> Self {
+        Self::Window(value.into())
+    }
+}
+
+impl From<PlatformInfoError> for GlxError {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::Window(value.into())
+    }
+}
+
+/// The drawable a [GlxSurface] renders into.
+#[derive(Debug)]
+pub enum GlxTarget {
+    /// An FBConfig matched the visual AWT chose, so the context renders into the Canvas drawable
+    /// directly.
+    CanvasDrawable(Window),
+    /// No FBConfig matched the visual of the Canvas, so the context renders into a child window
+    /// created with the visual of the chosen FBConfig.
+    ChildWindow(X11ChildWindow),
+}
+
+impl GlxTarget {
+    pub fn window(&self) -> Window {
+        match self {
+            GlxTarget::CanvasDrawable(window) => *window,
+            GlxTarget::ChildWindow(child) => child.window(),
+        }
+    }
+
+    fn destroy(self, info: &DrawingSurfaceInfo) {
+        if let GlxTarget::ChildWindow(child) = self {
+            child.destroy(info);
+        }
+    }
+}
+
+/// An OpenGL context created for the FBConfig matching [X11DrawingSurfaceInfo::visual_id()],
+/// falling back to an [X11ChildWindow] when no FBConfig matches.
+///
+/// GLX requests go through AWT's display connection, so methods that issue them take the
+/// [DrawingSurfaceInfo] of the locked drawing surface. Dropping the surface sends no requests and
+/// leaks the context; call [GlxSurface::destroy()] while the surface is locked instead. Debug
+/// builds panic when a surface is dropped without being destroyed.
+///
+/// [X11DrawingSurfaceInfo::visual_id()]: crate::md::unix::X11DrawingSurfaceInfo::visual_id()
+pub struct GlxSurface {
+    glx: &'static Glx,
+    display: *mut Display,
+    config: GLXFBConfig,
+    context: GLXContext,
+    target: GlxTarget,
+    size: (u32, u32),
+}
+
+impl fmt::Debug for GlxSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlxSurface")
+            .field("display", &self.display)
+            .field("config", &self.config)
+            .field("context", &self.context)
+            .field("target", &self.target)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl GlxSurface {
+    /// Chooses an FBConfig, creates a context and a child window if needed. The context is not
+    /// made current.
+    pub fn new(
+        info: &DrawingSurfaceInfo,
+        attributes: &GlxContextAttributes,
+    ) -> Result<Self, GlxError> {
+        let glx = glx().map_err(GlxError::GlxOpenFailed)?;
+        let platform_info = info.try_platform_info()?;
+        let display = platform_info.display();
+        let window = platform_info.window()?;
+        let screen = platform_info.screen_number()?;
+
+        let (mut major, mut minor) = (0, 0);
+        let has_glx = unsafe {
+            trap_errors(display, |_| {
+                (glx.glXQueryVersion)(display, &mut major, &mut minor)
+            })?
+        } != 0;
+        if !has_glx || (major, minor) < (1, 3) {
+            return Err(GlxError::UnsupportedVersion);
+        }
+
+        let (config, matches_canvas) =
+            Self::choose_config(glx, display, screen, platform_info.visual_id(), attributes)?;
+        let target = if matches_canvas {
+            GlxTarget::CanvasDrawable(window)
+        } else {
+            let visual_info = unsafe { Self::visual_info(glx, display, config)? };
+            let child_config = X11ChildWindowConfig::from_visual_info(&visual_info);
+            let mut child = X11ChildWindow::new(info, child_config)?;
+            if let Err(error) = child.set_visible(info, true) {
+                child.destroy(info);
+                return Err(error.into());
+            }
+            GlxTarget::ChildWindow(child)
+        };
+
+        let context = match Self::create_context(glx, display, config, attributes) {
+            Ok(context) => context,
+            Err(error) => {
+                target.destroy(info);
+                return Err(error);
+            }
+        };
+        let bounds = info.bounds();
+        Ok(Self {
+            glx,
+            display,
+            config,
+            context,
+            target,
+            size: (
+                u32::try_from(bounds.width).unwrap_or_default(),
+                u32::try_from(bounds.height).unwrap_or_default(),
+            ),
+        })
+    }
+
+    unsafe fn visual_info(
+        glx: &Glx,
+        display: *mut Display,
+        config: GLXFBConfig,
+    ) -> Result<XVisualInfo, GlxError> {
+        let visual_info = (glx.glXGetVisualFromFBConfig)(display, config);
+        if visual_info.is_null() {
+            return Err(GlxError::NoMatchingConfig);
+        }
+        let result = *visual_info;
+        (xlib().map_err(X11Error::from)?.XFree)(visual_info.cast());
+        Ok(result)
+    }
+
+    /// Chooses an FBConfig on `screen`, the screen of the Canvas, which is not necessarily the
+    /// default screen of the display.
+    fn choose_config(
+        glx: &Glx,
+        display: *mut Display,
+        screen: c_int,
+        visual_id: VisualID,
+        attributes: &GlxContextAttributes,
+    ) -> Result<(GLXFBConfig, bool), GlxError> {
+        let config_attributes = attributes.config_attributes();
+        let chosen = unsafe {
+            trap_errors(display, |xlib| {
+                let mut count = 0;
+                let configs = (glx.glXChooseFBConfig)(
+                    display,
+                    screen,
+                    config_attributes.as_ptr(),
+                    &mut count,
+                );
+                if configs.is_null() {
+                    return None;
+                }
+                let candidates = slice::from_raw_parts(configs, count.max(0) as usize);
+                let matching = candidates.iter().copied().find(|&config| {
+                    let mut config_visual_id = 0;
+                    (glx.glXGetFBConfigAttrib)(
+                        display,
+                        config,
+                        GLX_VISUAL_ID,
+                        &mut config_visual_id,
+                    );
+                    config_visual_id as VisualID == visual_id
+                });
+                let chosen = match matching {
+                    Some(config) => Some((config, true)),
+                    None => candidates.first().map(|&config| (config, false)),
+                };
+                (xlib.XFree)(configs.cast());
+                chosen
+            })?
+        };
+        chosen.ok_or(GlxError::NoMatchingConfig)
+    }
+
+    fn create_context(
+        glx: &Glx,
+        display: *mut Display,
+        config: GLXFBConfig,
+        attributes: &GlxContextAttributes,
+    ) -> Result<GLXContext, GlxError> {
+        let create_context_attribs = unsafe {
+            (glx.glXGetProcAddressARB)(b"glXCreateContextAttribsARB\0".as_ptr()).map(|f| {
+                std::mem::transmute::<unsafe extern "C" fn(), GlxCreateContextAttribsArb>(f)
+            })
+        };
+        let context_attributes = attributes.context_attributes();
+        let context = unsafe {
+            trap_errors(display, |_| match create_context_attribs {
+                Some(create_context_attribs) => create_context_attribs(
+                    display,
+                    config,
+                    ptr::null_mut(),
+                    True,
+                    context_attributes.as_ptr(),
+                ),
+                // Without GLX_ARB_create_context only legacy contexts can be created.
+                None if attributes.profile == GlxProfile::Compatibility => {
+                    (glx.glXCreateNewContext)(display, config, GLX_RGBA_TYPE, ptr::null_mut(), True)
+                }
+                None => ptr::null_mut(),
+            })
+        };
+        match context {
+            Ok(context) if !context.is_null() => Ok(context),
+            _ => Err(GlxError::ContextCreationFailed),
+        }
+    }
+
+    /// The drawable the context renders into.
+    pub fn target(&self) -> &GlxTarget {
+        &self.target
+    }
+
+    pub fn context(&self) -> GLXContext {
+        self.context
+    }
+
+    pub fn config(&self) -> GLXFBConfig {
+        self.config
+    }
+
+    /// Width and height of the drawable, which follow the bounds of the Canvas.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Binds the context and the drawable to the calling thread. `info` must be the information of
+    /// the locked drawing surface the context was created for.
+    pub fn make_current(&self, _info: &DrawingSurfaceInfo) -> Result<(), GlxError> {
+        let display = self.display;
+        let window = self.target.window();
+        let context = self.context;
+        let made_current = unsafe {
+            trap_errors(display, |_| {
+                (self.glx.glXMakeContextCurrent)(display, window, window, context)
+            })
+        };
+        match made_current {
+            Ok(status) if status != 0 => Ok(()),
+            _ => Err(GlxError::MakeCurrentFailed),
+        }
+    }
+
+    /// Unbinds the context if it is current on the calling thread.
+    pub fn release_current(&self, _info: &DrawingSurfaceInfo) {
+        let display = self.display;
+        unsafe {
+            if (self.glx.glXGetCurrentContext)() == self.context {
+                // Releasing only fails if the drawable is gone already, which is fine.
+                let _ = trap_errors(display, |_| {
+                    (self.glx.glXMakeContextCurrent)(display, 0, 0, ptr::null_mut())
+                });
+            }
+        }
+    }
+
+    /// Looks up an OpenGL function, for use with loaders such as `gl::load_with`.
+    pub fn proc_address(&self, name: &CStr) -> *const c_void {
+        unsafe {
+            (self.glx.glXGetProcAddressARB)(name.as_ptr().cast())
+                .map_or(ptr::null(), |f| f as *const c_void)
+        }
+    }
+
+    /// Presents the back buffer. Fails if AWT destroyed the drawable since the last
+    /// [GlxSurface::update()].
+    pub fn swap_buffers(&self, _info: &DrawingSurfaceInfo) -> Result<(), GlxError> {
+        let display = self.display;
+        let window = self.target.window();
+        unsafe { trap_errors(display, |_| (self.glx.glXSwapBuffers)(display, window))? };
+        Ok(())
+    }
+
+    /// Follows changes reported by [DrawingSurface::lock()]. The drawable is replaced when AWT
+    /// re-created the Canvas drawable, and the child window, if any, is resized with the Canvas.
+    /// Make the context current again after this function returns.
+    ///
+    /// [DrawingSurface::lock()]: crate::ds::DrawingSurface::lock()
+    pub fn update(
+        &mut self,
+        info: &DrawingSurfaceInfo,
+        lock_result: DrawingSurfaceLockResult,
+    ) -> Result<(), GlxError> {
+        let bounds = info.bounds();
+        self.size = (
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+        );
+        match &mut self.target {
+            GlxTarget::CanvasDrawable(window) => {
+                if lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED) {
+                    *window = info.try_platform_info()?.window()?;
+                }
+            }
+            GlxTarget::ChildWindow(child) => {
+                child.update(info, lock_result)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Releases and destroys the context, and destroys the child window if there is one. `info`
+    /// must be the information of the locked drawing surface the context was created for.
+    pub fn destroy(mut self, info: &DrawingSurfaceInfo) {
+        self.release_current(info);
+        let display = self.display;
+        let context = mem::replace(&mut self.context, ptr::null_mut());
+        let _ = unsafe { trap_errors(display, |_| (self.glx.glXDestroyContext)(display, context)) };
+        mem::replace(&mut self.target, GlxTarget::CanvasDrawable(0)).destroy(info);
+    }
+}
+
+impl Drop for GlxSurface {
+    fn drop(&mut self) {
+        debug_assert!(
+            self.context.is_null() || thread::panicking(),
+            "GlxSurface dropped without GlxSurface::destroy(), leaking its GLX context"
+        );
+    }
+}
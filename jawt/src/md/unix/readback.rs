@@ -0,0 +1,232 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements reading back the pixels of an X11 drawable with `XGetImage`.
+
+use std::ffi::c_ulong;
+use std::ptr;
+
+use x11_dl::xlib::*;
+
+use crate::dsi::DrawingSurfaceInfo;
+use crate::md::unix::{
+    trap_errors, visual_info, X11DrawingSurfaceInfo, X11Error, X11Geometry, X11SurfaceKind,
+};
+use crate::{Rect, RgbaImage};
+
+/// Converts pixel values of a TrueColor or DirectColor visual to RGBA.
+struct ChannelMasks {
+    red: c_ulong,
+    green: c_ulong,
+    blue: c_ulong,
+}
+
+impl ChannelMasks {
+    fn channel(pixel: c_ulong, mask: c_ulong) -> u8 {
+        let shift = mask.trailing_zeros();
+        let max = mask >> shift;
+        (((pixel & mask) >> shift) * 255 / max) as u8
+    }
+
+    fn to_rgba(&self, pixel: c_ulong) -> [u8; 4] {
+        [
+            Self::channel(pixel, self.red),
+            Self::channel(pixel, self.green),
+            Self::channel(pixel, self.blue),
+            u8::MAX,
+        ]
+    }
+}
+
+/// Returns the area of `window` inside all of its ancestors up to the root window, in the
+/// coordinates of `window`. `XGetImage` fails with `BadMatch` if a rectangle of a window extends
+/// past it. Failures are reported through the active [XErrorTrap].
+///
+/// [XErrorTrap]: crate::md::unix::XErrorTrap
+fn viewable_area(xlib: &Xlib, display: *mut Display, window: Window) -> Option<Rect> {
+    let geometry = X11Geometry::query(xlib, display, window);
+    let mut area = Rect {
+        x: 0,
+        y: 0,
+        width: geometry.width as i32,
+        height: geometry.height as i32,
+    };
+    let mut current = window;
+    let mut current_geometry = geometry;
+    // The origin of `current` in the coordinates of `window`.
+    let (mut x, mut y) = (0, 0);
+    loop {
+        let (mut root, mut parent) = (0, 0);
+        let mut children = ptr::null_mut();
+        let mut child_count = 0;
+        let status = unsafe {
+            (xlib.XQueryTree)(
+                display,
+                current,
+                &mut root,
+                &mut parent,
+                &mut children,
+                &mut child_count,
+            )
+        };
+        if !children.is_null() {
+            unsafe { (xlib.XFree)(children.cast()) };
+        }
+        if status == 0 || parent == 0 {
+            return Some(area);
+        }
+
+        // The position of a window is the outer corner of its border.
+        let border_width = current_geometry.border_width as i32;
+        x -= current_geometry.x + border_width;
+        y -= current_geometry.y + border_width;
+        let parent_geometry = X11Geometry::query(xlib, display, parent);
+        area = area.intersection(&Rect {
+            x,
+            y,
+            width: parent_geometry.width as i32,
+            height: parent_geometry.height as i32,
+        })?;
+        current = parent;
+        current_geometry = parent_geometry;
+    }
+}
+
+impl X11DrawingSurfaceInfo {
+    /// Reads back the pixels of the drawable inside [DrawingSurfaceInfo::bounds()] with
+    /// `XGetImage`. The returned image has the size of the bounds. Pixels inside
+    /// [DrawingSurfaceInfo::clip()] are opaque; pixels outside of it, or outside of the drawable,
+    /// are left transparent black. Use [ImageMask::from_clip()] to compare only the read pixels.
+    ///
+    /// Parts of a window outside of the screen or of one of its ancestors have no contents and
+    /// are left transparent black as well.
+    ///
+    /// Only call this while the drawing surface is locked. Content of windows covered by other
+    /// windows is undefined unless the server keeps backing store for them.
+    ///
+    /// [ImageMask::from_clip()]: crate::ImageMask::from_clip()
+    pub fn read_pixels(&self, dsi: &DrawingSurfaceInfo) -> Result<RgbaImage, X11Error> {
+        let display = self.0.display;
+        let drawable = self.0.drawable;
+        let bounds = dsi.bounds();
+        let mut image = RgbaImage::new(
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+        );
+
+        // Clip rectangles share the coordinate space of the bounds, whose origin is the origin of
+        // the drawable.
+        let geometry = self.geometry()?;
+        let mut extent = Rect {
+            x: 0,
+            y: 0,
+            width: i32::try_from(geometry.width.min(image.width())).unwrap_or_default(),
+            height: i32::try_from(geometry.height.min(image.height())).unwrap_or_default(),
+        };
+        if let X11SurfaceKind::Window(window) = self.surface_kind()? {
+            let viewable =
+                unsafe { trap_errors(display, |xlib| viewable_area(xlib, display, window))? };
+            extent = match viewable.and_then(|viewable| viewable.intersection(&extent)) {
+                Some(extent) => extent,
+                None => return Ok(image),
+            };
+        }
+        let rects = dsi
+            .clip()
+            .iter()
+            .filter_map(|clip| clip.translate(-bounds.x, -bounds.y).intersection(&extent))
+            .collect::<Vec<_>>();
+        if rects.is_empty() {
+            return Ok(image);
+        }
+
+        let masks = unsafe { visual_info(display, self.0.visualID)? }
+            .filter(|info| info.class == TrueColor || info.class == DirectColor)
+            .map(|info| ChannelMasks {
+                red: info.red_mask,
+                green: info.green_mask,
+                blue: info.blue_mask,
+            })
+            .filter(|masks| masks.red != 0 && masks.green != 0 && masks.blue != 0);
+        let colormap = self.0.colormapID;
+
+        unsafe {
+            trap_errors(display, |xlib| {
+                for rect in rects {
+                    let ximage = (xlib.XGetImage)(
+                        display,
+                        drawable,
+                        rect.x,
+                        rect.y,
+                        rect.width as _,
+                        rect.height as _,
+                        (xlib.XAllPlanes)(),
+                        ZPixmap,
+                    );
+                    if ximage.is_null() {
+                        continue;
+                    }
+
+                    let mut pixels = Vec::with_capacity(rect.width as usize * rect.height as usize);
+                    for y in 0..rect.height {
+                        for x in 0..rect.width {
+                            pixels.push((xlib.XGetPixel)(ximage, x, y));
+                        }
+                    }
+                    (xlib.XDestroyImage)(ximage);
+
+                    let rgba = match &masks {
+                        Some(masks) => pixels
+                            .iter()
+                            .map(|&pixel| masks.to_rgba(pixel))
+                            .collect::<Vec<_>>(),
+                        // Indexed visuals need the colormap to resolve pixel values.
+                        None => {
+                            let mut colors = pixels
+                                .iter()
+                                .map(|&pixel| XColor {
+                                    pixel,
+                                    red: 0,
+                                    green: 0,
+                                    blue: 0,
+                                    flags: 0,
+                                    pad: 0,
+                                })
+                                .collect::<Vec<_>>();
+                            (xlib.XQueryColors)(
+                                display,
+                                colormap,
+                                colors.as_mut_ptr(),
+                                colors.len() as _,
+                            );
+                            colors
+                                .iter()
+                                .map(|color| {
+                                    [
+                                        (color.red >> 8) as u8,
+                                        (color.green >> 8) as u8,
+                                        (color.blue >> 8) as u8,
+                                        u8::MAX,
+                                    ]
+                                })
+                                .collect::<Vec<_>>()
+                        }
+                    };
+
+                    for (index, pixel) in rgba.into_iter().enumerate() {
+                        let x = rect.x + index as i32 % rect.width;
+                        let y = rect.y + index as i32 / rect.width;
+                        image.set_pixel(x as u32, y as u32, pixel);
+                    }
+                }
+            })?;
+        }
+        Ok(image)
+    }
+}
+
+impl DrawingSurfaceInfo<'_> {
+    /// Reads back the pixels of the drawing surface. See [X11DrawingSurfaceInfo::read_pixels()].
+    pub fn read_pixels(&self) -> Result<RgbaImage, X11Error> {
+        self.try_platform_info()?.read_pixels(self)
+    }
+}
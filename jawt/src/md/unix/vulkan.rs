@@ -0,0 +1,399 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements [VulkanSurface], a `VkSurfaceKHR` for the window of an AWT Canvas on X11.
+
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+
+use ash::vk;
+use x11_dl::xlib::{Display, Window};
+
+use crate::ds::DrawingSurfaceLockResult;
+use crate::dsi::{DrawingSurfaceInfo, PlatformInfoError};
+use crate::md::unix::WindowRetrievalError;
+
+/// The window-system extension a [VulkanSurface] is created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VulkanSurfaceExtension {
+    /// `VK_KHR_xlib_surface`, using AWT's Xlib display.
+    Xlib,
+    /// `VK_KHR_xcb_surface`, using the XCB connection behind AWT's Xlib display.
+    #[cfg(feature = "xcb")]
+    Xcb,
+}
+
+impl VulkanSurfaceExtension {
+    /// Name of the window-system extension.
+    pub fn name(self) -> &'static CStr {
+        match self {
+            VulkanSurfaceExtension::Xlib => ash::khr::xlib_surface::NAME,
+            #[cfg(feature = "xcb")]
+            VulkanSurfaceExtension::Xcb => ash::khr::xcb_surface::NAME,
+        }
+    }
+
+    /// Instance extensions to enable in `VkInstanceCreateInfo` to create surfaces with this
+    /// extension.
+    pub fn required_instance_extensions(self) -> [&'static CStr; 2] {
+        [ash::khr::surface::NAME, self.name()]
+    }
+
+    /// Returns the first extension in the order of preference that the Vulkan implementation
+    /// supports, preferring XCB when it is enabled.
+    pub fn choose(entry: &ash::Entry) -> Result<Option<Self>, VulkanSurfaceError> {
+        let properties =
+            unsafe { entry.enumerate_instance_extension_properties(None) }.map_err(|result| {
+                VulkanSurfaceError::vulkan("vkEnumerateInstanceExtensionProperties", result)
+            })?;
+        let supports = |name: &CStr| {
+            properties
+                .iter()
+                .any(|property| property.extension_name_as_c_str() == Ok(name))
+        };
+        if !supports(ash::khr::surface::NAME) {
+            return Ok(None);
+        }
+        #[cfg(feature = "xcb")]
+        if supports(ash::khr::xcb_surface::NAME) {
+            return Ok(Some(VulkanSurfaceExtension::Xcb));
+        }
+        if supports(ash::khr::xlib_surface::NAME) {
+            return Ok(Some(VulkanSurfaceExtension::Xlib));
+        }
+        Ok(None)
+    }
+}
+
+/// Error returned by [VulkanSurface].
+#[derive(Debug, Clone)]
+pub enum VulkanSurfaceError {
+    /// A Vulkan function failed with the given result.
+    Vulkan {
+        function: &'static str,
+        result: vk::Result,
+    },
+    Window(WindowRetrievalError),
+    /// The platform information of the drawing surface can't be used as X11.
+    PlatformInfo(PlatformInfoError),
+}
+
+impl VulkanSurfaceError {
+    fn vulkan(function: &'static str, result: vk::Result) -> Self {
+        Self::Vulkan { function, result }
+    }
+}
+
+impl fmt::Display for VulkanSurfaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VulkanSurfaceError::Vulkan { function, result } => {
+                write!(f, "{function} failed with {result}")
+            }
+            VulkanSurfaceError::Window(error) => error.fmt(f),
+            VulkanSurfaceError::PlatformInfo(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for VulkanSurfaceError {}
+
+impl From<WindowRetrievalError> for VulkanSurfaceError {
+    fn from(value: WindowRetrievalError) -> Self {
+        Self::Window(value)
+    }
+}
+
+impl From<PlatformInfoError> for VulkanSurfaceError {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::PlatformInfo(value)
+    }
+}
+
+/// What a renderer must do after [VulkanSurface::update()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VulkanSurfaceUpdate {
+    /// The swapchain is still valid.
+    Unchanged,
+    /// The Canvas was resized. Re-create the swapchain with
+    /// [VulkanSurface::swapchain_extent()].
+    RebuildSwapchain,
+    /// The Canvas got a new window. Destroy the swapchain, call [VulkanSurface::recreate()], then
+    /// create a new swapchain.
+    RecreateSurface,
+}
+
+/// Capabilities, formats and present modes a physical device supports for a [VulkanSurface].
+#[derive(Debug, Clone)]
+pub struct VulkanSurfaceSupport {
+    pub capabilities: vk::SurfaceCapabilitiesKHR,
+    pub formats: Vec<vk::SurfaceFormatKHR>,
+    pub present_modes: Vec<vk::PresentModeKHR>,
+}
+
+#[derive(Clone)]
+enum PlatformSurface {
+    Xlib(ash::khr::xlib_surface::Instance),
+    #[cfg(feature = "xcb")]
+    Xcb(ash::khr::xcb_surface::Instance),
+}
+
+/// A `VkSurfaceKHR` created for the window of an AWT Canvas.
+///
+/// Creating, re-creating and querying the surface may issue requests on AWT's display connection,
+/// so they must be done while the drawing surface is locked. The `VkInstance` the surface was
+/// created with must outlive it, and every swapchain created for the surface must be destroyed
+/// before the surface is dropped or re-created.
+pub struct VulkanSurface {
+    surface_fn: ash::khr::surface::Instance,
+    platform: PlatformSurface,
+    surface: vk::SurfaceKHR,
+    display: *mut Display,
+    window: Window,
+    size: (u32, u32),
+}
+
+impl fmt::Debug for VulkanSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VulkanSurface")
+            .field("extension", &self.extension())
+            .field("surface", &self.surface)
+            .field("display", &self.display)
+            .field("window", &self.window)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl VulkanSurface {
+    /// Creates a surface for the window of the Canvas with `extension`.
+    ///
+    /// # Safety
+    ///
+    /// `instance` must have been created from `entry` with the extensions returned by
+    /// [VulkanSurfaceExtension::required_instance_extensions()] enabled, and must outlive the
+    /// returned surface.
+    pub unsafe fn new(
+        entry: &ash::Entry,
+        instance: &ash::Instance,
+        info: &DrawingSurfaceInfo,
+        extension: VulkanSurfaceExtension,
+    ) -> Result<Self, VulkanSurfaceError> {
+        let platform_info = info.try_platform_info()?;
+        let platform = match extension {
+            VulkanSurfaceExtension::Xlib => {
+                PlatformSurface::Xlib(ash::khr::xlib_surface::Instance::new(entry, instance))
+            }
+            #[cfg(feature = "xcb")]
+            VulkanSurfaceExtension::Xcb => {
+                PlatformSurface::Xcb(ash::khr::xcb_surface::Instance::new(entry, instance))
+            }
+        };
+        let mut surface = Self {
+            surface_fn: ash::khr::surface::Instance::new(entry, instance),
+            platform,
+            surface: vk::SurfaceKHR::null(),
+            display: platform_info.display(),
+            window: 0,
+            size: (0, 0),
+        };
+        surface.recreate(info)?;
+        Ok(surface)
+    }
+
+    /// The window-system extension the surface was created with.
+    pub fn extension(&self) -> VulkanSurfaceExtension {
+        match self.platform {
+            PlatformSurface::Xlib(_) => VulkanSurfaceExtension::Xlib,
+            #[cfg(feature = "xcb")]
+            PlatformSurface::Xcb(_) => VulkanSurfaceExtension::Xcb,
+        }
+    }
+
+    pub fn surface(&self) -> vk::SurfaceKHR {
+        self.surface
+    }
+
+    /// The window the surface presents to.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Width and height of the Canvas as of the last [VulkanSurface::update()].
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Returns whether the queue family of `physical_device` can present to the surface.
+    pub fn supports_present(
+        &self,
+        physical_device: vk::PhysicalDevice,
+        queue_family_index: u32,
+    ) -> Result<bool, VulkanSurfaceError> {
+        unsafe {
+            self.surface_fn.get_physical_device_surface_support(
+                physical_device,
+                queue_family_index,
+                self.surface,
+            )
+        }
+        .map_err(|result| {
+            VulkanSurfaceError::vulkan("vkGetPhysicalDeviceSurfaceSupportKHR", result)
+        })
+    }
+
+    pub fn capabilities(
+        &self,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<vk::SurfaceCapabilitiesKHR, VulkanSurfaceError> {
+        unsafe {
+            self.surface_fn
+                .get_physical_device_surface_capabilities(physical_device, self.surface)
+        }
+        .map_err(|result| {
+            VulkanSurfaceError::vulkan("vkGetPhysicalDeviceSurfaceCapabilitiesKHR", result)
+        })
+    }
+
+    pub fn formats(
+        &self,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Vec<vk::SurfaceFormatKHR>, VulkanSurfaceError> {
+        unsafe {
+            self.surface_fn
+                .get_physical_device_surface_formats(physical_device, self.surface)
+        }
+        .map_err(|result| {
+            VulkanSurfaceError::vulkan("vkGetPhysicalDeviceSurfaceFormatsKHR", result)
+        })
+    }
+
+    pub fn present_modes(
+        &self,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Vec<vk::PresentModeKHR>, VulkanSurfaceError> {
+        unsafe {
+            self.surface_fn
+                .get_physical_device_surface_present_modes(physical_device, self.surface)
+        }
+        .map_err(|result| {
+            VulkanSurfaceError::vulkan("vkGetPhysicalDeviceSurfacePresentModesKHR", result)
+        })
+    }
+
+    /// Queries capabilities, formats and present modes at once.
+    pub fn support(
+        &self,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<VulkanSurfaceSupport, VulkanSurfaceError> {
+        Ok(VulkanSurfaceSupport {
+            capabilities: self.capabilities(physical_device)?,
+            formats: self.formats(physical_device)?,
+            present_modes: self.present_modes(physical_device)?,
+        })
+    }
+
+    /// Extent for a new swapchain: the current extent of the surface if the implementation reports
+    /// one, otherwise the size of the Canvas clamped to the supported range.
+    pub fn swapchain_extent(&self, capabilities: &vk::SurfaceCapabilitiesKHR) -> vk::Extent2D {
+        if capabilities.current_extent.width != u32::MAX {
+            return capabilities.current_extent;
+        }
+        vk::Extent2D {
+            width: self.size.0.clamp(
+                capabilities.min_image_extent.width,
+                capabilities.max_image_extent.width,
+            ),
+            height: self.size.1.clamp(
+                capabilities.min_image_extent.height,
+                capabilities.max_image_extent.height,
+            ),
+        }
+    }
+
+    /// Follows changes reported by [DrawingSurface::lock()] and returns what the renderer must do
+    /// with its swapchain.
+    ///
+    /// [DrawingSurface::lock()]: crate::ds::DrawingSurface::lock()
+    pub fn update(
+        &mut self,
+        info: &DrawingSurfaceInfo,
+        lock_result: DrawingSurfaceLockResult,
+    ) -> VulkanSurfaceUpdate {
+        let size = Self::bounds_size(info);
+        let resized = size != self.size;
+        self.size = size;
+        if lock_result.contains(DrawingSurfaceLockResult::SURFACE_CHANGED) {
+            // Unusable platform information is reported by `recreate()`.
+            let window = info
+                .try_platform_info()
+                .ok()
+                .and_then(|platform_info| platform_info.window().ok());
+            if window != Some(self.window) {
+                return VulkanSurfaceUpdate::RecreateSurface;
+            }
+        }
+        if resized || lock_result.contains(DrawingSurfaceLockResult::BOUNDS_CHANGED) {
+            VulkanSurfaceUpdate::RebuildSwapchain
+        } else {
+            VulkanSurfaceUpdate::Unchanged
+        }
+    }
+
+    /// Creates a surface for the current window of the Canvas and destroys the old one. The old
+    /// surface is kept if the new one cannot be created.
+    ///
+    /// # Safety
+    ///
+    /// Every swapchain created for the surface must have been destroyed.
+    pub unsafe fn recreate(&mut self, info: &DrawingSurfaceInfo) -> Result<(), VulkanSurfaceError> {
+        let platform_info = info.try_platform_info()?;
+        let window = platform_info.window()?;
+        let surface = match &self.platform {
+            PlatformSurface::Xlib(xlib_surface) => {
+                let create_info = vk::XlibSurfaceCreateInfoKHR::default()
+                    .dpy(self.display.cast())
+                    .window(window);
+                xlib_surface.create_xlib_surface(&create_info, None)
+            }
+            #[cfg(feature = "xcb")]
+            PlatformSurface::Xcb(xcb_surface) => {
+                let connection = platform_info
+                    .xcb_connection()
+                    .map_err(WindowRetrievalError::from)?;
+                let create_info = vk::XcbSurfaceCreateInfoKHR::default()
+                    .connection(connection.cast())
+                    .window(window as vk::xcb_window_t);
+                xcb_surface.create_xcb_surface(&create_info, None)
+            }
+        }
+        .map_err(|result| VulkanSurfaceError::vulkan("vkCreateSurfaceKHR", result))?;
+        self.destroy();
+        self.surface = surface;
+        self.window = window;
+        self.size = Self::bounds_size(info);
+        Ok(())
+    }
+
+    fn bounds_size(info: &DrawingSurfaceInfo) -> (u32, u32) {
+        let bounds = info.bounds();
+        (
+            u32::try_from(bounds.width).unwrap_or_default(),
+            u32::try_from(bounds.height).unwrap_or_default(),
+        )
+    }
+
+    fn destroy(&mut self) {
+        if self.surface == vk::SurfaceKHR::null() {
+            return;
+        }
+        unsafe { self.surface_fn.destroy_surface(self.surface, None) };
+        self.surface = vk::SurfaceKHR::null();
+    }
+}
+
+impl Drop for VulkanSurface {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
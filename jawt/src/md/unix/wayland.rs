@@ -0,0 +1,233 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements toolkit detection and Wayland drawing surfaces.
+
+#[cfg(feature = "unstable-wayland")]
+use std::ffi::c_void;
+#[cfg(feature = "unstable-wayland")]
+use std::fmt;
+
+use once_cell::sync::OnceCell;
+
+use crate::dsi::{DrawingSurfaceInfo, PlatformInfoError};
+use crate::environment::Toolkit;
+use crate::md::unix::X11DrawingSurfaceInfo;
+
+/// `struct wl_display`
+#[cfg(feature = "unstable-wayland")]
+pub type WlDisplay = c_void;
+
+/// `struct wl_surface`
+#[cfg(feature = "unstable-wayland")]
+pub type WlSurface = c_void;
+
+/// AWT toolkit that creates drawing surfaces on Unix-like systems. The layout of
+/// `JAWT_DrawingSurfaceInfo.platformInfo` depends on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnixToolkit {
+    /// `XToolkit`, whose platform information is a `JAWT_X11DrawingSurfaceInfo`.
+    X11,
+    /// The native Wayland toolkit of newer JDKs. Its platform information can only be typed, as
+    /// `WaylandDrawingSurfaceInfo`, with the `unstable-wayland` feature.
+    Wayland,
+}
+
+impl UnixToolkit {
+    /// Detects the active toolkit from the native library AWT loaded for it: `libawt_xawt.so`
+    /// for X11 or `libawt_wlawt.so` for Wayland. Returns [None] if neither or both are loaded,
+    /// for example because AWT is headless, no component was created yet, or AWT is linked
+    /// statically. Callers that know the toolkit can use
+    /// [DrawingSurfaceInfo::unix_platform_info_with()] instead.
+    pub fn detect() -> Option<Self> {
+        static TOOLKIT: OnceCell<Option<UnixToolkit>> = OnceCell::new();
+        if let Some(&toolkit) = TOOLKIT.get() {
+            return toolkit;
+        }
+        let x11 = is_loaded(b"libawt_xawt.so\0");
+        let wayland = is_loaded(b"libawt_wlawt.so\0");
+        let toolkit = Self::from_loaded_libraries(x11, wayland);
+        // AWT may load its library later, so the result is only cached once one was found.
+        if x11 || wayland {
+            let _ = TOOLKIT.set(toolkit);
+        }
+        toolkit
+    }
+
+    fn from_loaded_libraries(x11: bool, wayland: bool) -> Option<Self> {
+        match (x11, wayland) {
+            (true, false) => Some(UnixToolkit::X11),
+            (false, true) => Some(UnixToolkit::Wayland),
+            _ => None,
+        }
+    }
+
+    /// Returns the toolkit corresponding to a [Toolkit] reported by
+    /// [probe()](crate::environment::probe()), or [None] if it creates no drawing surfaces on
+    /// Unix-like systems.
+    pub fn from_toolkit(toolkit: &Toolkit) -> Option<Self> {
+        match toolkit {
+            Toolkit::X11 => Some(UnixToolkit::X11),
+            Toolkit::Wayland => Some(UnixToolkit::Wayland),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if the library with the soname `name`, which ends with a NUL, is loaded, without
+/// loading it.
+fn is_loaded(name: &[u8]) -> bool {
+    unsafe {
+        let handle = libc::dlopen(name.as_ptr() as _, libc::RTLD_LAZY | libc::RTLD_NOLOAD);
+        if handle.is_null() {
+            libc::dlerror();
+            return false;
+        }
+        libc::dlclose(handle);
+        true
+    }
+}
+
+/// Platform information of a drawing surface created by the Wayland toolkit, laid out as
+/// `struct { struct wl_display *display; struct wl_surface *surface; }`.
+///
+/// The layout is unverified: no released JDK ships a Wayland toolkit or a `jawt_md.h` declaring
+/// its platform information, so it is assumed from the handles a Wayland client needs. Only the
+/// null checks of [DrawingSurfaceInfo::unix_platform_info()] guard against a different layout, so
+/// it is only available with the `unstable-wayland` feature, and may change or be removed until a
+/// JDK declares it.
+#[cfg(feature = "unstable-wayland")]
+#[repr(C)]
+pub struct WaylandDrawingSurfaceInfo {
+    display: *mut WlDisplay,
+    surface: *mut WlSurface,
+}
+
+#[cfg(feature = "unstable-wayland")]
+impl fmt::Debug for WaylandDrawingSurfaceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaylandDrawingSurfaceInfo")
+            .field("display", &self.display)
+            .field("surface", &self.surface)
+            .finish()
+    }
+}
+
+#[cfg(feature = "unstable-wayland")]
+impl WaylandDrawingSurfaceInfo {
+    /// Creates platform information for a mock JAWT or for tests.
+    pub const fn new(display: *mut WlDisplay, surface: *mut WlSurface) -> Self {
+        Self { display, surface }
+    }
+
+    /// The Wayland display connection shared with AWT. It must not be disconnected.
+    pub fn display(&self) -> *mut WlDisplay {
+        self.display
+    }
+
+    /// The surface of the component.
+    pub fn surface(&self) -> *mut WlSurface {
+        self.surface
+    }
+}
+
+#[cfg(all(feature = "unstable-wayland", feature = "raw-window-handle"))]
+impl WaylandDrawingSurfaceInfo {
+    /// Returns a [raw_window_handle::WaylandDisplayHandle] for the display.
+    pub fn wayland_display_handle(&self) -> Option<raw_window_handle::WaylandDisplayHandle> {
+        Some(raw_window_handle::WaylandDisplayHandle::new(
+            std::ptr::NonNull::new(self.display)?,
+        ))
+    }
+
+    /// Returns a [raw_window_handle::WaylandWindowHandle] for the surface.
+    pub fn wayland_window_handle(&self) -> Option<raw_window_handle::WaylandWindowHandle> {
+        Some(raw_window_handle::WaylandWindowHandle::new(
+            std::ptr::NonNull::new(self.surface)?,
+        ))
+    }
+}
+
+/// Platform information of a drawing surface on Unix-like systems, typed by the toolkit that
+/// created it.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum UnixDrawingSurfaceInfo<'a> {
+    X11(&'a X11DrawingSurfaceInfo),
+    #[cfg(feature = "unstable-wayland")]
+    Wayland(&'a WaylandDrawingSurfaceInfo),
+}
+
+impl<'a> UnixDrawingSurfaceInfo<'a> {
+    pub fn toolkit(self) -> UnixToolkit {
+        match self {
+            UnixDrawingSurfaceInfo::X11(_) => UnixToolkit::X11,
+            #[cfg(feature = "unstable-wayland")]
+            UnixDrawingSurfaceInfo::Wayland(_) => UnixToolkit::Wayland,
+        }
+    }
+
+    pub fn x11(self) -> Option<&'a X11DrawingSurfaceInfo> {
+        match self {
+            UnixDrawingSurfaceInfo::X11(info) => Some(info),
+            #[cfg(feature = "unstable-wayland")]
+            UnixDrawingSurfaceInfo::Wayland(_) => None,
+        }
+    }
+
+    #[cfg(feature = "unstable-wayland")]
+    pub fn wayland(self) -> Option<&'a WaylandDrawingSurfaceInfo> {
+        match self {
+            UnixDrawingSurfaceInfo::X11(_) => None,
+            UnixDrawingSurfaceInfo::Wayland(info) => Some(info),
+        }
+    }
+}
+
+impl DrawingSurfaceInfo<'_> {
+    /// Platform information typed by the toolkit detected with [UnixToolkit::detect()]. Unlike
+    /// [DrawingSurfaceInfo::platform_info()], which always assumes X11, this refuses to
+    /// reinterpret the information if the toolkit is unknown or the information does not look
+    /// like what the toolkit hands out.
+    pub fn unix_platform_info(&self) -> Result<UnixDrawingSurfaceInfo<'_>, PlatformInfoError> {
+        let toolkit = UnixToolkit::detect().ok_or(PlatformInfoError::UnknownToolkit)?;
+        // Safety: the toolkit is the one AWT loaded.
+        unsafe { self.unix_platform_info_with(toolkit) }
+    }
+
+    /// Platform information typed as created by `toolkit`. Returns an error if it is null or its
+    /// pointers and handles are null, and [PlatformInfoError::OtherToolkit] for the Wayland
+    /// toolkit without the `unstable-wayland` feature.
+    ///
+    /// # Safety
+    ///
+    /// `toolkit` must be the toolkit that created the drawing surface, such as the one reported by
+    /// [probe()](crate::environment::probe()).
+    pub unsafe fn unix_platform_info_with(
+        &self,
+        toolkit: UnixToolkit,
+    ) -> Result<UnixDrawingSurfaceInfo<'_>, PlatformInfoError> {
+        let platform_info = self.as_ref().platformInfo;
+        if platform_info.is_null() {
+            return Err(PlatformInfoError::Null);
+        }
+        match toolkit {
+            UnixToolkit::X11 => {
+                let info = unsafe { &*(platform_info as *const X11DrawingSurfaceInfo) };
+                if info.0.display.is_null() || info.0.drawable == 0 {
+                    return Err(PlatformInfoError::InvalidLayout);
+                }
+                Ok(UnixDrawingSurfaceInfo::X11(info))
+            }
+            #[cfg(feature = "unstable-wayland")]
+            UnixToolkit::Wayland => {
+                let info = unsafe { &*(platform_info as *const WaylandDrawingSurfaceInfo) };
+                if info.display.is_null() || info.surface.is_null() {
+                    return Err(PlatformInfoError::InvalidLayout);
+                }
+                Ok(UnixDrawingSurfaceInfo::Wayland(info))
+            }
+            #[cfg(not(feature = "unstable-wayland"))]
+            UnixToolkit::Wayland => Err(PlatformInfoError::OtherToolkit),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements XCB access to X11 drawing surfaces.
+
+use x11_dl::error::OpenError;
+use x11_dl::xlib_xcb::Xlib_xcb;
+
+pub use x11_dl::xlib_xcb::xcb_connection_t;
+
+use crate::md::unix::{LazyLibrary, WindowRetrievalError, X11DrawingSurfaceInfo, X11Error};
+
+/// `xcb_drawable_t`
+pub type XcbDrawable = u32;
+
+/// `xcb_window_t`
+pub type XcbWindow = u32;
+
+/// `xcb_visualid_t`
+pub type XcbVisualId = u32;
+
+/// Returns the process-wide [Xlib_xcb] handle, loading `libX11-xcb` on the first call.
+pub fn xlib_xcb() -> Result<&'static Xlib_xcb, OpenError> {
+    static XLIB_XCB: LazyLibrary<Xlib_xcb> = LazyLibrary::new();
+    XLIB_XCB.get_or_load(Xlib_xcb::open)
+}
+
+impl X11DrawingSurfaceInfo {
+    /// XCB connection underlying [X11DrawingSurfaceInfo::display()], retrieved with
+    /// `XGetXCBConnection`. The connection is owned by the display and must not be disconnected.
+    pub fn xcb_connection(&self) -> Result<*mut xcb_connection_t, X11Error> {
+        let xlib_xcb = xlib_xcb().map_err(X11Error::XcbOpenFailed)?;
+        Ok(unsafe { (xlib_xcb.XGetXCBConnection)(self.0.display) })
+    }
+
+    /// Drawable as an XCB resource ID. X resource IDs always fit in 29 bits.
+    pub fn xcb_drawable(&self) -> XcbDrawable {
+        self.0.drawable as _
+    }
+
+    /// Drawable as an XCB window, if it is a window.
+    pub fn xcb_window(&self) -> Result<XcbWindow, WindowRetrievalError> {
+        Ok(self.window()? as _)
+    }
+
+    /// Visual ID as an XCB visual ID.
+    pub fn xcb_visual_id(&self) -> XcbVisualId {
+        self.0.visualID as _
+    }
+}
+
+#[cfg(feature = "raw-window-handle")]
+impl X11DrawingSurfaceInfo {
+    /// Returns a [raw_window_handle::XcbDisplayHandle] for the XCB connection and the screen of
+    /// the drawable.
+    pub fn xcb_display_handle(&self) -> Result<raw_window_handle::XcbDisplayHandle, X11Error> {
+        Ok(raw_window_handle::XcbDisplayHandle::new(
+            std::ptr::NonNull::new(self.xcb_connection()?),
+            self.screen_number()?,
+        ))
+    }
+
+    /// Returns a [raw_window_handle::XcbWindowHandle] for the drawable, which must be a window.
+    pub fn xcb_window_handle(
+        &self,
+    ) -> Result<raw_window_handle::XcbWindowHandle, WindowRetrievalError> {
+        let window = std::num::NonZeroU32::new(self.xcb_window()?)
+            .ok_or(WindowRetrievalError::DrawableIsNotWindow(self.0.drawable))?;
+        let mut handle = raw_window_handle::XcbWindowHandle::new(window);
+        handle.visual_id = std::num::NonZeroU32::new(self.xcb_visual_id());
+        Ok(handle)
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the shared [Xlib] handle and [XErrorTrap].
+
+use std::cell::Cell;
+use std::error::Error;
+use std::ffi::{c_int, c_ulong};
+use std::fmt;
+use std::ptr;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use once_cell::sync::OnceCell;
+use x11_dl::error::OpenError;
+use x11_dl::xlib::*;
+
+use crate::dsi::PlatformInfoError;
+
+type XErrorHandler = Option<unsafe extern "C" fn(*mut Display, *mut XErrorEvent) -> c_int>;
+
+/// A system library that is loaded on first use and then shared by the whole process.
+///
+/// Failures are not cached, so a later call retries loading the library. This matters for the
+/// optional libraries such as `libGL` and `libEGL`, which the application may load itself after a
+/// first probe failed.
+pub(crate) struct LazyLibrary<T>(OnceCell<T>);
+
+impl<T> LazyLibrary<T> {
+    pub(crate) const fn new() -> Self {
+        Self(OnceCell::new())
+    }
+
+    pub(crate) fn get_or_load<E>(&self, load: impl FnOnce() -> Result<T, E>) -> Result<&T, E> {
+        self.0.get_or_try_init(load)
+    }
+}
+
+/// Returns the process-wide [Xlib] handle, loading `libX11` on the first call.
+pub fn xlib() -> Result<&'static Xlib, OpenError> {
+    static XLIB: LazyLibrary<Xlib> = LazyLibrary::new();
+    XLIB.get_or_load(Xlib::open)
+}
+
+/// An X protocol error captured by an [XErrorTrap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XProtocolError {
+    /// Error code such as [BadWindow] or [BadDrawable].
+    pub error_code: u8,
+    /// Major opcode of the failed request.
+    pub request_code: u8,
+    /// Minor opcode of the failed request.
+    pub minor_code: u8,
+    /// Resource ID of the failed request.
+    pub resource_id: XID,
+    /// Serial number of the failed request.
+    pub serial: c_ulong,
+}
+
+impl XProtocolError {
+    fn from_event(event: &XErrorEvent) -> Self {
+        Self {
+            error_code: event.error_code,
+            request_code: event.request_code,
+            minor_code: event.minor_code,
+            resource_id: event.resourceid,
+            serial: event.serial,
+        }
+    }
+
+    /// Returns the name of the error code if it is one of the core protocol errors.
+    pub fn error_name(&self) -> Option<&'static str> {
+        const NAMES: [&str; 17] = [
+            "BadRequest",
+            "BadValue",
+            "BadWindow",
+            "BadPixmap",
+            "BadAtom",
+            "BadCursor",
+            "BadFont",
+            "BadMatch",
+            "BadDrawable",
+            "BadAccess",
+            "BadAlloc",
+            "BadColor",
+            "BadGC",
+            "BadIDChoice",
+            "BadName",
+            "BadLength",
+            "BadImplementation",
+        ];
+        NAMES
+            .get(usize::from(self.error_code).checked_sub(1)?)
+            .copied()
+    }
+}
+
+impl fmt::Display for XProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error_name() {
+            Some(name) => write!(f, "X protocol error {name}")?,
+            None => write!(f, "X protocol error {}", self.error_code)?,
+        }
+        write!(
+            f,
+            " in request {}.{} on resource {:#x}",
+            self.request_code, self.minor_code, self.resource_id
+        )
+    }
+}
+
+impl Error for XProtocolError {}
+
+/// Error returned by X11 operations performed by this crate.
+#[derive(Debug, Clone)]
+pub enum X11Error {
+    XlibOpenFailed(OpenError),
+    /// `libX11-xcb` could not be loaded. Only the XCB accessors load it.
+    XcbOpenFailed(OpenError),
+    Protocol(XProtocolError),
+    /// The platform information of the drawing surface can't be used as X11.
+    PlatformInfo(PlatformInfoError),
+}
+
+impl fmt::Display for X11Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            X11Error::XlibOpenFailed(open_error) => write!(f, "failed to open X11: {open_error}"),
+            X11Error::XcbOpenFailed(open_error) => {
+                write!(f, "failed to open X11-xcb: {open_error}")
+            }
+            X11Error::Protocol(protocol_error) => protocol_error.fmt(f),
+            X11Error::PlatformInfo(platform_info_error) => platform_info_error.fmt(f),
+        }
+    }
+}
+
+impl Error for X11Error {}
+
+impl From<OpenError> for X11Error {
+    fn from(value: OpenError) -> Self {
+        Self::XlibOpenFailed(value)
+    }
+}
+
+impl From<XProtocolError> for X11Error {
+    fn from(value: XProtocolError) -> Self {
+        Self::Protocol(value)
+    }
+}
+
+impl From<PlatformInfoError> for X11Error {
+    fn from(value: PlatformInfoError) -> Self {
+        Self::PlatformInfo(value)
+    }
+}
+
+struct TrapState {
+    display: *mut Display,
+    previous: XErrorHandler,
+    error: Option<XProtocolError>,
+}
+
+// Safety: `display` is only compared against, never dereferenced.
+unsafe impl Send for TrapState {}
+
+static TRAP_LOCK: Mutex<()> = Mutex::new(());
+
+static TRAP_STATE: Mutex<TrapState> = Mutex::new(TrapState {
+    display: ptr::null_mut(),
+    previous: None,
+    error: None,
+});
+
+thread_local! {
+    static TRAP_ACTIVE: Cell<bool> = const { Cell::new(false) };
+}
+
+fn trap_state() -> MutexGuard<'static, TrapState> {
+    TRAP_STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+unsafe extern "C" fn trap_error_handler(display: *mut Display, event: *mut XErrorEvent) -> c_int {
+    let mut state = trap_state();
+    if state.display != display {
+        // Errors on other connections belong to whoever installed the previous handler.
+        let previous = state.previous;
+        drop(state);
+        return match previous {
+            Some(previous) => previous(display, event),
+            None => 0,
+        };
+    }
+    if state.error.is_none() {
+        state.error = Some(XProtocolError::from_event(&*event));
+    }
+    0
+}
+
+/// A scope in which X protocol errors on a [Display] are captured instead of being delivered to
+/// the process-wide Xlib error handler, which AWT may have set to abort on errors.
+///
+/// Creating a trap flushes pending requests, then installs a temporary error handler. The
+/// previous handler is restored when the trap is dropped. Only one trap can be active in the
+/// process at a time; creating another trap blocks until the active one is dropped, and nesting
+/// traps on the same thread panics.
+///
+/// The error handler is process-wide, so the trap also catches errors raised by requests AWT's
+/// toolkit thread issues on the same display. Holding the AWT lock for the lifetime of the trap,
+/// either by locking the drawing surface or with [Awt::lock()], keeps the toolkit thread off the
+/// display.
+///
+/// [Awt::lock()]: crate::Awt::lock()
+pub struct XErrorTrap {
+    xlib: &'static Xlib,
+    display: *mut Display,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl fmt::Debug for XErrorTrap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XErrorTrap")
+            .field("display", &self.display)
+            .finish()
+    }
+}
+
+impl XErrorTrap {
+    /// Starts trapping X protocol errors on `display`.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid, open connection for the lifetime of the trap. If it is AWT's
+    /// display, the drawing surface must be locked, or [Awt::lock()] held, for the lifetime of the
+    /// trap.
+    ///
+    /// [Awt::lock()]: crate::Awt::lock()
+    pub unsafe fn new(display: *mut Display) -> Result<Self, OpenError> {
+        let xlib = xlib()?;
+        if TRAP_ACTIVE.with(|active| active.replace(true)) {
+            panic!("XErrorTrap cannot be nested");
+        }
+        let lock = TRAP_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Errors of requests issued before the trap should reach the original handler.
+        (xlib.XSync)(display, False);
+
+        // The handler is installed while holding the state, so an error raised on another thread
+        // in between blocks until `previous` is known and can be forwarded.
+        let mut state = trap_state();
+        state.previous = (xlib.XSetErrorHandler)(Some(trap_error_handler));
+        state.display = display;
+        state.error = None;
+        drop(state);
+
+        Ok(Self {
+            xlib,
+            display,
+            _lock: lock,
+        })
+    }
+
+    /// The shared [Xlib] handle.
+    pub fn xlib(&self) -> &'static Xlib {
+        self.xlib
+    }
+
+    /// The display errors are trapped on.
+    pub fn display(&self) -> *mut Display {
+        self.display
+    }
+
+    /// Waits until the server has processed every request issued so far, then returns the first
+    /// error trapped since the last check.
+    pub fn check(&self) -> Result<(), XProtocolError> {
+        unsafe { (self.xlib.XSync)(self.display, False) };
+        match trap_state().error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks for errors and ends the trap.
+    pub fn finish(self) -> Result<(), XProtocolError> {
+        self.check()
+    }
+}
+
+impl Drop for XErrorTrap {
+    fn drop(&mut self) {
+        unsafe { (self.xlib.XSync)(self.display, False) };
+        // As in `new()`, `previous` stays available to concurrent errors until it is restored.
+        let mut state = trap_state();
+        unsafe { (self.xlib.XSetErrorHandler)(state.previous.take()) };
+        state.display = ptr::null_mut();
+        state.error = None;
+        drop(state);
+        TRAP_ACTIVE.with(|active| active.set(false));
+    }
+}
+
+/// Runs `f` inside an [XErrorTrap] on `display` and returns its result, or the first X protocol
+/// error raised by the requests it issued.
+///
+/// # Safety
+///
+/// `display` must be a valid, open connection. If it is AWT's display, the drawing surface must
+/// be locked, or [Awt::lock()] held, until this function returns.
+///
+/// [Awt::lock()]: crate::Awt::lock()
+pub unsafe fn trap_errors<T>(
+    display: *mut Display,
+    f: impl FnOnce(&'static Xlib) -> T,
+) -> Result<T, X11Error> {
+    let trap = XErrorTrap::new(display)?;
+    let result = f(trap.xlib());
+    trap.finish()?;
+    Ok(result)
+}
+
+/// Looks up the [XVisualInfo] of `visual_id` with `XGetVisualInfo`.
+///
+/// # Safety
+///
+/// `display` must be a valid, open connection.
+pub unsafe fn visual_info(
+    display: *mut Display,
+    visual_id: VisualID,
+) -> Result<Option<XVisualInfo>, OpenError> {
+    let xlib = xlib()?;
+    let mut template: XVisualInfo = std::mem::zeroed();
+    template.visualid = visual_id;
+    let mut count = 0;
+    let visual_infos = (xlib.XGetVisualInfo)(display, VisualIDMask, &mut template, &mut count);
+    if visual_infos.is_null() {
+        return Ok(None);
+    }
+    let result = (count > 0).then(|| *visual_infos);
+    (xlib.XFree)(visual_infos.cast());
+    Ok(result)
+}
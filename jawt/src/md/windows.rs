@@ -9,6 +9,8 @@ use windows::Win32::Foundation::{HWND, TRUE};
 use windows::Win32::Graphics::Gdi::{GetObjectType, HBITMAP, HDC, HGDIOBJ, HPALETTE, OBJ_BITMAP};
 use windows::Win32::UI::WindowsAndMessaging::IsWindow;
 
+use crate::dsi::VersionedPlatformInfo;
+
 /// Microsoft Windows specific declarations for AWT native interface.
 #[repr(transparent)]
 pub struct Win32DrawingSurfaceInfo(pub(crate) JAWT_Win32DrawingSurfaceInfo);
@@ -131,6 +133,23 @@ impl Win32DrawingSurfaceInfo {
     }
 }
 
+impl VersionedPlatformInfo<'_> {
+    /// See [Win32DrawingSurfaceInfo::surface_kind()].
+    pub fn surface_kind(&self) -> Option<SurfaceKind> {
+        self.inner().surface_kind()
+    }
+
+    /// See [Win32DrawingSurfaceInfo::hdc()].
+    pub fn hdc(&self) -> HDC {
+        self.inner().hdc()
+    }
+
+    /// See [Win32DrawingSurfaceInfo::hpalette()].
+    pub fn hpalette(&self) -> HPALETTE {
+        self.inner().hpalette()
+    }
+}
+
 impl AsRef<JAWT_Win32DrawingSurfaceInfo> for Win32DrawingSurfaceInfo {
     fn as_ref(&self) -> &JAWT_Win32DrawingSurfaceInfo {
         &self.0
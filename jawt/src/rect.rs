@@ -36,6 +36,96 @@ impl Rect {
     }
 }
 
+impl Rect {
+    /// Returns `true` if the rectangle covers no area.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Returns the area covered by both rectangles, or [None] if they do not overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        let rect = Rect {
+            x,
+            y,
+            width: saturate(right - i64::from(x)),
+            height: saturate(bottom - i64::from(y)),
+        };
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Returns the number of pixels covered by the rectangle, which is zero if it is empty.
+    #[inline(always)]
+    pub const fn area(&self) -> i64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.width as i64 * self.height as i64
+        }
+    }
+
+    /// Returns `true` if `other` lies entirely inside the rectangle. Empty rectangles are contained
+    /// in every rectangle.
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.is_empty()
+            || (self.x <= other.x
+                && self.y <= other.y
+                && other.right() <= self.right()
+                && other.bottom() <= self.bottom())
+    }
+
+    /// Returns the smallest rectangle containing both rectangles. Empty rectangles are ignored.
+    /// The width and height saturate at [i32::MAX] if the union is wider or taller than that.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Rect {
+            x,
+            y,
+            width: saturate(right - i64::from(x)),
+            height: saturate(bottom - i64::from(y)),
+        }
+    }
+
+    /// Returns the rectangle moved by `dx` and `dy`. The position saturates at the bounds of
+    /// [i32].
+    #[inline(always)]
+    pub const fn translate(self, dx: i32, dy: i32) -> Rect {
+        Rect {
+            x: self.x.saturating_add(dx),
+            y: self.y.saturating_add(dy),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // The edges are computed in `i64`, as `x + width` overflows `i32` for rectangles such as the
+    // ones Swing passes to `repaint()` to repaint a whole component.
+    fn right(&self) -> i64 {
+        i64::from(self.x) + i64::from(self.width)
+    }
+
+    fn bottom(&self) -> i64 {
+        i64::from(self.y) + i64::from(self.height)
+    }
+}
+
+fn saturate(value: i64) -> i32 {
+    value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+}
+
 impl From<jawt_Rectangle> for Rect {
     fn from(value: jawt_Rectangle) -> Self {
         Self::from_sys(value)
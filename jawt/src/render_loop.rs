@@ -0,0 +1,395 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! A dedicated render thread for an AWT component.
+//!
+//! [RenderLoop::spawn()] starts a thread attached to the JVM, which creates its own
+//! [DrawingSurface] for the component and drives a [Renderer] with it. Each frame, the drawing
+//! surface is locked only while [Renderer::snapshot()] runs; [Renderer::render()] runs with the
+//! drawing surface unlocked, so neither the EDT nor other components wait for GPU work.
+//!
+//! A `java.awt.Canvas` typically spawns the loop in `addNotify()`, calls [RenderLoop::pause()]
+//! and [RenderLoop::resume()] from `componentHidden()` and `componentShown()`, and calls
+//! [RenderLoop::stop()] in `removeNotify()` before calling `super.removeNotify()`. As
+//! [RenderLoop::stop()] waits for the thread to exit, it must not be called while holding the AWT
+//! lock acquired by [Awt::lock()].
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use jni::objects::{GlobalRef, JObject};
+use jni::{JNIEnv, JavaVM};
+
+use crate::awt::Awt;
+use crate::ds::{DrawingSurface, DrawingSurfaceLockResult};
+use crate::dsi::DrawingSurfaceInfo;
+
+/// Renders the frames of a [RenderLoop]. Created and used only on the render thread.
+pub trait Renderer {
+    /// What [Renderer::render()] needs from the drawing surface.
+    type Snapshot;
+
+    /// Called with the drawing surface locked, which blocks AWT from painting and laying out the
+    /// component. Copy what the frame needs, such as the size of the surface or the handles of a
+    /// re-created one, and return without rendering.
+    fn snapshot(
+        &mut self,
+        lock_result: DrawingSurfaceLockResult,
+        info: &DrawingSurfaceInfo<'_>,
+    ) -> Self::Snapshot;
+
+    /// Renders and presents a frame with the drawing surface unlocked.
+    fn render(&mut self, snapshot: Self::Snapshot) -> Frame;
+}
+
+/// What [Renderer::render()] did, fed back to [Pacing].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Frame {
+    /// A frame was presented.
+    Presented,
+    /// No frame was presented, for example because the component has no area.
+    Skipped,
+}
+
+/// How a [RenderLoop] paces its frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pacing {
+    /// Starts a frame every interval. Slots missed by a slow frame are dropped rather than
+    /// rendered back to back.
+    Interval(Duration),
+    /// Relies on presenting blocking until the vertical blank, as FIFO presentation does. When a
+    /// frame is skipped or returns in less than half of `fallback`, which happens when the window
+    /// is occluded or the presentation doesn't wait, the next frame starts `fallback` after it
+    /// instead.
+    Vsync { fallback: Duration },
+}
+
+impl Pacing {
+    /// Paces frames to `frames_per_second`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames_per_second` is zero.
+    pub fn rate(frames_per_second: u32) -> Self {
+        assert!(frames_per_second > 0, "frame rate must be positive");
+        Self::Interval(Duration::from_secs(1) / frames_per_second)
+    }
+
+    /// Returns when the frame after the one started at `start` should start.
+    fn next_start(&self, previous_deadline: Instant, start: Instant, frame: Frame) -> Instant {
+        let now = Instant::now();
+        match *self {
+            Pacing::Interval(interval) => {
+                let deadline = previous_deadline + interval;
+                if deadline >= now || interval.is_zero() {
+                    return deadline.max(now);
+                }
+                let missed = (now - deadline).as_nanos() / interval.as_nanos() + 1;
+                deadline + interval * u32::try_from(missed).unwrap_or(u32::MAX)
+            }
+            Pacing::Vsync { fallback } => {
+                if frame == Frame::Presented && now - start >= fallback / 2 {
+                    now
+                } else {
+                    start + fallback
+                }
+            }
+        }
+    }
+}
+
+impl Default for Pacing {
+    /// Vsync with a fallback of 60 frames per second.
+    fn default() -> Self {
+        Self::Vsync {
+            fallback: Duration::from_secs(1) / 60,
+        }
+    }
+}
+
+/// Options of [RenderLoop::spawn()].
+#[derive(Debug, Clone)]
+pub struct RenderLoopOptions {
+    pub pacing: Pacing,
+    /// Name of the render thread.
+    pub thread_name: String,
+    /// Whether the loop starts paused, for components that are not shown yet.
+    pub paused: bool,
+}
+
+impl Default for RenderLoopOptions {
+    fn default() -> Self {
+        Self {
+            pacing: Pacing::default(),
+            thread_name: "jawt-render-loop".to_string(),
+            paused: false,
+        }
+    }
+}
+
+/// Error returned by [RenderLoop::spawn()].
+#[derive(Debug)]
+pub enum RenderLoopError {
+    /// The JVM or a global reference to the component could not be retrieved.
+    Jni(jni::errors::Error),
+    /// The render thread could not be spawned.
+    Spawn(io::Error),
+}
+
+impl fmt::Display for RenderLoopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderLoopError::Jni(error) => write!(f, "JNI error: {error}"),
+            RenderLoopError::Spawn(error) => {
+                write!(f, "failed to spawn the render thread: {error}")
+            }
+        }
+    }
+}
+
+impl Error for RenderLoopError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RenderLoopError::Jni(error) => Some(error),
+            RenderLoopError::Spawn(error) => Some(error),
+        }
+    }
+}
+
+impl From<jni::errors::Error> for RenderLoopError {
+    fn from(value: jni::errors::Error) -> Self {
+        Self::Jni(value)
+    }
+}
+
+/// A thread rendering an AWT component. Dropping it stops the thread like [RenderLoop::stop()].
+pub struct RenderLoop {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl fmt::Debug for RenderLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.shared.state();
+        f.debug_struct("RenderLoop")
+            .field("paused", &state.paused)
+            .field("stopping", &state.stopping)
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+impl RenderLoop {
+    /// Spawns a render thread for `component`, which must be a `java.awt.Component` such as a
+    /// Canvas. On the new thread, the drawing surface of `component` is retrieved with `awt`, and
+    /// `create` is called with it to create the [Renderer]. The thread exits without rendering if
+    /// the drawing surface can't be retrieved or `create` returns [None].
+    pub fn spawn<R, F>(
+        env: &JNIEnv,
+        awt: &Awt,
+        component: &JObject,
+        options: RenderLoopOptions,
+        create: F,
+    ) -> Result<Self, RenderLoopError>
+    where
+        R: Renderer,
+        F: FnOnce(&mut JNIEnv, &mut DrawingSurface) -> Option<R> + Send + 'static,
+    {
+        let java_vm = env.get_java_vm()?;
+        let component = env.new_global_ref(component)?;
+        let awt = awt.clone();
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                paused: options.paused,
+                stopping: false,
+            }),
+            condvar: Condvar::new(),
+        });
+        let pacing = options.pacing;
+        let thread = thread::Builder::new()
+            .name(options.thread_name)
+            .spawn({
+                let shared = shared.clone();
+                move || run(java_vm, awt, component, pacing, &shared, create)
+            })
+            .map_err(RenderLoopError::Spawn)?;
+        Ok(Self {
+            shared,
+            thread: Some(thread),
+        })
+    }
+
+    /// Stops rendering after the current frame until [RenderLoop::resume()] is called.
+    pub fn pause(&self) {
+        self.shared.state().paused = true;
+        self.shared.condvar.notify_all();
+    }
+
+    /// Resumes rendering paused by [RenderLoop::pause()] or [RenderLoopOptions::paused].
+    pub fn resume(&self) {
+        self.shared.state().paused = false;
+        self.shared.condvar.notify_all();
+    }
+
+    /// Returns `true` if the loop is paused.
+    pub fn is_paused(&self) -> bool {
+        self.shared.state().paused
+    }
+
+    /// Returns `true` if the render thread has exited.
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().map_or(true, JoinHandle::is_finished)
+    }
+
+    /// Stops the loop after the current frame and waits until the render thread has dropped the
+    /// [Renderer] and the drawing surface and exited. Returns the panic payload if the [Renderer]
+    /// panicked.
+    pub fn stop(mut self) -> thread::Result<()> {
+        self.stop_and_join()
+    }
+
+    fn stop_and_join(&mut self) -> thread::Result<()> {
+        self.shared.state().stopping = true;
+        self.shared.condvar.notify_all();
+        match self.thread.take() {
+            Some(thread) => thread.join(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RenderLoop {
+    fn drop(&mut self) {
+        let _ = self.stop_and_join();
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+struct State {
+    paused: bool,
+    stopping: bool,
+}
+
+/// What the render thread should do next.
+enum Next {
+    Render,
+    /// Render after having been paused.
+    Resume,
+    Stop,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn wait_while_paused(&self) -> Next {
+        let mut state = self.state();
+        let mut resumed = false;
+        while state.paused && !state.stopping {
+            resumed = true;
+            state = self
+                .condvar
+                .wait(state)
+                .unwrap_or_else(|error| error.into_inner());
+        }
+        match (state.stopping, resumed) {
+            (true, _) => Next::Stop,
+            (false, true) => Next::Resume,
+            (false, false) => Next::Render,
+        }
+    }
+
+    /// Sleeps until `deadline`, waking up early when paused or stopped. Returns `false` if the
+    /// loop is stopping.
+    fn sleep_until(&self, deadline: Instant) -> bool {
+        let mut state = self.state();
+        loop {
+            if state.stopping {
+                return false;
+            }
+            let now = Instant::now();
+            if state.paused || now >= deadline {
+                return true;
+            }
+            state = self
+                .condvar
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|error| error.into_inner())
+                .0;
+        }
+    }
+}
+
+fn run<R, F>(
+    java_vm: JavaVM,
+    awt: Awt,
+    component: GlobalRef,
+    pacing: Pacing,
+    shared: &Shared,
+    create: F,
+) where
+    R: Renderer,
+    F: FnOnce(&mut JNIEnv, &mut DrawingSurface) -> Option<R>,
+{
+    let Ok(mut env) = java_vm.attach_current_thread() else {
+        return;
+    };
+    // Safety: `component` outlives the drawing surface, and `Awt::drawing_surface()` doesn't
+    // delete the reference.
+    let target = unsafe { JObject::from_raw(component.as_obj().as_raw()) };
+    let Some(mut drawing_surface) = awt.drawing_surface(&env, target) else {
+        return;
+    };
+    let Some(mut renderer) = create(&mut env, &mut drawing_surface) else {
+        return;
+    };
+
+    // Changes reported by a lock after which no snapshot could be taken.
+    let mut pending = DrawingSurfaceLockResult::empty();
+    let mut deadline = Instant::now();
+    loop {
+        match shared.wait_while_paused() {
+            Next::Render => {}
+            Next::Resume => deadline = Instant::now(),
+            Next::Stop => break,
+        }
+        let start = Instant::now();
+        let frame = render_frame(&mut drawing_surface, &mut renderer, &mut pending);
+        deadline = pacing.next_start(deadline, start, frame);
+        if !shared.sleep_until(deadline) {
+            break;
+        }
+    }
+
+    // Drop the renderer before the drawing surface it renders to, both on this thread.
+    drop(renderer);
+    drop(drawing_surface);
+}
+
+fn render_frame<R: Renderer>(
+    drawing_surface: &mut DrawingSurface,
+    renderer: &mut R,
+    pending: &mut DrawingSurfaceLockResult,
+) -> Frame {
+    let snapshot = {
+        let Some((lock_result, mut guard)) = drawing_surface.lock() else {
+            return Frame::Skipped;
+        };
+        let lock_result = lock_result | *pending;
+        let Some(info) = guard.drawing_surface_info() else {
+            *pending = lock_result;
+            return Frame::Skipped;
+        };
+        *pending = DrawingSurfaceLockResult::empty();
+        renderer.snapshot(lock_result, &info)
+    };
+    renderer.render(snapshot)
+}
@@ -0,0 +1,523 @@
+// Copyright (c) 2025 Gobley Contributors.
+
+//! Implements the validation mode, which checks how JAWT is used and reports misuse as
+//! [Violation]s, much like Vulkan validation layers.
+//!
+//! [wrap()] replaces every function pointer of a [JAWT] with one that checks the call before
+//! forwarding it. [Awt::from_version()] wraps the table it receives when the `validation` feature is
+//! enabled, and a table created by other means, such as a mock, can be wrapped before passing it to
+//! [Awt::from_inner()].
+//!
+//! Drawing surfaces and drawing surface information returned by a wrapped table are copies owned by
+//! this module. This lets it recognize pointers it never returned and pointers to freed drawing
+//! surfaces. The copies of freed drawing surfaces are never deallocated, so that calls through
+//! stale pointers are still caught.
+//!
+//! Calls that would certainly crash or corrupt the state of AWT, such as calls through a freed
+//! drawing surface or an unbalanced unlock, are not forwarded. Other violations are reported and
+//! the call is forwarded.
+//!
+//! [Awt::from_version()]: crate::Awt::from_version()
+//! [Awt::from_inner()]: crate::Awt::from_inner()
+
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::fmt;
+use std::ptr;
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock};
+use std::thread::{self, ThreadId};
+
+use jni::sys::{jboolean, jint, jobject, JNIEnv};
+use once_cell::sync::Lazy;
+
+use crate::sys::*;
+
+type ViolationHandler = Box<dyn Fn(&Violation) + Send + Sync>;
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+static HANDLER: RwLock<Option<ViolationHandler>> = RwLock::new(None);
+
+thread_local! {
+    static AWT_LOCK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Kind of a [Violation].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// A drawing surface pointer that was not returned by [JAWT::GetDrawingSurface] was used.
+    UnknownDrawingSurface,
+    /// A drawing surface was used after it was freed with [JAWT::FreeDrawingSurface].
+    StaleDrawingSurface,
+    /// A drawing surface information pointer that was not returned by
+    /// [JAWT_DrawingSurface::GetDrawingSurfaceInfo], or was already freed, was freed.
+    UnknownDrawingSurfaceInfo,
+    /// A drawing surface was used from a thread other than the one it was created on.
+    ForeignThread { owner: ThreadId },
+    /// [JAWT_DrawingSurface::GetDrawingSurfaceInfo] was called without holding the lock of the
+    /// drawing surface.
+    NotLocked,
+    /// [JAWT_DrawingSurface::Unlock] was called without holding the lock of the drawing surface.
+    UnbalancedUnlock,
+    /// [JAWT_DrawingSurface::Unlock] was called while drawing surface information obtained under
+    /// the lock was not freed.
+    UnlockedWithInfo { outstanding: usize },
+    /// A drawing surface was freed while it was locked.
+    FreedWhileLocked { lock_depth: usize },
+    /// A drawing surface was freed while drawing surface information obtained from it was not
+    /// freed.
+    FreedWithInfo { outstanding: usize },
+    /// [JAWT::Unlock] was called on a thread that does not hold the AWT lock.
+    UnbalancedAwtUnlock,
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDrawingSurface => {
+                write!(
+                    f,
+                    "the drawing surface was not returned by GetDrawingSurface"
+                )
+            }
+            Self::StaleDrawingSurface => write!(f, "the drawing surface was already freed"),
+            Self::UnknownDrawingSurfaceInfo => write!(
+                f,
+                "the drawing surface information was not returned by GetDrawingSurfaceInfo or was \
+                 already freed"
+            ),
+            Self::ForeignThread { owner } => write!(
+                f,
+                "the drawing surface was created on {owner:?} and used on another thread"
+            ),
+            Self::NotLocked => write!(f, "the drawing surface is not locked"),
+            Self::UnbalancedUnlock => write!(f, "the drawing surface is unlocked more than locked"),
+            Self::UnlockedWithInfo { outstanding } => write!(
+                f,
+                "the drawing surface is unlocked while {outstanding} drawing surface information \
+                 is not freed"
+            ),
+            Self::FreedWhileLocked { lock_depth } => write!(
+                f,
+                "the drawing surface is freed while locked {lock_depth} time(s)"
+            ),
+            Self::FreedWithInfo { outstanding } => write!(
+                f,
+                "the drawing surface is freed while {outstanding} drawing surface information is \
+                 not freed"
+            ),
+            Self::UnbalancedAwtUnlock => write!(f, "AWT is unlocked more than locked"),
+        }
+    }
+}
+
+/// Misuse of JAWT detected by the validation mode.
+#[derive(Debug)]
+pub struct Violation {
+    /// What was violated.
+    pub kind: ViolationKind,
+    /// Name of the JAWT function that was called, such as `JAWT_DrawingSurface.Lock`.
+    pub function: &'static str,
+    /// Thread the function was called on.
+    pub thread: ThreadId,
+    /// Backtrace of the call.
+    pub backtrace: Backtrace,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "JAWT validation: {} in {} on {:?}",
+            self.kind, self.function, self.thread
+        )
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Sets the function [Violation]s are reported to, replacing the previous one. By default,
+/// violations are printed to the standard error with their backtraces.
+///
+/// The handler is called from inside JAWT functions, which cannot unwind. A panicking handler
+/// aborts the process.
+pub fn set_violation_handler(handler: impl Fn(&Violation) + Send + Sync + 'static) {
+    *HANDLER.write().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(handler));
+}
+
+fn report(kind: ViolationKind, function: &'static str) {
+    let violation = Violation {
+        kind,
+        function,
+        thread: thread::current().id(),
+        backtrace: Backtrace::force_capture(),
+    };
+    match &*HANDLER.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(handler) => handler(&violation),
+        None => eprintln!("{violation}\n{}", violation.backtrace),
+    }
+}
+
+#[derive(Default)]
+struct State {
+    awt: Functions,
+    /// Drawing surfaces by the address of their copies.
+    surfaces: HashMap<usize, Surface>,
+    /// Drawing surface information by the address of their copies.
+    infos: HashMap<usize, Info>,
+}
+
+/// Functions of the wrapped [JAWT]. Tables of every version AWT returned are merged, as the
+/// trampolines cannot tell which table they belong to.
+#[derive(Default)]
+struct Functions {
+    get_drawing_surface:
+        Option<unsafe extern "C" fn(*mut JNIEnv, jobject) -> *mut JAWT_DrawingSurface>,
+    free_drawing_surface: Option<unsafe extern "C" fn(*mut JAWT_DrawingSurface)>,
+    lock: Option<unsafe extern "C" fn(*mut JNIEnv)>,
+    unlock: Option<unsafe extern "C" fn(*mut JNIEnv)>,
+    get_component: Option<unsafe extern "C" fn(*mut JNIEnv, *mut c_void) -> jobject>,
+    create_embedded_frame: Option<unsafe extern "C" fn(*mut JNIEnv, *mut c_void) -> jobject>,
+    set_bounds: Option<unsafe extern "C" fn(*mut JNIEnv, jobject, jint, jint, jint, jint)>,
+    synthesize_window_activation: Option<unsafe extern "C" fn(*mut JNIEnv, jobject, jboolean)>,
+}
+
+struct Surface {
+    /// Address of the drawing surface returned by AWT, or [None] once it is freed.
+    real: Option<usize>,
+    owner: ThreadId,
+    lock_depth: usize,
+    outstanding_infos: usize,
+}
+
+struct Info {
+    /// Address of the drawing surface information returned by AWT.
+    real: usize,
+    /// Address of the copy of the drawing surface the information was obtained from.
+    surface: usize,
+}
+
+fn state() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns a copy of `jawt` whose functions check each call before forwarding it to `jawt`.
+/// Functions missing in `jawt` stay missing.
+///
+/// # Safety
+///
+/// `jawt` must contain valid function pointers. Only one JAWT implementation may be wrapped in a
+/// process, as the functions of every wrapped table are shared.
+pub unsafe fn wrap(jawt: JAWT) -> JAWT {
+    let mut state = state();
+    let awt = &mut state.awt;
+    macro_rules! merge {
+        ($($field:ident => $trampoline:ident: $awt_field:ident,)*) => {
+            JAWT {
+                version: jawt.version,
+                $($field: jawt.$field.map(|function| {
+                    awt.$awt_field = Some(function);
+                    $trampoline as _
+                }),)*
+            }
+        };
+    }
+    merge! {
+        GetDrawingSurface => get_drawing_surface: get_drawing_surface,
+        FreeDrawingSurface => free_drawing_surface: free_drawing_surface,
+        Lock => lock_awt: lock,
+        Unlock => unlock_awt: unlock,
+        GetComponent => get_component: get_component,
+        CreateEmbeddedFrame => create_embedded_frame: create_embedded_frame,
+        SetBounds => set_bounds: set_bounds,
+        SynthesizeWindowActivation => synthesize_window_activation: synthesize_window_activation,
+    }
+}
+
+/// Looks up the drawing surface at `ds`, reporting it if it is unknown, freed or owned by another
+/// thread. Returns the address of the drawing surface AWT returned if it can be used.
+fn check_surface(
+    state: &State,
+    ds: *mut JAWT_DrawingSurface,
+) -> Result<(usize, Vec<ViolationKind>), ViolationKind> {
+    let surface = state
+        .surfaces
+        .get(&(ds as usize))
+        .ok_or(ViolationKind::UnknownDrawingSurface)?;
+    let real = surface.real.ok_or(ViolationKind::StaleDrawingSurface)?;
+    let mut violations = Vec::new();
+    if surface.owner != thread::current().id() {
+        violations.push(ViolationKind::ForeignThread {
+            owner: surface.owner,
+        });
+    }
+    Ok((real, violations))
+}
+
+fn report_all(violations: Vec<ViolationKind>, function: &'static str) {
+    for violation in violations {
+        report(violation, function);
+    }
+}
+
+unsafe extern "C" fn get_drawing_surface(
+    env: *mut JNIEnv,
+    target: jobject,
+) -> *mut JAWT_DrawingSurface {
+    let Some(function) = state().awt.get_drawing_surface else {
+        return ptr::null_mut();
+    };
+    let real = unsafe { function(env, target) };
+    if real.is_null() {
+        return real;
+    }
+
+    let copy = Box::into_raw(Box::new(JAWT_DrawingSurface {
+        env: unsafe { (*real).env },
+        target: unsafe { (*real).target },
+        Lock: Some(lock_surface),
+        GetDrawingSurfaceInfo: Some(get_drawing_surface_info),
+        FreeDrawingSurfaceInfo: Some(free_drawing_surface_info),
+        Unlock: Some(unlock_surface),
+    }));
+    state().surfaces.insert(
+        copy as usize,
+        Surface {
+            real: Some(real as usize),
+            owner: thread::current().id(),
+            lock_depth: 0,
+            outstanding_infos: 0,
+        },
+    );
+    copy
+}
+
+unsafe extern "C" fn free_drawing_surface(ds: *mut JAWT_DrawingSurface) {
+    const FUNCTION: &str = "JAWT.FreeDrawingSurface";
+    let mut state = state();
+    let (real, mut violations) = match check_surface(&state, ds) {
+        Ok(checked) => checked,
+        Err(violation) => {
+            drop(state);
+            return report(violation, FUNCTION);
+        }
+    };
+    let surface = state.surfaces.get_mut(&(ds as usize)).unwrap();
+    if surface.lock_depth > 0 {
+        violations.push(ViolationKind::FreedWhileLocked {
+            lock_depth: surface.lock_depth,
+        });
+    }
+    if surface.outstanding_infos > 0 {
+        violations.push(ViolationKind::FreedWithInfo {
+            outstanding: surface.outstanding_infos,
+        });
+    }
+    surface.real = None;
+    let function = state.awt.free_drawing_surface;
+    drop(state);
+
+    report_all(violations, FUNCTION);
+    if let Some(function) = function {
+        unsafe { function(real as *mut JAWT_DrawingSurface) };
+    }
+}
+
+unsafe extern "C" fn lock_awt(env: *mut JNIEnv) {
+    let Some(function) = state().awt.lock else {
+        return;
+    };
+    unsafe { function(env) };
+    AWT_LOCK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+}
+
+unsafe extern "C" fn unlock_awt(env: *mut JNIEnv) {
+    let locked = AWT_LOCK_DEPTH.with(|depth| {
+        let locked = depth.get() > 0;
+        depth.set(depth.get().saturating_sub(1));
+        locked
+    });
+    if !locked {
+        return report(ViolationKind::UnbalancedAwtUnlock, "JAWT.Unlock");
+    }
+    if let Some(function) = state().awt.unlock {
+        unsafe { function(env) };
+    }
+}
+
+unsafe extern "C" fn get_component(env: *mut JNIEnv, platform_info: *mut c_void) -> jobject {
+    match state().awt.get_component {
+        Some(function) => unsafe { function(env, platform_info) },
+        None => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn create_embedded_frame(
+    env: *mut JNIEnv,
+    platform_info: *mut c_void,
+) -> jobject {
+    match state().awt.create_embedded_frame {
+        Some(function) => unsafe { function(env, platform_info) },
+        None => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn set_bounds(
+    env: *mut JNIEnv,
+    embedded_frame: jobject,
+    x: jint,
+    y: jint,
+    width: jint,
+    height: jint,
+) {
+    if let Some(function) = state().awt.set_bounds {
+        unsafe { function(env, embedded_frame, x, y, width, height) };
+    }
+}
+
+unsafe extern "C" fn synthesize_window_activation(
+    env: *mut JNIEnv,
+    embedded_frame: jobject,
+    activate: jboolean,
+) {
+    if let Some(function) = state().awt.synthesize_window_activation {
+        unsafe { function(env, embedded_frame, activate) };
+    }
+}
+
+unsafe extern "C" fn lock_surface(ds: *mut JAWT_DrawingSurface) -> jint {
+    const FUNCTION: &str = "JAWT_DrawingSurface.Lock";
+    let checked = check_surface(&state(), ds);
+    let (real, violations) = match checked {
+        Ok(checked) => checked,
+        Err(violation) => {
+            report(violation, FUNCTION);
+            return JAWT_LOCK_ERROR;
+        }
+    };
+    report_all(violations, FUNCTION);
+
+    let real = real as *mut JAWT_DrawingSurface;
+    let Some(function) = (unsafe { (*real).Lock }) else {
+        return JAWT_LOCK_ERROR;
+    };
+    let lock_result = unsafe { function(real) };
+    if lock_result & JAWT_LOCK_ERROR == 0 {
+        if let Some(surface) = state().surfaces.get_mut(&(ds as usize)) {
+            surface.lock_depth += 1;
+        }
+    }
+    lock_result
+}
+
+unsafe extern "C" fn get_drawing_surface_info(
+    ds: *mut JAWT_DrawingSurface,
+) -> *mut JAWT_DrawingSurfaceInfo {
+    const FUNCTION: &str = "JAWT_DrawingSurface.GetDrawingSurfaceInfo";
+    let state_guard = state();
+    let (real, mut violations) = match check_surface(&state_guard, ds) {
+        Ok(checked) => checked,
+        Err(violation) => {
+            drop(state_guard);
+            report(violation, FUNCTION);
+            return ptr::null_mut();
+        }
+    };
+    let locked = state_guard.surfaces[&(ds as usize)].lock_depth > 0;
+    drop(state_guard);
+    if !locked {
+        violations.push(ViolationKind::NotLocked);
+    }
+    report_all(violations, FUNCTION);
+    if !locked {
+        return ptr::null_mut();
+    }
+
+    let real = real as *mut JAWT_DrawingSurface;
+    let Some(function) = (unsafe { (*real).GetDrawingSurfaceInfo }) else {
+        return ptr::null_mut();
+    };
+    let real_info = unsafe { function(real) };
+    if real_info.is_null() {
+        return real_info;
+    }
+
+    let copy = Box::into_raw(Box::new(JAWT_DrawingSurfaceInfo {
+        ds,
+        ..unsafe { ptr::read(real_info) }
+    }));
+    let mut state = state();
+    state.infos.insert(
+        copy as usize,
+        Info {
+            real: real_info as usize,
+            surface: ds as usize,
+        },
+    );
+    if let Some(surface) = state.surfaces.get_mut(&(ds as usize)) {
+        surface.outstanding_infos += 1;
+    }
+    copy
+}
+
+unsafe extern "C" fn free_drawing_surface_info(dsi: *mut JAWT_DrawingSurfaceInfo) {
+    const FUNCTION: &str = "JAWT_DrawingSurface.FreeDrawingSurfaceInfo";
+    let mut state = state();
+    let Some(info) = state.infos.remove(&(dsi as usize)) else {
+        drop(state);
+        return report(ViolationKind::UnknownDrawingSurfaceInfo, FUNCTION);
+    };
+    let checked = check_surface(&state, info.surface as *mut _);
+    if let Some(surface) = state.surfaces.get_mut(&info.surface) {
+        surface.outstanding_infos = surface.outstanding_infos.saturating_sub(1);
+    }
+    drop(state);
+    // Safety: the copy was created by `get_drawing_surface_info`.
+    drop(unsafe { Box::from_raw(dsi) });
+
+    let real_ds = match checked {
+        Ok((real_ds, violations)) => {
+            report_all(violations, FUNCTION);
+            real_ds as *mut JAWT_DrawingSurface
+        }
+        // The drawing surface the information belongs to is already freed.
+        Err(violation) => return report(violation, FUNCTION),
+    };
+    if let Some(function) = unsafe { (*real_ds).FreeDrawingSurfaceInfo } {
+        unsafe { function(info.real as *mut JAWT_DrawingSurfaceInfo) };
+    }
+}
+
+unsafe extern "C" fn unlock_surface(ds: *mut JAWT_DrawingSurface) {
+    const FUNCTION: &str = "JAWT_DrawingSurface.Unlock";
+    let mut state = state();
+    let (real, mut violations) = match check_surface(&state, ds) {
+        Ok(checked) => checked,
+        Err(violation) => {
+            drop(state);
+            return report(violation, FUNCTION);
+        }
+    };
+    let surface = state.surfaces.get_mut(&(ds as usize)).unwrap();
+    let locked = surface.lock_depth > 0;
+    if !locked {
+        violations.push(ViolationKind::UnbalancedUnlock);
+    } else if surface.outstanding_infos > 0 {
+        violations.push(ViolationKind::UnlockedWithInfo {
+            outstanding: surface.outstanding_infos,
+        });
+    }
+    surface.lock_depth = surface.lock_depth.saturating_sub(1);
+    drop(state);
+
+    report_all(violations, FUNCTION);
+    if !locked {
+        return;
+    }
+    let real = real as *mut JAWT_DrawingSurface;
+    if let Some(function) = unsafe { (*real).Unlock } {
+        unsafe { function(real) };
+    }
+}
@@ -28,6 +28,12 @@ impl AwtVersion {
         self.0
     }
 
+    /// Returns `true` if this version is `version` or later. Flags such as
+    /// `JAWT_MACOSX_USE_CALAYER`, which is stored in the sign bit, are ignored.
+    pub const fn is_at_least(self, version: AwtVersion) -> bool {
+        (self.0 & jint::MAX) >= (version.0 & jint::MAX)
+    }
+
     pub const VERSION_1_3: Self = Self(JAWT_VERSION_1_3);
     #[cfg(feature = "java-1-4")]
     pub const VERSION_1_4: Self = Self(JAWT_VERSION_1_4);